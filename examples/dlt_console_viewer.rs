@@ -15,53 +15,41 @@ fn main() -> std::io::Result<()> {
     println!("{}", "=".repeat(80));
     
     let mut message_count = 0u32;
-    let mut buffer = [0u8; 4096];
+    let mut decoder = DltStreamDecoder::<4096>::new();
 
-    loop {       
-        // Read standard header (4 bytes)
-        let mut std_header = [0u8; 4];
-        match stream.read_exact(&mut std_header) {
-            Ok(_) => {},
-            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock || 
+    loop {
+        // Read whatever is available; messages may be split across reads
+        let read = match stream.read(decoder.read_buffer()) {
+            Ok(0) => {
+                eprintln!("\n❌ Connection closed by dlt-daemon");
+                break;
+            }
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock ||
                       e.kind() == std::io::ErrorKind::TimedOut => {
                 // No data available, wait and try again
                 std::thread::sleep(Duration::from_millis(100));
                 continue;
             }
             Err(e) => {
-                eprintln!("\n❌ Error reading standard header: {}", e);
+                eprintln!("\n❌ Error reading from stream: {}", e);
                 break;
             }
-        }
-
-        // Extract message length (bytes 2-3 of standard header, big-endian)
-        let msg_len = u16::from_be_bytes([std_header[2], std_header[3]]) as usize;
-        
-        if msg_len < 4 || msg_len > buffer.len() {
-            eprintln!("⚠️  Invalid message length: {}", msg_len);
-            continue;
-        }
-
-        // Copy standard header to buffer
-        buffer[0..4].copy_from_slice(&std_header);
+        };
+        decoder.commit(read);
 
-        // Read rest of message
-        let remaining = msg_len - 4;
-        if remaining > 0 {
-            match stream.read_exact(&mut buffer[4..4 + remaining]) {
-                Ok(_) => {},
-                Err(e) => {
-                    eprintln!("❌ Error reading message body: {}", e);
-                    continue;
+        // Analyze all complete messages
+        loop {
+            match decoder.next_frame() {
+                Ok(Some(frame)) => {
+                    let message = frame.strip_prefix(&DLT_SERIAL_HEADER_ARRAY[..]).unwrap_or(frame);
+                    message_count += 1;
+                    analyze_and_display(message, message_count);
                 }
+                Ok(None) => break,
+                Err(e) => eprintln!("⚠️  Stream error: {:?}", e),
             }
         }
-
-        // Analyze the message
-        message_count += 1;
-        let message = &buffer[..msg_len];
-        
-        analyze_and_display(message, message_count);
     }
 
     Ok(())
@@ -37,7 +37,7 @@ fn main() {
                 if let Some(value) = args[i] {
                     print!("  Arg {}: ", i);
                     match value {
-                        DltValue::String(s) => println!("String(\"{}\")", s),
+                        DltValue::String(s) => println!("String(\"{}\", {:?})", s, s.coding()),
                         DltValue::F32(v) => println!("Float({:.2})", v),
                         DltValue::U32(v) => println!("U32({})", v),
                         DltValue::Bool(v) => println!("Bool({})", v),
@@ -51,6 +51,10 @@ fn main() {
                         DltValue::U128(v) => println!("U128({})", v),
                         DltValue::F64(v) => println!("F64({:.6})", v),
                         DltValue::Raw(bytes) => println!("Raw({:?})", bytes),
+                        DltValue::Array(array) => println!("Array({} elements)", array.len()),
+                        DltValue::Struct(fields) => println!("Struct({} entries)", fields.len()),
+                        DltValue::FixedPoint(v) => println!("FixedPoint({:.3})", v.physical()),
+                        DltValue::TraceInfo(name) => println!("TraceInfo(\"{}\")", name),
                     }
                 }
             }
@@ -101,7 +105,7 @@ fn handle_incoming_packet(payload_data: &[u8], expected_args: u8) {
                         let _ = v;
                     }
                     DltValue::String(s) => {
-                        // Process string value
+                        // Process string value (s.as_str(), s.chars(), s.coding())
                         let _ = s;
                     }
                     DltValue::F32(v) => {
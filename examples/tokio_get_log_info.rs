@@ -0,0 +1,71 @@
+// Async GetLogInfo client using the Tokio codec
+//
+// Same as `test_get_log_info`, but framing is done by `DltCodec` instead of a
+// hand-written read loop.
+//
+// Usage:
+// 1. Start the daemon: cargo run --example dlt_daemon_simple
+// 2. Run this example: cargo run --example tokio_get_log_info --features tokio
+
+use std::time::Duration;
+
+use dlt_protocol::r19_11::*;
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_util::codec::Framed;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> std::io::Result<()> {
+    println!("🧪 Testing GetLogInfo Request (async)");
+    println!("Connecting to daemon at localhost:3490...\n");
+
+    let stream = TcpStream::connect("localhost:3490").await?;
+    let mut framed = Framed::new(stream, DltCodec::new());
+    println!("✅ Connected!");
+
+    let mut buffer = [0u8; 256];
+    let mut builder = DltServiceMessageBuilder::new()
+        .with_ecu_id(b"TEST")
+        .with_app_id(b"TEST")
+        .with_context_id(b"TST1");
+
+    // Request all apps and contexts (wildcard)
+    let len = builder
+        .generate_get_log_info_request(&mut buffer, 7, &[0, 0, 0, 0], &[0, 0, 0, 0])
+        .map_err(|e| std::io::Error::other(format!("{:?}", e)))?;
+    framed.send(&buffer[..len]).await?;
+    println!("📤 GetLogInfo request sent, waiting for response...\n");
+
+    loop {
+        let frame = match tokio::time::timeout(Duration::from_secs(5), framed.next()).await {
+            Ok(Some(frame)) => frame?,
+            Ok(None) => break,
+            Err(_) => {
+                eprintln!("⏱️  No response within 5 seconds");
+                break;
+            }
+        };
+
+        let message = frame.message();
+        let is_control = message
+            .extended_header
+            .is_some_and(|ext| ext.message_type() == MstpType::DltTypeControl);
+        if !is_control {
+            println!("  ✓ Skipping non-control message ({} bytes)", frame.as_bytes().len());
+            continue;
+        }
+
+        let service_parser = DltServiceParser::new(message.payload);
+        if let Ok(ServiceId::GetLogInfo) = service_parser.parse_service_id() {
+            match service_parser.parse_get_log_info_response() {
+                Ok((status, data)) => {
+                    println!("📋 GetLogInfo Response - Status: {:?} ({} bytes)", status, data.len());
+                }
+                Err(e) => eprintln!("❌ Failed to parse response: {:?}", e),
+            }
+            break;
+        }
+    }
+
+    Ok(())
+}
@@ -9,3 +9,6 @@ fn panic(_info: &core::panic::PanicInfo) -> ! {
 
 pub mod r19_11;
 pub use r19_11::*;
+pub mod r22_11;
+pub mod version;
+pub use version::*;
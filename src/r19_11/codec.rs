@@ -0,0 +1,199 @@
+//! # Tokio Codec (requires `tokio` feature)
+//!
+//! [`DltCodec`] implements `tokio_util::codec::Decoder` and `Encoder`, so a
+//! `TcpStream` (or any `AsyncRead + AsyncWrite`) can be wrapped in `Framed` to
+//! receive and send DLT messages without hand-written read loops.
+//!
+//! - Decoding yields [`DltFrame`]s: owned, validated message bytes that can be
+//!   parsed with [`DltFrame::message`] at any time. Messages with and without
+//!   serial header are accepted. Invalid data is skipped (see
+//!   [`DltCodec::skipped_bytes`]), as with [`DltStreamDecoder`].
+//! - Encoding accepts complete messages as produced by `DltMessageBuilder`
+//!   (`&[u8]`) or received frames. With [`DltCodec::with_serial_header`] a
+//!   serial header is added to messages that don't have one.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//! use futures::{SinkExt, StreamExt};
+//! use tokio::net::TcpStream;
+//! use tokio_util::codec::Framed;
+//!
+//! # async fn run() -> std::io::Result<()> {
+//! let stream = TcpStream::connect("localhost:3490").await?;
+//! let mut framed = Framed::new(stream, DltCodec::new());
+//!
+//! let mut builder = DltServiceMessageBuilder::new();
+//! let mut buffer = [0u8; 64];
+//! let size = builder.generate_get_log_info_request(&mut buffer, 7, &[0; 4], &[0; 4]).unwrap();
+//! framed.send(&buffer[..size]).await?;
+//!
+//! while let Some(frame) = framed.next().await {
+//!     let frame = frame?;
+//!     println!("Payload: {:?}", frame.message().payload);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::r19_11::*;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use std::io;
+use tokio_util::codec::{Decoder, Encoder};
+
+// ========================================
+// Owned Frame
+// ========================================
+
+/// Owned bytes of one complete DLT message (including a serial header, if any)
+///
+/// The message is validated on construction, so it can always be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DltFrame {
+    data: Bytes,
+}
+
+impl DltFrame {
+    /// Create a frame from complete message bytes
+    ///
+    /// # Returns
+    /// - `Ok(DltFrame)`: `data` holds exactly one valid message
+    /// - `Err(DltHeaderError)`: Invalid header, or the length doesn't match `data`
+    pub fn from_bytes(data: impl Into<Bytes>) -> Result<Self, DltHeaderError> {
+        let data = data.into();
+        let size = frame_size(&data, None)?;
+        if size != data.len() {
+            return Err(DltHeaderError::InvalidLength);
+        }
+        Ok(Self { data })
+    }
+
+    /// Parse the message
+    pub fn message(&self) -> DltMessage<'_> {
+        DltHeaderParser::new(&self.data)
+            .parse_message()
+            .expect("frame validated on construction")
+    }
+
+    /// Whether the frame starts with a serial header
+    pub fn has_serial_header(&self) -> bool {
+        self.data.starts_with(&DLT_SERIAL_HEADER_ARRAY)
+    }
+
+    /// Message bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Consume the frame, returning the message bytes
+    pub fn into_bytes(self) -> Bytes {
+        self.data
+    }
+}
+
+impl AsRef<[u8]> for DltFrame {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+// ========================================
+// Codec
+// ========================================
+
+/// Tokio codec framing DLT v1 messages
+#[derive(Debug, Default)]
+pub struct DltCodec {
+    /// Add a serial header when encoding
+    serial_header: bool,
+    /// Whether the received stream uses serial headers (learned from the first message)
+    serial: Option<bool>,
+    /// Total bytes skipped while resynchronizing
+    skipped: usize,
+}
+
+impl DltCodec {
+    /// Create a codec that writes messages unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prefix encoded messages with a serial header ("DLS\x01") if they lack one
+    pub fn with_serial_header(mut self) -> Self {
+        self.serial_header = true;
+        self
+    }
+
+    /// Total number of received bytes skipped because they didn't form a valid message
+    pub fn skipped_bytes(&self) -> usize {
+        self.skipped
+    }
+}
+
+impl Decoder for DltCodec {
+    type Item = DltFrame;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        while !src.is_empty() {
+            match frame_size(src, self.serial) {
+                Ok(size) if src.len() < size => {
+                    src.reserve(size - src.len());
+                    return Ok(None);
+                }
+                Ok(size) => {
+                    self.serial.get_or_insert(src.starts_with(&DLT_SERIAL_HEADER_ARRAY));
+                    let data = src.split_to(size).freeze();
+                    return Ok(Some(DltFrame { data }));
+                }
+                Err(DltHeaderError::BufferTooSmall) => return Ok(None),
+                Err(_) => {
+                    src.advance(1);
+                    self.skipped += 1;
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Encoder<&[u8]> for DltCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: &[u8], dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let size = frame_size(item, None)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, format!("{:?}", e)))?;
+        if size != item.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "message length doesn't match its length field",
+            ));
+        }
+
+        let add_serial = self.serial_header && !item.starts_with(&DLT_SERIAL_HEADER_ARRAY);
+        dst.reserve(item.len() + if add_serial { DLT_SERIAL_HEADER_SIZE } else { 0 });
+        if add_serial {
+            dst.put_slice(&DLT_SERIAL_HEADER_ARRAY);
+        }
+        dst.put_slice(item);
+        Ok(())
+    }
+}
+
+impl Encoder<&DltFrame> for DltCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: &DltFrame, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode(item.as_bytes(), dst)
+    }
+}
+
+impl Encoder<DltFrame> for DltCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: DltFrame, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode(item.as_bytes(), dst)
+    }
+}
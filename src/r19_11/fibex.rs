@@ -0,0 +1,684 @@
+//! # FIBEX Message Catalogs (requires `std` feature)
+//!
+//! Loads non-verbose message descriptions from FIBEX XML files in the layout
+//! used by dlt-viewer's non-verbose plugin:
+//!
+//! - Every `FRAME` with ID `ID_<message id>` describes one message. Its
+//!   `MANUFACTURER-EXTENSION` holds `APPLICATION_ID`, `CONTEXT_ID` and
+//!   `MESSAGE_INFO` (log level).
+//! - The frame's `PDU-INSTANCES` reference PDUs in sequence order. PDUs without
+//!   signals contribute their `DESC` to the format text; PDUs with a
+//!   `SIGNAL-INSTANCE` describe one argument each.
+//! - Signals are resolved to argument types by their dlt-viewer ID (`S_UINT32`,
+//!   `S_STRG_ASCII`, ...) or, failing that, by the `BASE-DATA-TYPE` of their coding.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//!
+//! let catalog = DltFibexCatalog::from_file("ecu1.xml").unwrap();
+//!
+//! let data: &[u8] = &[/* DLT packet bytes */];
+//! let message = DltHeaderParser::new(data).parse_message().unwrap();
+//!
+//! let decoded = decode_non_verbose(&catalog, &message).unwrap();
+//! println!("{} {:?}", decoded.text(), decoded.args().collect::<Vec<_>>());
+//! ```
+
+use crate::r19_11::*;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+
+// ========================================
+// FIBEX Error Type
+// ========================================
+
+/// Error while loading a FIBEX file
+#[derive(Debug)]
+pub enum DltFibexError {
+    /// Reading the file failed
+    Io(io::Error),
+    /// The XML is malformed (byte offset of the problem)
+    InvalidXml(usize),
+    /// A frame ID is not of the form `ID_<message id>`
+    InvalidMessageId(String),
+    /// A PDU or signal reference points to an unknown element
+    MissingReference(String),
+    /// A signal has no known argument type
+    UnsupportedSignal(String),
+}
+
+impl fmt::Display for DltFibexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DltFibexError::Io(e) => write!(f, "failed to read FIBEX file: {}", e),
+            DltFibexError::InvalidXml(offset) => write!(f, "malformed XML at byte {}", offset),
+            DltFibexError::InvalidMessageId(id) => write!(f, "invalid frame ID \"{}\"", id),
+            DltFibexError::MissingReference(id) => write!(f, "unknown reference \"{}\"", id),
+            DltFibexError::UnsupportedSignal(id) => write!(f, "unsupported signal \"{}\"", id),
+        }
+    }
+}
+
+impl std::error::Error for DltFibexError {}
+
+impl From<io::Error> for DltFibexError {
+    fn from(e: io::Error) -> Self {
+        DltFibexError::Io(e)
+    }
+}
+
+// ========================================
+// FIBEX Catalog
+// ========================================
+
+/// Owned description of one non-verbose message loaded from FIBEX
+#[derive(Debug, Clone, PartialEq)]
+pub struct DltFibexFrame {
+    /// Message ID at the start of the payload
+    pub message_id: u32,
+    /// Application ID of the message
+    pub app_id: [u8; DLT_ID_SIZE],
+    /// Context ID of the message
+    pub context_id: [u8; DLT_ID_SIZE],
+    /// Log level of the message
+    pub log_level: MtinTypeDltLog,
+    /// Format text (the `DESC` of all text PDUs, joined by spaces)
+    pub text: String,
+    /// Argument types in payload order
+    pub args: Vec<DltArgType>,
+}
+
+impl DltFibexFrame {
+    /// Borrow as a catalog frame
+    pub fn as_frame(&self) -> DltNonVerboseFrame<'_> {
+        DltNonVerboseFrame {
+            message_id: self.message_id,
+            app_id: self.app_id,
+            context_id: self.context_id,
+            log_level: self.log_level,
+            text: &self.text,
+            args: &self.args,
+        }
+    }
+}
+
+/// Message catalog loaded from a FIBEX file
+#[derive(Debug, Clone, Default)]
+pub struct DltFibexCatalog {
+    frames: Vec<DltFibexFrame>,
+    index: HashMap<u32, usize>,
+}
+
+impl DltFibexCatalog {
+    /// Load a catalog from a FIBEX file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, DltFibexError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Load a catalog from FIBEX XML text
+    pub fn parse(xml: &str) -> Result<Self, DltFibexError> {
+        let root = XmlElement::parse(xml)?;
+
+        let mut pdus = HashMap::new();
+        let mut signals = HashMap::new();
+        let mut codings = HashMap::new();
+        let mut frames = Vec::new();
+        root.visit(&mut |element| {
+            let id = element.attribute("ID");
+            match (element.name.as_str(), id) {
+                ("PDU", Some(id)) => {
+                    pdus.insert(id, element);
+                }
+                ("SIGNAL", Some(id)) => {
+                    signals.insert(id, element);
+                }
+                ("CODING", Some(id)) => {
+                    codings.insert(id, element);
+                }
+                ("FRAME", Some(_)) => frames.push(element),
+                _ => {}
+            }
+        });
+
+        let mut catalog = Self::default();
+        for frame in frames {
+            catalog.insert(parse_frame(frame, &pdus, &signals, &codings)?);
+        }
+        Ok(catalog)
+    }
+
+    /// Add a frame, replacing any frame with the same message ID
+    pub fn insert(&mut self, frame: DltFibexFrame) {
+        match self.index.get(&frame.message_id) {
+            Some(&position) => self.frames[position] = frame,
+            None => {
+                self.index.insert(frame.message_id, self.frames.len());
+                self.frames.push(frame);
+            }
+        }
+    }
+
+    /// All frames in file order
+    pub fn frames(&self) -> &[DltFibexFrame] {
+        &self.frames
+    }
+
+    /// Number of frames in the catalog
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if the catalog has no frames
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+impl DltMessageCatalog for DltFibexCatalog {
+    fn frame(&self, message_id: u32) -> Option<DltNonVerboseFrame<'_>> {
+        let position = *self.index.get(&message_id)?;
+        Some(self.frames[position].as_frame())
+    }
+}
+
+// ========================================
+// FIBEX Element Mapping
+// ========================================
+
+/// Build a catalog frame from a FIBEX `FRAME` element
+fn parse_frame(
+    frame: &XmlElement,
+    pdus: &HashMap<&str, &XmlElement>,
+    signals: &HashMap<&str, &XmlElement>,
+    codings: &HashMap<&str, &XmlElement>,
+) -> Result<DltFibexFrame, DltFibexError> {
+    let id = frame.attribute("ID").unwrap_or_default();
+    let message_id = id
+        .strip_prefix("ID_")
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| DltFibexError::InvalidMessageId(id.to_string()))?;
+
+    let extension = frame.child("MANUFACTURER-EXTENSION");
+    let extension_text = |name: &str| extension.and_then(|ext| ext.child(name)).map(|e| e.text());
+    let log_level = extension_text("MESSAGE_INFO")
+        .map(|info| parse_log_level(&info))
+        .unwrap_or(MtinTypeDltLog::DltLogInfo);
+    let app_id = to_dlt_id_array(extension_text("APPLICATION_ID").unwrap_or_default().as_bytes());
+    let context_id = to_dlt_id_array(extension_text("CONTEXT_ID").unwrap_or_default().as_bytes());
+
+    let mut text = Vec::new();
+    let mut args = Vec::new();
+    for instance in sorted_instances(frame.child("PDU-INSTANCES"), "PDU-INSTANCE") {
+        let pdu = resolve(instance, "PDU-REF", pdus)?;
+        let signal_instances = sorted_instances(pdu.child("SIGNAL-INSTANCES"), "SIGNAL-INSTANCE");
+        if let Some(desc) = pdu.child("DESC").filter(|_| signal_instances.is_empty()) {
+            text.push(desc.text());
+        }
+        for signal_instance in signal_instances {
+            let signal_id = signal_instance
+                .child("SIGNAL-REF")
+                .and_then(|r| r.attribute("ID-REF"))
+                .unwrap_or_default();
+            args.push(signal_arg_type(signal_id, signals, codings)?);
+        }
+    }
+
+    Ok(DltFibexFrame {
+        message_id,
+        app_id,
+        context_id,
+        log_level,
+        text: text.join(" "),
+        args,
+    })
+}
+
+/// Children named `name` of `parent`, ordered by their `SEQUENCE-NUMBER`
+fn sorted_instances<'x>(parent: Option<&'x XmlElement>, name: &str) -> Vec<&'x XmlElement> {
+    let mut instances: Vec<_> = parent
+        .map(|p| p.children.iter().filter(|c| c.name == name).collect())
+        .unwrap_or_default();
+    instances.sort_by_key(|instance| {
+        instance
+            .child("SEQUENCE-NUMBER")
+            .and_then(|n| n.text().parse::<u32>().ok())
+            .unwrap_or(u32::MAX)
+    });
+    instances
+}
+
+/// Follow the `ID-REF` of the child `reference` into `elements`
+fn resolve<'x>(
+    element: &XmlElement,
+    reference: &str,
+    elements: &HashMap<&str, &'x XmlElement>,
+) -> Result<&'x XmlElement, DltFibexError> {
+    let id = element
+        .child(reference)
+        .and_then(|r| r.attribute("ID-REF"))
+        .unwrap_or_default();
+    elements
+        .get(id)
+        .copied()
+        .ok_or_else(|| DltFibexError::MissingReference(id.to_string()))
+}
+
+/// Standard signals: argument type, dlt-viewer signal ID, coding base data type
+///
+/// Bool is last so that `A_UINT8` codings map to `U8`.
+const SIGNALS: &[(DltArgType, &str, &str)] = &[
+    (DltArgType::I8, "S_SINT8", "A_INT8"),
+    (DltArgType::I16, "S_SINT16", "A_INT16"),
+    (DltArgType::I32, "S_SINT32", "A_INT32"),
+    (DltArgType::I64, "S_SINT64", "A_INT64"),
+    (DltArgType::U8, "S_UINT8", "A_UINT8"),
+    (DltArgType::U16, "S_UINT16", "A_UINT16"),
+    (DltArgType::U32, "S_UINT32", "A_UINT32"),
+    (DltArgType::U64, "S_UINT64", "A_UINT64"),
+    (DltArgType::F32, "S_FLOA32", "A_FLOAT32"),
+    (DltArgType::F64, "S_FLOA64", "A_FLOAT64"),
+    (DltArgType::AsciiString, "S_STRG_ASCII", "A_ASCIISTRING"),
+    (DltArgType::Utf8String, "S_STRG_UTF8", "A_UNICODE2STRING"),
+    (DltArgType::Raw, "S_RAWD", "A_BYTEFIELD"),
+    (DltArgType::Bool, "S_BOOL", "A_UINT8"),
+];
+
+/// Argument type of a signal, by dlt-viewer signal ID or coding base data type
+fn signal_arg_type(
+    signal_id: &str,
+    signals: &HashMap<&str, &XmlElement>,
+    codings: &HashMap<&str, &XmlElement>,
+) -> Result<DltArgType, DltFibexError> {
+    if let Some((arg_type, _, _)) = SIGNALS.iter().find(|(_, id, _)| *id == signal_id) {
+        return Ok(*arg_type);
+    }
+
+    let signal = signals
+        .get(signal_id)
+        .ok_or_else(|| DltFibexError::MissingReference(signal_id.to_string()))?;
+    let base_type = resolve(signal, "CODING-REF", codings)?
+        .child("CODED-TYPE")
+        .and_then(|coded| coded.attribute("BASE-DATA-TYPE"))
+        .unwrap_or_default();
+    SIGNALS
+        .iter()
+        .find(|(_, _, base)| *base == base_type)
+        .map(|(arg_type, _, _)| *arg_type)
+        .ok_or_else(|| DltFibexError::UnsupportedSignal(signal_id.to_string()))
+}
+
+/// Parse a `MESSAGE_INFO` value such as `DLT_LOG_INFO`
+fn parse_log_level(info: &str) -> MtinTypeDltLog {
+    match info {
+        "DLT_LOG_FATAL" => MtinTypeDltLog::DltLogFatal,
+        "DLT_LOG_ERROR" => MtinTypeDltLog::DltLogError,
+        "DLT_LOG_WARN" => MtinTypeDltLog::DltLogWarn,
+        "DLT_LOG_DEBUG" => MtinTypeDltLog::DltLogDebug,
+        "DLT_LOG_VERBOSE" => MtinTypeDltLog::DltLogVerbose,
+        _ => MtinTypeDltLog::DltLogInfo,
+    }
+}
+
+/// `MESSAGE_INFO` value of a log level
+fn log_level_name(level: MtinTypeDltLog) -> &'static str {
+    match level {
+        MtinTypeDltLog::DltLogFatal => "DLT_LOG_FATAL",
+        MtinTypeDltLog::DltLogError => "DLT_LOG_ERROR",
+        MtinTypeDltLog::DltLogWarn => "DLT_LOG_WARN",
+        MtinTypeDltLog::DltLogDebug => "DLT_LOG_DEBUG",
+        MtinTypeDltLog::DltLogVerbose => "DLT_LOG_VERBOSE",
+        _ => "DLT_LOG_INFO",
+    }
+}
+
+// ========================================
+// FIBEX Export
+// ========================================
+
+/// Write frames as a FIBEX file that dlt-viewer can use to decode them
+///
+/// Each frame becomes a `FRAME` with ID `ID_<message id>`, one text PDU holding
+/// the format text and one PDU per argument referencing the standard signals
+/// (`S_UINT32`, `S_STRG_ASCII`, ...). The output can be loaded again with
+/// [`DltFibexCatalog::parse`].
+///
+/// # Example
+/// ```no_run
+/// use dlt_protocol::r19_11::*;
+///
+/// const CATALOG: DltFrameCatalog = DltFrameCatalog::new(&[]);
+///
+/// let mut file = std::fs::File::create("ecu1.xml").unwrap();
+/// write_fibex(&mut file, b"ECU1", CATALOG.frames().iter().copied()).unwrap();
+/// ```
+pub fn write_fibex<'f, W: Write>(
+    writer: &mut W,
+    ecu_id: &[u8; DLT_ID_SIZE],
+    frames: impl IntoIterator<Item = DltNonVerboseFrame<'f>>,
+) -> io::Result<()> {
+    let ecu = id_text(ecu_id);
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<fx:FIBEX xmlns:fx="http://www.asam.net/xml/fbx" xmlns:ho="http://www.asam.net/xml" VERSION="3.1.0">"#
+    )?;
+    writeln!(writer, r#"  <fx:PROJECT ID="{0}"><ho:SHORT-NAME>{0}</ho:SHORT-NAME></fx:PROJECT>"#, ecu)?;
+    writeln!(writer, "  <fx:ELEMENTS>")?;
+    writeln!(writer, "    <fx:ECUS>")?;
+    writeln!(writer, r#"      <fx:ECU ID="{0}"><ho:SHORT-NAME>{0}</ho:SHORT-NAME></fx:ECU>"#, ecu)?;
+    writeln!(writer, "    </fx:ECUS>")?;
+
+    // PDUs are collected while writing the frames and emitted afterwards
+    let mut pdus = Vec::new();
+    writeln!(writer, "    <fx:FRAMES>")?;
+    for frame in frames {
+        let id = frame.message_id;
+        let byte_length: usize = frame.args.iter().filter_map(|arg| arg.fixed_size()).sum();
+        writeln!(writer, r#"      <fx:FRAME ID="ID_{}">"#, id)?;
+        writeln!(writer, "        <ho:SHORT-NAME>ID_{}</ho:SHORT-NAME>", id)?;
+        writeln!(writer, "        <fx:BYTE-LENGTH>{}</fx:BYTE-LENGTH>", byte_length)?;
+        writeln!(writer, "        <fx:FRAME-TYPE>OTHER</fx:FRAME-TYPE>")?;
+        writeln!(writer, "        <fx:PDU-INSTANCES>")?;
+
+        // PDU 0 carries the format text, PDUs 1.. one argument each
+        write_pdu_instance(writer, id, 0)?;
+        write_pdu_start(&mut pdus, id, 0, 0)?;
+        writeln!(pdus, "        <ho:DESC>{}</ho:DESC>", escape(frame.text))?;
+        writeln!(pdus, "      </fx:PDU>")?;
+
+        for (index, arg) in frame.args.iter().enumerate() {
+            let index = index + 1;
+            let signal = SIGNALS
+                .iter()
+                .find(|(arg_type, _, _)| arg_type == arg)
+                .map_or("", |(_, signal, _)| signal);
+            write_pdu_instance(writer, id, index)?;
+            write_pdu_start(&mut pdus, id, index, arg.fixed_size().unwrap_or(0))?;
+            writeln!(pdus, "        <fx:SIGNAL-INSTANCES>")?;
+            writeln!(pdus, r#"          <fx:SIGNAL-INSTANCE ID="S_{}_{}">"#, id, index)?;
+            writeln!(pdus, "            <fx:SEQUENCE-NUMBER>0</fx:SEQUENCE-NUMBER>")?;
+            writeln!(pdus, r#"            <fx:SIGNAL-REF ID-REF="{}"/>"#, signal)?;
+            writeln!(pdus, "          </fx:SIGNAL-INSTANCE>")?;
+            writeln!(pdus, "        </fx:SIGNAL-INSTANCES>")?;
+            writeln!(pdus, "      </fx:PDU>")?;
+        }
+
+        writeln!(writer, "        </fx:PDU-INSTANCES>")?;
+        writeln!(writer, "        <fx:MANUFACTURER-EXTENSION>")?;
+        writeln!(writer, "          <MESSAGE_TYPE>DLT_TYPE_LOG</MESSAGE_TYPE>")?;
+        writeln!(writer, "          <MESSAGE_INFO>{}</MESSAGE_INFO>", log_level_name(frame.log_level))?;
+        writeln!(writer, "          <APPLICATION_ID>{}</APPLICATION_ID>", id_text(&frame.app_id))?;
+        writeln!(writer, "          <CONTEXT_ID>{}</CONTEXT_ID>", id_text(&frame.context_id))?;
+        writeln!(writer, "        </fx:MANUFACTURER-EXTENSION>")?;
+        writeln!(writer, "      </fx:FRAME>")?;
+    }
+    writeln!(writer, "    </fx:FRAMES>")?;
+    writeln!(writer, "    <fx:PDUS>")?;
+    writer.write_all(&pdus)?;
+    writeln!(writer, "    </fx:PDUS>")?;
+
+    writeln!(writer, "    <fx:SIGNALS>")?;
+    for (_, signal, _) in SIGNALS {
+        writeln!(writer, r#"      <fx:SIGNAL ID="{}">"#, signal)?;
+        writeln!(writer, "        <ho:SHORT-NAME>{}</ho:SHORT-NAME>", signal)?;
+        writeln!(writer, r#"        <fx:CODING-REF ID-REF="{}"/>"#, &signal[2..])?;
+        writeln!(writer, "      </fx:SIGNAL>")?;
+    }
+    writeln!(writer, "    </fx:SIGNALS>")?;
+    writeln!(writer, "  </fx:ELEMENTS>")?;
+
+    writeln!(writer, "  <fx:PROCESSING-INFORMATION>")?;
+    writeln!(writer, "    <fx:CODINGS>")?;
+    for (arg_type, signal, base_type) in SIGNALS {
+        writeln!(writer, r#"      <fx:CODING ID="{}">"#, &signal[2..])?;
+        writeln!(writer, "        <ho:SHORT-NAME>{}</ho:SHORT-NAME>", &signal[2..])?;
+        match arg_type.fixed_size() {
+            Some(size) => {
+                writeln!(
+                    writer,
+                    r#"        <ho:CODED-TYPE ho:BASE-DATA-TYPE="{}" CATEGORY="STANDARD-LENGTH-TYPE">"#,
+                    base_type
+                )?;
+                writeln!(writer, "          <ho:BIT-LENGTH>{}</ho:BIT-LENGTH>", size * 8)?;
+                writeln!(writer, "        </ho:CODED-TYPE>")?;
+            }
+            None => writeln!(
+                writer,
+                r#"        <ho:CODED-TYPE ho:BASE-DATA-TYPE="{}" CATEGORY="LEADING-LENGTH-INFO-TYPE"/>"#,
+                base_type
+            )?,
+        }
+        writeln!(writer, "      </fx:CODING>")?;
+    }
+    writeln!(writer, "    </fx:CODINGS>")?;
+    writeln!(writer, "  </fx:PROCESSING-INFORMATION>")?;
+    writeln!(writer, "</fx:FIBEX>")
+}
+
+/// Write a `PDU-INSTANCE` referencing PDU `index` of frame `id`
+fn write_pdu_instance<W: Write>(writer: &mut W, id: u32, index: usize) -> io::Result<()> {
+    writeln!(writer, r#"          <fx:PDU-INSTANCE ID="P_{}_{}">"#, id, index)?;
+    writeln!(writer, r#"            <fx:PDU-REF ID-REF="PDU_{}_{}"/>"#, id, index)?;
+    writeln!(writer, "            <fx:SEQUENCE-NUMBER>{}</fx:SEQUENCE-NUMBER>", index)?;
+    writeln!(writer, "          </fx:PDU-INSTANCE>")
+}
+
+/// Write the start of PDU `index` of frame `id` (closed by the caller)
+fn write_pdu_start<W: Write>(
+    writer: &mut W,
+    id: u32,
+    index: usize,
+    byte_length: usize,
+) -> io::Result<()> {
+    writeln!(writer, r#"      <fx:PDU ID="PDU_{}_{}">"#, id, index)?;
+    writeln!(writer, "        <ho:SHORT-NAME>PDU_{}_{}</ho:SHORT-NAME>", id, index)?;
+    writeln!(writer, "        <fx:BYTE-LENGTH>{}</fx:BYTE-LENGTH>", byte_length)?;
+    writeln!(writer, "        <fx:PDU-TYPE>OTHER</fx:PDU-TYPE>")
+}
+
+/// DLT ID as text, without trailing null padding
+fn id_text(id: &[u8; DLT_ID_SIZE]) -> String {
+    let len = id.iter().position(|&b| b == 0).unwrap_or(DLT_ID_SIZE);
+    escape(&String::from_utf8_lossy(&id[..len]))
+}
+
+/// Escape text for use in XML content and attribute values
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '&' => result.push_str("&amp;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+// ========================================
+// Minimal XML Reader
+// ========================================
+
+/// XML element with namespace prefixes removed from element and attribute names
+#[derive(Debug, Default)]
+struct XmlElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<XmlElement>,
+    content: String,
+}
+
+impl XmlElement {
+    /// Parse a document and return its root element
+    ///
+    /// Supports elements, attributes, character references and CDATA; the
+    /// prolog, comments and doctype are skipped.
+    fn parse(xml: &str) -> Result<Self, DltFibexError> {
+        let mut stack = vec![XmlElement::default()];
+        let mut position = 0;
+
+        while let Some(offset) = xml[position..].find('<') {
+            let start = position + offset;
+            stack
+                .last_mut()
+                .expect("document root")
+                .content
+                .push_str(&unescape(&xml[position..start]));
+
+            let rest = &xml[start..];
+            let skip_to = |end: &str| {
+                rest.find(end)
+                    .map(|e| start + e + end.len())
+                    .ok_or(DltFibexError::InvalidXml(start))
+            };
+            if rest.starts_with("<?") {
+                position = skip_to("?>")?;
+            } else if rest.starts_with("<!--") {
+                position = skip_to("-->")?;
+            } else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let end = cdata.find("]]>").ok_or(DltFibexError::InvalidXml(start))?;
+                stack.last_mut().expect("document root").content.push_str(&cdata[..end]);
+                position = start + "<![CDATA[".len() + end + "]]>".len();
+            } else if rest.starts_with("<!") {
+                position = skip_to(">")?;
+            } else if let Some(closing) = rest.strip_prefix("</") {
+                let end = closing.find('>').ok_or(DltFibexError::InvalidXml(start))?;
+                let element = stack.pop().filter(|_| !stack.is_empty());
+                let parent = stack.last_mut();
+                match (element, parent) {
+                    (Some(element), Some(parent))
+                        if element.name == local_name(closing[..end].trim()) =>
+                    {
+                        parent.children.push(element)
+                    }
+                    _ => return Err(DltFibexError::InvalidXml(start)),
+                }
+                position = start + 2 + end + 1;
+            } else {
+                let end = rest.find('>').ok_or(DltFibexError::InvalidXml(start))?;
+                let tag = &rest[1..end];
+                let (tag, self_closing) = match tag.strip_suffix('/') {
+                    Some(tag) => (tag, true),
+                    None => (tag, false),
+                };
+                let element = parse_tag(tag).ok_or(DltFibexError::InvalidXml(start))?;
+                if self_closing {
+                    stack.last_mut().expect("document root").children.push(element);
+                } else {
+                    stack.push(element);
+                }
+                position = start + end + 1;
+            }
+        }
+
+        let mut document = stack.pop().filter(|_| stack.is_empty());
+        document
+            .as_mut()
+            .and_then(|doc| doc.children.pop())
+            .ok_or(DltFibexError::InvalidXml(xml.len()))
+    }
+
+    /// Value of the attribute `name` (without namespace prefix)
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// First child element named `name`
+    fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Trimmed text content
+    fn text(&self) -> String {
+        self.content.trim().to_string()
+    }
+
+    /// Call `f` for this element and all descendants
+    fn visit<'x>(&'x self, f: &mut impl FnMut(&'x XmlElement)) {
+        f(self);
+        for child in &self.children {
+            child.visit(f);
+        }
+    }
+}
+
+/// Parse the inside of a start tag: name and attributes
+fn parse_tag(tag: &str) -> Option<XmlElement> {
+    let tag = tag.trim();
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let mut element = XmlElement {
+        name: local_name(&tag[..name_end]).to_string(),
+        ..XmlElement::default()
+    };
+    if element.name.is_empty() {
+        return None;
+    }
+
+    let mut rest = tag[name_end..].trim_start();
+    while !rest.is_empty() {
+        let eq = rest.find('=')?;
+        let key = local_name(rest[..eq].trim());
+        let value = rest[eq + 1..].trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let end = value[1..].find(quote)?;
+        element
+            .attributes
+            .push((key.to_string(), unescape(&value[1..1 + end])));
+        rest = value[end + 2..].trim_start();
+    }
+    Some(element)
+}
+
+/// Strip a namespace prefix (`fx:FRAME` -> `FRAME`)
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Replace predefined entities and character references
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        result.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16).ok())
+                .unwrap_or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
@@ -0,0 +1,454 @@
+//! # DLT File Writer (requires `std` feature)
+//!
+//! Writes messages to `.dlt` files that can be opened with dlt-viewer. Every
+//! message is prefixed with a [`DltStorageHeader`] holding the wall-clock time
+//! at which it was written.
+//!
+//! ## File Rotation
+//!
+//! Rotation can be enabled by file size, message count and file age. When any
+//! rotation limit is configured, files are named `<stem>_<index>.<ext>`
+//! (e.g. `bench_000.dlt`, `bench_001.dlt`); otherwise the given path is used
+//! as-is. With [`DltFileWriter::with_max_files`] the oldest files are deleted
+//! so that at most that many files are kept.
+//!
+//! Existing data is never overwritten: without rotation, messages are appended
+//! to the file at the given path. With rotation, numbering continues after the
+//! highest index of rotated files left by earlier runs. Those files are only
+//! deleted to stay within the `with_max_files` limit when
+//! [`DltFileWriter::with_delete_earlier_files`] is set.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//! use std::time::Duration;
+//!
+//! let mut writer = DltFileWriter::new("logs/bench.dlt")
+//!     .with_max_file_size(10 * 1024 * 1024)
+//!     .with_max_file_age(Duration::from_secs(3600))
+//!     .with_max_files(5);
+//!
+//! let mut builder = DltMessageBuilder::new()
+//!     .with_ecu_id(b"ECU1")
+//!     .with_app_id(b"APP1")
+//!     .with_context_id(b"CTX1");
+//!
+//! let mut buffer = [0u8; 256];
+//! let size = builder.generate_log_message_with_payload(
+//!     &mut buffer,
+//!     b"Hello, file!",
+//!     MtinTypeDltLog::DltLogInfo,
+//!     1,
+//!     true,
+//! ).unwrap();
+//!
+//! writer.write_message(&buffer[..size]).unwrap();
+//! writer.flush().unwrap();
+//! ```
+
+use crate::r19_11::*;
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// ========================================
+// Open File State
+// ========================================
+
+/// Currently open output file
+struct DltOpenFile {
+    writer: BufWriter<File>,
+    /// Bytes written to this file
+    size: u64,
+    /// Messages written to this file
+    messages: u64,
+    /// When the file was opened
+    opened_at: Instant,
+}
+
+// ========================================
+// DLT File Writer
+// ========================================
+
+/// Writer for `.dlt` files with optional rotation
+///
+/// Files are opened lazily on the first write.
+pub struct DltFileWriter {
+    /// Path given by the user (used as-is when rotation is disabled)
+    base_path: PathBuf,
+    /// ECU ID for storage headers (taken from the message if not set)
+    ecu_id: Option<[u8; DLT_ID_SIZE]>,
+    /// Rotate when the file would exceed this many bytes
+    max_file_size: Option<u64>,
+    /// Rotate after this many messages
+    max_messages: Option<u64>,
+    /// Rotate when the file is older than this
+    max_file_age: Option<Duration>,
+    /// Maximum number of files to keep (oldest are deleted)
+    max_files: Option<usize>,
+    /// Whether rotated files of earlier runs count towards `max_files`
+    delete_earlier_files: bool,
+    /// Index of the next rotated file
+    next_index: u32,
+    /// Whether rotated files of earlier runs have been looked up
+    scanned: bool,
+    /// Files written so far that still exist (oldest first)
+    files: VecDeque<PathBuf>,
+    /// Currently open file
+    current: Option<DltOpenFile>,
+}
+
+impl DltFileWriter {
+    /// Create a new file writer
+    ///
+    /// Default configuration:
+    /// - No rotation, messages are appended to the file at `path` (created on
+    ///   first write if it doesn't exist)
+    /// - Storage header ECU ID taken from each message (falls back to "ECU\0")
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            base_path: path.as_ref().to_path_buf(),
+            ecu_id: None,
+            max_file_size: None,
+            max_messages: None,
+            max_file_age: None,
+            max_files: None,
+            delete_earlier_files: false,
+            next_index: 0,
+            scanned: false,
+            files: VecDeque::new(),
+            current: None,
+        }
+    }
+
+    // ========================================
+    // Configuration Methods (Builder Pattern)
+    // ========================================
+
+    /// Set the ECU ID written into every storage header
+    pub fn with_ecu_id(mut self, ecu_id: &[u8; DLT_ID_SIZE]) -> Self {
+        self.ecu_id = Some(*ecu_id);
+        self
+    }
+
+    /// Rotate before a file would grow beyond `bytes`
+    ///
+    /// A single record larger than the limit is still written to a fresh file.
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Rotate after `count` messages have been written to a file
+    pub fn with_max_messages(mut self, count: u64) -> Self {
+        self.max_messages = Some(count);
+        self
+    }
+
+    /// Rotate when a file has been open for longer than `age`
+    pub fn with_max_file_age(mut self, age: Duration) -> Self {
+        self.max_file_age = Some(age);
+        self
+    }
+
+    /// Keep at most `count` files, deleting the oldest on rotation
+    ///
+    /// Only files written by this writer are deleted, unless
+    /// [`DltFileWriter::with_delete_earlier_files`] is set.
+    pub fn with_max_files(mut self, count: usize) -> Self {
+        self.max_files = Some(count.max(1));
+        self
+    }
+
+    /// Count rotated files left by earlier runs towards the
+    /// [`with_max_files`](Self::with_max_files) limit, deleting the oldest of them
+    pub fn with_delete_earlier_files(mut self) -> Self {
+        self.delete_earlier_files = true;
+        self
+    }
+
+    // ========================================
+    // Writing
+    // ========================================
+
+    /// Write a message with a storage header holding the current wall-clock time
+    ///
+    /// `message` is a complete DLT message as produced by `DltMessageBuilder`,
+    /// `DltServiceMessageBuilder` or received from the network. A leading serial
+    /// header ("DLS\x01") is stripped, since DLT files don't contain it.
+    pub fn write_message(&mut self, message: &[u8]) -> io::Result<()> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO);
+        self.write_message_at(message, now.as_secs() as u32, now.subsec_micros() as i32)
+    }
+
+    /// Write a message with a storage header holding the given time
+    pub fn write_message_at(
+        &mut self,
+        message: &[u8],
+        seconds: u32,
+        microseconds: i32,
+    ) -> io::Result<()> {
+        let message = Self::strip_serial_header(message);
+        let ecu_id = self
+            .ecu_id
+            .or_else(|| Self::message_ecu_id(message))
+            .unwrap_or(*b"ECU\0");
+        let header = DltStorageHeader::new(seconds, microseconds, &ecu_id);
+        self.write_stored(&header, message)
+    }
+
+    /// Write a message with an explicit storage header
+    ///
+    /// As in [`write_message`](Self::write_message), a leading serial header
+    /// is stripped.
+    pub fn write_stored(&mut self, header: &DltStorageHeader, message: &[u8]) -> io::Result<()> {
+        let message = Self::strip_serial_header(message);
+        let record_size = (DLT_STORAGE_HEADER_SIZE + message.len()) as u64;
+
+        if self.needs_rotation(record_size) {
+            self.rotate()?;
+        }
+        if self.current.is_none() {
+            self.open_next()?;
+        }
+
+        let mut storage = [0u8; DLT_STORAGE_HEADER_SIZE];
+        header
+            .write(&mut storage)
+            .map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+        let file = self.current.as_mut().expect("file opened above");
+        file.writer.write_all(&storage)?;
+        file.writer.write_all(message)?;
+        file.size += record_size;
+        file.messages += 1;
+        Ok(())
+    }
+
+    /// Flush buffered data to the current file
+    pub fn flush(&mut self) -> io::Result<()> {
+        match self.current.as_mut() {
+            Some(file) => file.writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Close the current file and start a new one on the next write
+    ///
+    /// Without rotation limits there is only one file; writing continues at
+    /// its end.
+    pub fn rotate(&mut self) -> io::Result<()> {
+        if let Some(mut file) = self.current.take() {
+            file.writer.flush()?;
+        }
+        Ok(())
+    }
+
+    // ========================================
+    // Inspection
+    // ========================================
+
+    /// Path of the currently open file, if any
+    pub fn current_path(&self) -> Option<&Path> {
+        self.current.as_ref().and(self.files.back().map(PathBuf::as_path))
+    }
+
+    /// Paths of all retained files, oldest first
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(PathBuf::as_path)
+    }
+
+    /// Bytes written to the current file
+    pub fn current_size(&self) -> u64 {
+        self.current.as_ref().map_or(0, |file| file.size)
+    }
+
+    /// Messages written to the current file
+    pub fn current_messages(&self) -> u64 {
+        self.current.as_ref().map_or(0, |file| file.messages)
+    }
+
+    // ========================================
+    // Internal Helpers
+    // ========================================
+
+    /// Whether any rotation limit is configured
+    fn rotation_enabled(&self) -> bool {
+        self.max_file_size.is_some() || self.max_messages.is_some() || self.max_file_age.is_some()
+    }
+
+    /// Check whether writing `record_size` bytes requires a new file
+    fn needs_rotation(&self, record_size: u64) -> bool {
+        let Some(file) = self.current.as_ref() else {
+            return false;
+        };
+        if file.messages == 0 {
+            return false;
+        }
+
+        self.max_file_size
+            .is_some_and(|max| file.size + record_size > max)
+            || self.max_messages.is_some_and(|max| file.messages >= max)
+            || self
+                .max_file_age
+                .is_some_and(|max| file.opened_at.elapsed() >= max)
+    }
+
+    /// Open the next output file and delete old files beyond the limit
+    fn open_next(&mut self) -> io::Result<()> {
+        let (path, file, size) = if self.rotation_enabled() {
+            if !self.scanned {
+                self.scan_rotated_files()?;
+            }
+            let (path, file) = self.create_rotated()?;
+            (path, file, 0)
+        } else {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.base_path)?;
+            let size = file.metadata()?.len();
+            (self.base_path.clone(), file, size)
+        };
+
+        self.current = Some(DltOpenFile {
+            writer: BufWriter::new(file),
+            size,
+            messages: 0,
+            opened_at: Instant::now(),
+        });
+
+        if !self.files.contains(&path) {
+            self.files.push_back(path);
+        }
+        if let Some(max) = self.max_files {
+            while self.files.len() > max {
+                if let Some(old) = self.files.pop_front() {
+                    fs::remove_file(old)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Create the next rotated file, skipping indices that already exist
+    fn create_rotated(&mut self) -> io::Result<(PathBuf, File)> {
+        loop {
+            let path = self.rotated_path(self.next_index);
+            self.next_index = self.next_index.checked_add(1).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::StorageFull, "rotated file index exhausted")
+            })?;
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((path, file)),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Find `<stem>_<index>.<ext>` files of earlier runs
+    ///
+    /// Numbering continues after the highest index. With
+    /// `delete_earlier_files` they are also added to the retained files
+    /// (oldest first).
+    fn scan_rotated_files(&mut self) -> io::Result<()> {
+        self.scanned = true;
+
+        let dir = match self.base_path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let mut existing = Vec::new();
+        for entry in entries {
+            let name = entry?.file_name();
+            if let Some(index) = name.to_str().and_then(|name| self.rotated_index(name)) {
+                existing.push(index);
+            }
+        }
+        existing.sort_unstable();
+
+        if let Some(&last) = existing.last() {
+            self.next_index = self.next_index.max(last.saturating_add(1));
+        }
+        if !self.delete_earlier_files {
+            return Ok(());
+        }
+        for index in existing {
+            let path = self.rotated_path(index);
+            if !self.files.contains(&path) {
+                self.files.push_back(path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Index of a file name built by [`DltFileWriter::rotated_path`]
+    fn rotated_index(&self, name: &str) -> Option<u32> {
+        let rest = name.strip_prefix(self.rotated_stem().as_str())?.strip_prefix('_')?;
+        let digits = match self.base_path.extension() {
+            Some(ext) => rest
+                .strip_suffix(ext.to_string_lossy().as_ref())?
+                .strip_suffix('.')?,
+            None => rest,
+        };
+        if digits.len() < 3 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    }
+
+    /// Build `<stem>_<index>.<ext>` from the base path
+    fn rotated_path(&self, index: u32) -> PathBuf {
+        let stem = self.rotated_stem();
+        let name = match self.base_path.extension() {
+            Some(ext) => format!("{}_{:03}.{}", stem, index, ext.to_string_lossy()),
+            None => format!("{}_{:03}", stem, index),
+        };
+        self.base_path.with_file_name(name)
+    }
+
+    /// File name stem of rotated files
+    fn rotated_stem(&self) -> String {
+        self.base_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| String::from("dlt"))
+    }
+
+    /// Message without a leading serial header ("DLS\x01")
+    fn strip_serial_header(message: &[u8]) -> &[u8] {
+        message
+            .strip_prefix(&DLT_SERIAL_HEADER_ARRAY[..])
+            .unwrap_or(message)
+    }
+
+    /// ECU ID from the standard header extra fields, if the WEID flag is set
+    fn message_ecu_id(message: &[u8]) -> Option<[u8; DLT_ID_SIZE]> {
+        let htyp = *message.first()?;
+        if htyp & WEID_MASK == 0 {
+            return None;
+        }
+        message
+            .get(DLT_STANDARD_HEADER_SIZE..DLT_STANDARD_HEADER_SIZE + DLT_ID_SIZE)?
+            .try_into()
+            .ok()
+    }
+}
+
+impl Drop for DltFileWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
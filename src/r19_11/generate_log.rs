@@ -47,6 +47,7 @@ impl DltHeaderHtyp {
 // ========================================
 
 /// Byte order for multi-byte fields in DLT messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DltEndian {
     /// Big-endian (network byte order)
     Big,
@@ -273,10 +274,21 @@ impl<'a> DltMessageBuilder<'a> {
     // ========================================
 
     /// Set byte order for multi-byte fields
+    ///
+    /// Sets the MSBF flag; verbose payloads must be written in the same byte order
+    /// (see [`DltMessageBuilder::payload_builder`]).
     pub fn set_endian(&mut self, endian: DltEndian) {
         self.endian = endian;
     }
 
+    /// Create a `PayloadBuilder` writing in this builder's byte order
+    ///
+    /// Use this for verbose payloads passed to `insert_header_at_front` so the
+    /// payload matches the MSBF flag of the generated header.
+    pub fn payload_builder<'b>(&self, buffer: &'b mut [u8]) -> PayloadBuilder<'b> {
+        PayloadBuilder::with_endian(buffer, self.endian)
+    }
+
     // ========================================
     // Internal Field Accessors (for service builder)
     // ========================================
@@ -418,7 +430,7 @@ impl<'a> DltMessageBuilder<'a> {
         // Build payload using PayloadBuilder in verbose mode, or copy raw bytes in non-verbose mode
         let payload_size = if verbose {
             // Use PayloadBuilder to encode the payload with type information
-            let mut payload_builder = PayloadBuilder::new(&mut buffer[payload_offset..]);
+            let mut payload_builder = PayloadBuilder::with_endian(&mut buffer[payload_offset..], self.endian);
             
             // Convert payload bytes to string and add with type info
             let payload_str = core::str::from_utf8(payload)
@@ -448,6 +460,256 @@ impl<'a> DltMessageBuilder<'a> {
         Ok(total_size)
     }
     
+    /// Generate a non-verbose log message from a catalog frame
+    ///
+    /// The payload is the frame's message ID followed by the packed arguments
+    /// without type info. Log level, application ID and context ID are taken
+    /// from the frame, so the message matches the catalog (and an exported
+    /// FIBEX file).
+    ///
+    /// An application or context ID of all zeros in the frame means "not set"
+    /// (e.g. no APPLICATION_ID in the FIBEX file); the builder's own ID
+    /// (see [`DltMessageBuilder::with_app_id`] and
+    /// [`DltMessageBuilder::with_context_id`]) is used instead.
+    ///
+    /// # Arguments
+    /// * `buffer` - Destination buffer for the complete message
+    /// * `frame` - Catalog entry describing the message
+    /// * `args` - Argument values, matching the frame's argument types
+    ///
+    /// # Returns
+    /// - `Ok(usize)`: Total message size
+    /// - `Err(DltError::BufferTooSmall)`: Buffer too small
+    /// - `Err(DltError::InvalidParameter)`: Arguments don't match the frame
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dlt_protocol::r19_11::*;
+    ///
+    /// const TEMPERATURE: DltNonVerboseFrame = DltNonVerboseFrame {
+    ///     message_id: 1001,
+    ///     app_id: *b"APP1",
+    ///     context_id: *b"CTX1",
+    ///     log_level: MtinTypeDltLog::DltLogInfo,
+    ///     text: "Coolant temperature",
+    ///     args: &[DltArgType::I16],
+    /// };
+    ///
+    /// let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1");
+    /// let mut buffer = [0u8; 64];
+    /// let size = builder
+    ///     .generate_non_verbose_message(&mut buffer, &TEMPERATURE, &[DltValue::I16(87)])
+    ///     .unwrap();
+    /// ```
+    pub fn generate_non_verbose_message(
+        &mut self,
+        buffer: &mut [u8],
+        frame: &DltNonVerboseFrame,
+        args: &[DltValue],
+    ) -> Result<usize, DltError> {
+        let header_size = self._generate_log_message_header_size();
+        let serial_size = if self.serial_header { DLT_SERIAL_HEADER_SIZE } else { 0 };
+        let payload_offset = serial_size + header_size;
+        if buffer.len() < payload_offset {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let payload_size =
+            write_non_verbose_payload(&mut buffer[payload_offset..], frame, args, self.endian)
+                .map_err(|e| match e {
+                    PayloadError::BufferTooSmall => DltError::BufferTooSmall,
+                    _ => DltError::InvalidParameter,
+                })?;
+        let number_of_arguments = u8::try_from(args.len()).map_err(|_| DltError::InvalidParameter)?;
+
+        // Write the headers with a copy of this builder that carries the frame's IDs
+        let mut frame_builder = DltMessageBuilder {
+            app_id: if frame.app_id == [0; DLT_ID_SIZE] { self.app_id } else { &frame.app_id },
+            context_id: if frame.context_id == [0; DLT_ID_SIZE] {
+                self.context_id
+            } else {
+                &frame.context_id
+            },
+            ..*self
+        };
+        frame_builder._generate_log_message(
+            buffer,
+            payload_size,
+            frame.log_level,
+            number_of_arguments,
+            false,
+        )?;
+        self.message_counter = frame_builder.message_counter;
+        self.session_id = frame_builder.session_id;
+        self.timestamp = frame_builder.timestamp;
+
+        Ok(payload_offset + payload_size)
+    }
+
+    /// Generate a non-verbose log message, looking up the message ID in a catalog
+    ///
+    /// Returns `Err(DltError::InvalidParameter)` if the ID is not in the catalog.
+    pub fn generate_non_verbose_message_by_id<C: DltMessageCatalog + ?Sized>(
+        &mut self,
+        buffer: &mut [u8],
+        catalog: &C,
+        message_id: u32,
+        args: &[DltValue],
+    ) -> Result<usize, DltError> {
+        let frame = catalog.frame(message_id).ok_or(DltError::InvalidParameter)?;
+        self.generate_non_verbose_message(buffer, &frame, args)
+    }
+
+    /// Generate an application trace message (MSTP = AppTrace)
+    ///
+    /// The payload is copied verbatim; in verbose mode build it with
+    /// [`DltMessageBuilder::payload_builder`], e.g. a trace info argument with the
+    /// function name followed by its parameters.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dlt_protocol::r19_11::*;
+    ///
+    /// let mut builder = DltMessageBuilder::new();
+    /// let mut payload = [0u8; 64];
+    /// let payload_len = {
+    ///     let mut args = builder.payload_builder(&mut payload);
+    ///     args.add_trace_info("can_tp::send_frame").unwrap();
+    ///     args.add_u32(0x7DF).unwrap();
+    ///     args.len()
+    /// };
+    ///
+    /// let mut buffer = [0u8; 128];
+    /// let size = builder.generate_app_trace_message(
+    ///     &mut buffer,
+    ///     &payload[..payload_len],
+    ///     MtinTypeDltAppTrace::DltTraceFunctionIn,
+    ///     2,
+    ///     true,
+    /// ).unwrap();
+    /// ```
+    pub fn generate_app_trace_message(
+        &mut self,
+        buffer: &mut [u8],
+        payload: &[u8],
+        trace_type: MtinTypeDltAppTrace,
+        number_of_arguments: u8,
+        verbose: bool,
+    ) -> Result<usize, DltError> {
+        self.generate_message_with_payload(
+            buffer,
+            payload,
+            MstpType::DltTypeAppTrace,
+            trace_type.to_bits(),
+            number_of_arguments,
+            verbose,
+        )
+    }
+
+    /// Generate a network trace message (MSTP = NwTrace)
+    ///
+    /// The payload carries two arguments, the bus-specific header (e.g. a CAN ID
+    /// or Ethernet header) and the frame data. In verbose mode both are raw
+    /// arguments without terminator (see [`PayloadBuilder::add_raw`]); in
+    /// non-verbose mode each is prefixed with a 16-bit length.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dlt_protocol::r19_11::*;
+    ///
+    /// let mut builder = DltMessageBuilder::new();
+    /// let mut buffer = [0u8; 128];
+    /// let size = builder.generate_network_trace_message(
+    ///     &mut buffer,
+    ///     MtinTypeDltNwTrace::DltNwTraceCan,
+    ///     &0x7DFu32.to_be_bytes(),
+    ///     &[0x02, 0x01, 0x0C],
+    ///     true,
+    /// ).unwrap();
+    /// ```
+    pub fn generate_network_trace_message(
+        &mut self,
+        buffer: &mut [u8],
+        trace_type: MtinTypeDltNwTrace,
+        header: &[u8],
+        payload: &[u8],
+        verbose: bool,
+    ) -> Result<usize, DltError> {
+        let header_size = self._generate_log_message_header_size();
+        let serial_size = if self.serial_header { DLT_SERIAL_HEADER_SIZE } else { 0 };
+        let payload_offset = serial_size + header_size;
+        if buffer.len() < payload_offset {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let payload_size = if verbose {
+            let mut payload_builder =
+                PayloadBuilder::with_endian(&mut buffer[payload_offset..], self.endian);
+            payload_builder
+                .add_raw(header)
+                .and_then(|_| payload_builder.add_raw(payload))
+                .map_err(|_| DltError::BufferTooSmall)?;
+            payload_builder.len()
+        } else {
+            // Non-verbose: 16-bit length + data, for header and payload
+            let mut offset = payload_offset;
+            for part in [header, payload] {
+                let len = u16::try_from(part.len()).map_err(|_| DltError::InvalidParameter)?;
+                let end = offset + 2 + part.len();
+                if buffer.len() < end {
+                    return Err(DltError::BufferTooSmall);
+                }
+                buffer[offset..offset + 2].copy_from_slice(&convert_u16_to_bytes(len, &self.endian));
+                buffer[offset + 2..end].copy_from_slice(part);
+                offset = end;
+            }
+            offset - payload_offset
+        };
+
+        self._generate_message_header(
+            buffer,
+            payload_size,
+            MstpType::DltTypeNwTrace,
+            trace_type.to_bits(),
+            2,
+            verbose,
+        )?;
+        Ok(payload_offset + payload_size)
+    }
+
+    /// Generate a message of any type with an already encoded payload
+    ///
+    /// `mtin` is the raw 4-bit message type info matching `mstp` (e.g.
+    /// `MtinTypeDltNwTrace::DltNwTraceCan.to_bits()`). The payload is copied verbatim.
+    pub fn generate_message_with_payload(
+        &mut self,
+        buffer: &mut [u8],
+        payload: &[u8],
+        mstp: MstpType,
+        mtin: u8,
+        number_of_arguments: u8,
+        verbose: bool,
+    ) -> Result<usize, DltError> {
+        let header_size = self._generate_log_message_header_size();
+        let serial_size = if self.serial_header { DLT_SERIAL_HEADER_SIZE } else { 0 };
+        let payload_offset = serial_size + header_size;
+        let total_size = payload_offset + payload.len();
+        if buffer.len() < total_size {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        buffer[payload_offset..total_size].copy_from_slice(payload);
+        self._generate_message_header(
+            buffer,
+            payload.len(),
+            mstp,
+            mtin,
+            number_of_arguments,
+            verbose,
+        )?;
+        Ok(total_size)
+    }
+
     // ========================================
     // Message Generation - Internal Implementation
     // ========================================
@@ -470,6 +732,59 @@ impl<'a> DltMessageBuilder<'a> {
         log_level: MtinTypeDltLog,
         number_of_arguments: u8,
         verbose: bool,
+    ) -> Result<usize, DltError> {
+        self._generate_message_header(
+            buffer,
+            payload_size,
+            MstpType::DltTypeLog,
+            log_level.to_bits(),
+            number_of_arguments,
+            verbose,
+        )
+    }
+
+    /// Generate a verbose message whose arguments are written by `write_args`
+    ///
+    /// The closure receives a `PayloadBuilder` positioned after the headers, using
+    /// the builder's byte order. Payload errors are reported as `BufferTooSmall`.
+    pub(crate) fn _generate_verbose_message<F>(
+        &mut self,
+        buffer: &mut [u8],
+        mstp: MstpType,
+        mtin: u8,
+        number_of_arguments: u8,
+        write_args: F,
+    ) -> Result<usize, DltError>
+    where
+        F: FnOnce(&mut PayloadBuilder) -> Result<(), PayloadError>,
+    {
+        let header_size = self._generate_log_message_header_size();
+        let serial_size = if self.serial_header { DLT_SERIAL_HEADER_SIZE } else { 0 };
+        let payload_offset = serial_size + header_size;
+        if buffer.len() < payload_offset {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let mut payload_builder =
+            PayloadBuilder::with_endian(&mut buffer[payload_offset..], self.endian);
+        write_args(&mut payload_builder).map_err(|_| DltError::BufferTooSmall)?;
+        let payload_size = payload_builder.len();
+
+        self._generate_message_header(buffer, payload_size, mstp, mtin, number_of_arguments, true)?;
+        Ok(payload_offset + payload_size)
+    }
+
+    /// Write all headers (serial, standard, extra, extended) for any message type
+    ///
+    /// `mtin` is the raw 4-bit message type info matching `mstp`.
+    fn _generate_message_header(
+        &mut self,
+        buffer: &mut [u8],
+        payload_size: usize,
+        mstp: MstpType,
+        mtin: u8,
+        number_of_arguments: u8,
+        verbose: bool,
     ) -> Result<usize, DltError> {
         let mut offset = 0;
         
@@ -554,12 +869,8 @@ impl<'a> DltMessageBuilder<'a> {
         // 4. Write Extended Header (10 bytes)
         // ----------------------------------------
         
-        // MSIN byte: Encode verbose flag, message type, and log level
-        let msin = encode_msin(
-            verbose,
-            MstpType::DltTypeLog.to_bits(),
-            log_level.to_bits()
-        );
+        // MSIN byte: Encode verbose flag, message type, and type info (e.g. log level)
+        let msin = encode_msin(verbose, mstp.to_bits(), mtin);
         buffer[offset] = msin;
         offset += 1;
 
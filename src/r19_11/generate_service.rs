@@ -155,6 +155,89 @@ impl ServiceStatus {
     }
 }
 
+// ========================================
+// Log Channel Assignment Operation
+// ========================================
+
+/// Operation of a SetLogChannelAssignment request (0x20)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum LogChannelAssignment {
+    /// Remove the app/context from the log channel
+    Remove = 0,
+    /// Add the app/context to the log channel
+    Add = 1,
+}
+
+impl LogChannelAssignment {
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_u8(value: u8) -> Option<LogChannelAssignment> {
+        match value {
+            0 => Some(LogChannelAssignment::Remove),
+            1 => Some(LogChannelAssignment::Add),
+            _ => None,
+        }
+    }
+}
+
+// ========================================
+// Sync Time Stamp
+// ========================================
+
+/// Time base status: no synchronization within the timeout
+pub const DLT_TIME_BASE_TIMEOUT: u8 = 0x01;
+/// Time base status: synchronized to a gateway, not to the global time master
+pub const DLT_TIME_BASE_SYNC_TO_GATEWAY: u8 = 0x04;
+/// Time base status: synchronized to the global time base at least once
+pub const DLT_TIME_BASE_GLOBAL_TIME_BASE: u8 = 0x08;
+
+/// Absolute time of a SyncTimeStamp response (0x24)
+///
+/// Layout as AUTOSAR `StbM_TimeStampType`: time base status (1 byte),
+/// nanoseconds (4 bytes), seconds (4 bytes), upper 16 bits of the seconds (2 bytes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DltSyncTimeStamp {
+    /// Time base status flags (`DLT_TIME_BASE_*`)
+    pub time_base_status: u8,
+    /// Seconds since 1970-01-01 (48 bit)
+    pub seconds: u64,
+    /// Nanoseconds (0..999_999_999)
+    pub nanoseconds: u32,
+}
+
+impl DltSyncTimeStamp {
+    /// Encoded size in bytes
+    pub const SIZE: usize = 11;
+
+    /// Whether the time base is synchronized to the global time master and not timed out
+    pub fn is_synchronized(&self) -> bool {
+        self.time_base_status & DLT_TIME_BASE_GLOBAL_TIME_BASE != 0
+            && self.time_base_status & (DLT_TIME_BASE_TIMEOUT | DLT_TIME_BASE_SYNC_TO_GATEWAY) == 0
+    }
+
+    /// Write the time stamp (little-endian, like all service parameters)
+    fn write(&self, buffer: &mut [u8]) {
+        buffer[0] = self.time_base_status;
+        buffer[1..5].copy_from_slice(&self.nanoseconds.to_le_bytes());
+        buffer[5..9].copy_from_slice(&(self.seconds as u32).to_le_bytes());
+        buffer[9..11].copy_from_slice(&((self.seconds >> 32) as u16).to_le_bytes());
+    }
+
+    /// Read a time stamp written by [`DltSyncTimeStamp::write`]
+    pub(crate) fn read(data: &[u8]) -> Self {
+        let seconds_low = u32::from_le_bytes([data[5], data[6], data[7], data[8]]) as u64;
+        let seconds_high = u16::from_le_bytes([data[9], data[10]]) as u64;
+        Self {
+            time_base_status: data[0],
+            seconds: seconds_high << 32 | seconds_low,
+            nanoseconds: u32::from_le_bytes([data[1], data[2], data[3], data[4]]),
+        }
+    }
+}
+
 // ========================================
 // Service Message Builder
 // ========================================
@@ -393,6 +476,84 @@ impl<'a> DltServiceMessageBuilder<'a> {
         self.generate_control_message(buffer, &payload, MtinTypeDltControl::DltControlRequest)
     }
 
+    /// Generate SyncTimeStamp service request (0x24)
+    pub fn generate_sync_time_stamp_request(
+        &mut self,
+        buffer: &mut [u8],
+    ) -> Result<usize, DltError> {
+        let payload = ServiceId::SyncTimeStamp.to_u32().to_le_bytes();
+        self.generate_control_message(buffer, &payload, MtinTypeDltControl::DltControlRequest)
+    }
+
+    /// Generate SetLogChannelAssignment service request (0x20)
+    ///
+    /// # Arguments
+    /// * `buffer` - Output buffer
+    /// * `app_id` - Application ID
+    /// * `ctx_id` - Context ID
+    /// * `log_channel` - Name of the log channel
+    /// * `operation` - Add the app/context to the channel or remove it
+    pub fn generate_set_log_channel_assignment_request(
+        &mut self,
+        buffer: &mut [u8],
+        app_id: &[u8; 4],
+        ctx_id: &[u8; 4],
+        log_channel: &[u8; 4],
+        operation: LogChannelAssignment,
+    ) -> Result<usize, DltError> {
+        let mut payload = [0u8; 17]; // 4 (service ID) + 4 (app) + 4 (ctx) + 4 (channel) + 1 (operation)
+
+        payload[0..4].copy_from_slice(&ServiceId::SetLogChannelAssignment.to_u32().to_le_bytes());
+        payload[4..8].copy_from_slice(app_id);
+        payload[8..12].copy_from_slice(ctx_id);
+        payload[12..16].copy_from_slice(log_channel);
+        payload[16] = operation.to_u8();
+
+        self.generate_control_message(buffer, &payload, MtinTypeDltControl::DltControlRequest)
+    }
+
+    /// Generate SetLogChannelThreshold service request (0x21)
+    ///
+    /// # Arguments
+    /// * `buffer` - Output buffer
+    /// * `log_channel` - Name of the log channel
+    /// * `log_level` - Log level threshold of the channel (0=block all, 1-6=specific level)
+    /// * `trace_status` - Trace status of the channel (0=off, 1=on)
+    pub fn generate_set_log_channel_threshold_request(
+        &mut self,
+        buffer: &mut [u8],
+        log_channel: &[u8; 4],
+        log_level: i8,
+        trace_status: i8,
+    ) -> Result<usize, DltError> {
+        let mut payload = [0u8; 10]; // 4 (service ID) + 4 (channel) + 1 (level) + 1 (status)
+
+        payload[0..4].copy_from_slice(&ServiceId::SetLogChannelThreshold.to_u32().to_le_bytes());
+        payload[4..8].copy_from_slice(log_channel);
+        payload[8] = log_level as u8;
+        payload[9] = trace_status as u8;
+
+        self.generate_control_message(buffer, &payload, MtinTypeDltControl::DltControlRequest)
+    }
+
+    /// Generate GetLogChannelThreshold service request (0x22)
+    ///
+    /// # Arguments
+    /// * `buffer` - Output buffer
+    /// * `log_channel` - Name of the log channel
+    pub fn generate_get_log_channel_threshold_request(
+        &mut self,
+        buffer: &mut [u8],
+        log_channel: &[u8; 4],
+    ) -> Result<usize, DltError> {
+        let mut payload = [0u8; 8];
+
+        payload[0..4].copy_from_slice(&ServiceId::GetLogChannelThreshold.to_u32().to_le_bytes());
+        payload[4..8].copy_from_slice(log_channel);
+
+        self.generate_control_message(buffer, &payload, MtinTypeDltControl::DltControlRequest)
+    }
+
     // ========================================
     // Service Response Generators
     // ========================================
@@ -433,6 +594,47 @@ impl<'a> DltServiceMessageBuilder<'a> {
         self.generate_control_message(buffer, &payload, MtinTypeDltControl::DltControlResponse)
     }
 
+    /// Generate GetLogChannelThreshold response (0x22)
+    ///
+    /// SetLogChannelAssignment (0x20) and SetLogChannelThreshold (0x21) are
+    /// answered with [`DltServiceMessageBuilder::generate_status_response`].
+    pub fn generate_get_log_channel_threshold_response(
+        &mut self,
+        buffer: &mut [u8],
+        status: ServiceStatus,
+        log_level: i8,
+        trace_status: i8,
+    ) -> Result<usize, DltError> {
+        let mut payload = [0u8; 7]; // 4 (service ID) + 1 (status) + 1 (level) + 1 (trace status)
+
+        payload[0..4].copy_from_slice(&ServiceId::GetLogChannelThreshold.to_u32().to_le_bytes());
+        payload[4] = status.to_u8();
+        payload[5] = log_level as u8;
+        payload[6] = trace_status as u8;
+
+        self.generate_control_message(buffer, &payload, MtinTypeDltControl::DltControlResponse)
+    }
+
+    /// Generate SyncTimeStamp response (0x24)
+    ///
+    /// The message timestamp (see [`DltServiceMessageBuilder::with_timestamp`])
+    /// should hold the uptime at which `time_stamp` was taken, so that receivers
+    /// can correlate uptime and absolute time.
+    pub fn generate_sync_time_stamp_response(
+        &mut self,
+        buffer: &mut [u8],
+        status: ServiceStatus,
+        time_stamp: &DltSyncTimeStamp,
+    ) -> Result<usize, DltError> {
+        let mut payload = [0u8; 5 + DltSyncTimeStamp::SIZE]; // 4 (service ID) + 1 (status) + 11 (time stamp)
+
+        payload[0..4].copy_from_slice(&ServiceId::SyncTimeStamp.to_u32().to_le_bytes());
+        payload[4] = status.to_u8();
+        time_stamp.write(&mut payload[5..]);
+
+        self.generate_control_message(buffer, &payload, MtinTypeDltControl::DltControlResponse)
+    }
+
     /// Generate GetSoftwareVersion response (0x13)
     pub fn generate_get_software_version_response(
         &mut self,
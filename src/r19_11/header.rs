@@ -23,6 +23,8 @@
 //!            For Log: 1=Fatal, 2=Error, 3=Warn, 4=Info, 5=Debug, 6=Verbose
 //! ```
 
+use crate::r19_11::common::DltError;
+
 // ========================================
 // Size Constants
 // ========================================
@@ -30,7 +32,7 @@
 /// DLT ID field size (ECU ID, App ID, Context ID)
 pub const DLT_ID_SIZE: usize = 4;
 
-/// Storage header size (not used in runtime messages)
+/// Storage header size (DLT files only, not used in runtime messages)
 pub const DLT_STORAGE_HEADER_SIZE: usize = 16;
 
 /// Standard header size (HTYP + MCNT + LEN)
@@ -236,7 +238,7 @@ pub enum Mtin {
 }
 
 /// Message Type Info for Log messages (MTIN when MSTP=0)
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MtinTypeDltLog {
     /// Level 1: Fatal error
     DltLogFatal,
@@ -286,30 +288,100 @@ impl MtinTypeDltLog {
 }
 
 /// Message Type Info for Application Trace (MTIN when MSTP=1)
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MtinTypeDltAppTrace {
+    /// Type 1: Value of a variable
     DltTraceVariable,
+    /// Type 2: Call of a function
+    DltTraceFunctionIn,
+    /// Type 3: Return of a function
+    DltTraceFunctionOut,
+    /// Type 4: State of a state machine
+    DltTraceState,
+    /// Type 5: RTE events (virtual functional bus)
+    DltTraceVfb,
+    /// Types 6-15: Reserved
     Reserved(u8),
+    /// Invalid type (0)
     Invalid(u8),
 }
 
 impl MtinTypeDltAppTrace {
+    /// Parse trace type from 4-bit MTIN field
     pub fn parse(value: u8) -> MtinTypeDltAppTrace {
-        MtinTypeDltAppTrace::Invalid(value)
+        match value {
+            0x1 => MtinTypeDltAppTrace::DltTraceVariable,
+            0x2 => MtinTypeDltAppTrace::DltTraceFunctionIn,
+            0x3 => MtinTypeDltAppTrace::DltTraceFunctionOut,
+            0x4 => MtinTypeDltAppTrace::DltTraceState,
+            0x5 => MtinTypeDltAppTrace::DltTraceVfb,
+            0x6..=0xF => MtinTypeDltAppTrace::Reserved(value),
+            _ => MtinTypeDltAppTrace::Invalid(value),
+        }
+    }
+
+    /// Convert to 4-bit value
+    pub fn to_bits(&self) -> u8 {
+        match self {
+            MtinTypeDltAppTrace::DltTraceVariable => 0x1,
+            MtinTypeDltAppTrace::DltTraceFunctionIn => 0x2,
+            MtinTypeDltAppTrace::DltTraceFunctionOut => 0x3,
+            MtinTypeDltAppTrace::DltTraceState => 0x4,
+            MtinTypeDltAppTrace::DltTraceVfb => 0x5,
+            MtinTypeDltAppTrace::Reserved(v) => *v,
+            MtinTypeDltAppTrace::Invalid(v) => *v,
+        }
     }
 }
 
 /// Message Type Info for Network Trace (MTIN when MSTP=2)
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MtinTypeDltNwTrace {
-    DltTraceVariable,
-    Reserved(u8),
+    /// Type 1: Inter-process communication
+    DltNwTraceIpc,
+    /// Type 2: CAN bus
+    DltNwTraceCan,
+    /// Type 3: FlexRay bus
+    DltNwTraceFlexray,
+    /// Type 4: MOST bus
+    DltNwTraceMost,
+    /// Type 5: Ethernet
+    DltNwTraceEthernet,
+    /// Type 6: SOME/IP
+    DltNwTraceSomeip,
+    /// Types 7-15: User defined
+    UserDefined(u8),
+    /// Invalid type (0)
     Invalid(u8),
 }
 
 impl MtinTypeDltNwTrace {
+    /// Parse network trace type from 4-bit MTIN field
     pub fn parse(value: u8) -> MtinTypeDltNwTrace {
-        MtinTypeDltNwTrace::Invalid(value)
+        match value {
+            0x1 => MtinTypeDltNwTrace::DltNwTraceIpc,
+            0x2 => MtinTypeDltNwTrace::DltNwTraceCan,
+            0x3 => MtinTypeDltNwTrace::DltNwTraceFlexray,
+            0x4 => MtinTypeDltNwTrace::DltNwTraceMost,
+            0x5 => MtinTypeDltNwTrace::DltNwTraceEthernet,
+            0x6 => MtinTypeDltNwTrace::DltNwTraceSomeip,
+            0x7..=0xF => MtinTypeDltNwTrace::UserDefined(value),
+            _ => MtinTypeDltNwTrace::Invalid(value),
+        }
+    }
+
+    /// Convert to 4-bit value
+    pub fn to_bits(&self) -> u8 {
+        match self {
+            MtinTypeDltNwTrace::DltNwTraceIpc => 0x1,
+            MtinTypeDltNwTrace::DltNwTraceCan => 0x2,
+            MtinTypeDltNwTrace::DltNwTraceFlexray => 0x3,
+            MtinTypeDltNwTrace::DltNwTraceMost => 0x4,
+            MtinTypeDltNwTrace::DltNwTraceEthernet => 0x5,
+            MtinTypeDltNwTrace::DltNwTraceSomeip => 0x6,
+            MtinTypeDltNwTrace::UserDefined(v) => *v,
+            MtinTypeDltNwTrace::Invalid(v) => *v,
+        }
     }
 }
 
@@ -365,6 +437,12 @@ pub enum DltHeaderError {
     InvalidSerialHeader,
     /// Invalid header type flags
     InvalidHeaderType,
+    /// Storage header pattern doesn't match "DLT\x01"
+    InvalidStorageHeader,
+    /// Length field is smaller than the headers announced by HTYP
+    InvalidLength,
+    /// Extended header has a reserved message type or an invalid type info
+    InvalidMessageType,
 }
 
 // ========================================
@@ -540,7 +618,7 @@ impl<'a> DltHeaderParser<'a> {
     }
 
     /// Decode HTYP byte into structured format
-    fn decode_htyp(htyp: u8) -> DltHTYP {
+    pub(crate) fn decode_htyp(htyp: u8) -> DltHTYP {
         DltHTYP {
             UEH: (htyp & UEH_MASK) != 0,
             MSBF: (htyp & MSBF_MASK) != 0,
@@ -646,6 +724,121 @@ impl<'a> DltHeaderParser<'a> {
     }
 }
 
+// ========================================
+// Message Serialization
+// ========================================
+
+impl DltMessage<'_> {
+    /// Header type byte matching the fields of the message
+    ///
+    /// Version and MSBF are taken from the standard header; UEH, WEID, WSID and
+    /// WTMS are set according to which optional fields are present.
+    pub fn encoded_htyp(&self) -> u8 {
+        let mut htyp = self.standard_header.htyp & (VERS_MASK | MSBF_MASK);
+        if self.extended_header.is_some() {
+            htyp |= UEH_MASK;
+        }
+        if self.ecu_id.is_some() {
+            htyp |= WEID_MASK;
+        }
+        if self.session_id.is_some() {
+            htyp |= WSID_MASK;
+        }
+        if self.timestamp.is_some() {
+            htyp |= WTMS_MASK;
+        }
+        htyp
+    }
+
+    /// Value of the length field: standard header up to the end of the payload
+    pub fn encoded_message_len(&self) -> usize {
+        let mut len = DLT_STANDARD_HEADER_SIZE + self.payload.len();
+        if self.ecu_id.is_some() {
+            len += DLT_ID_SIZE;
+        }
+        if self.session_id.is_some() {
+            len += 4;
+        }
+        if self.timestamp.is_some() {
+            len += 4;
+        }
+        if self.extended_header.is_some() {
+            len += DLT_EXTENDED_HEADER_SIZE;
+        }
+        len
+    }
+
+    /// Update HTYP, LEN and the decoded header type to match the fields
+    ///
+    /// LEN is clamped to 65535; [`DltMessage::write`] rejects longer messages.
+    pub fn sync_header(&mut self) {
+        let htyp = self.encoded_htyp();
+        self.standard_header.htyp = htyp;
+        self.standard_header.len = self.encoded_message_len().min(u16::MAX as usize) as u16;
+        self.header_type = DltHeaderParser::decode_htyp(htyp);
+    }
+
+    /// Size of the encoded message, including the serial header if present
+    pub fn encoded_len(&self) -> usize {
+        let serial = if self.has_serial_header { DLT_SERIAL_HEADER_SIZE } else { 0 };
+        serial + self.encoded_message_len()
+    }
+
+    /// Write the message into the start of `buffer`
+    ///
+    /// A parsed message is written back byte for byte. HTYP flags and the length
+    /// field are derived from the fields (see [`DltMessage::encoded_htyp`]), so a
+    /// modified message stays consistent.
+    ///
+    /// # Returns
+    /// - `Ok(usize)`: Number of bytes written
+    /// - `Err(DltError::BufferTooSmall)`: Buffer shorter than [`DltMessage::encoded_len`]
+    /// - `Err(DltError::InvalidParameter)`: Message too long for the 16-bit length field
+    pub fn write(&self, buffer: &mut [u8]) -> Result<usize, DltError> {
+        let len = u16::try_from(self.encoded_message_len()).map_err(|_| DltError::InvalidParameter)?;
+        let total = self.encoded_len();
+        if buffer.len() < total {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let mut position = 0;
+        if self.has_serial_header {
+            buffer[..DLT_SERIAL_HEADER_SIZE].copy_from_slice(&DLT_SERIAL_HEADER_ARRAY);
+            position += DLT_SERIAL_HEADER_SIZE;
+        }
+
+        // Standard header, extra fields and extended header use big-endian
+        buffer[position] = self.encoded_htyp();
+        buffer[position + 1] = self.standard_header.mcnt;
+        buffer[position + 2..position + 4].copy_from_slice(&len.to_be_bytes());
+        position += DLT_STANDARD_HEADER_SIZE;
+
+        if let Some(ecu_id) = self.ecu_id {
+            buffer[position..position + DLT_ID_SIZE].copy_from_slice(&ecu_id);
+            position += DLT_ID_SIZE;
+        }
+        if let Some(session_id) = self.session_id {
+            buffer[position..position + 4].copy_from_slice(&session_id.to_be_bytes());
+            position += 4;
+        }
+        if let Some(timestamp) = self.timestamp {
+            buffer[position..position + 4].copy_from_slice(&timestamp.to_be_bytes());
+            position += 4;
+        }
+
+        if let Some(extended) = self.extended_header {
+            buffer[position] = extended.msin;
+            buffer[position + 1] = extended.noar;
+            buffer[position + 2..position + 6].copy_from_slice(&extended.apid);
+            buffer[position + 6..position + 10].copy_from_slice(&extended.ctid);
+            position += DLT_EXTENDED_HEADER_SIZE;
+        }
+
+        buffer[position..total].copy_from_slice(self.payload);
+        Ok(total)
+    }
+}
+
 // ========================================
 // MSIN Byte Helper Functions
 // ========================================
@@ -728,5 +921,23 @@ impl DltExtendedHeader {
             None
         }
     }
+
+    /// Get trace type if this is an Application Trace message type
+    pub fn app_trace_type(&self) -> Option<MtinTypeDltAppTrace> {
+        if matches!(self.message_type(), MstpType::DltTypeAppTrace) {
+            Some(MtinTypeDltAppTrace::parse(self.message_type_info()))
+        } else {
+            None
+        }
+    }
+
+    /// Get network trace type if this is a Network Trace message type
+    pub fn nw_trace_type(&self) -> Option<MtinTypeDltNwTrace> {
+        if matches!(self.message_type(), MstpType::DltTypeNwTrace) {
+            Some(MtinTypeDltNwTrace::parse(self.message_type_info()))
+        } else {
+            None
+        }
+    }
 }
 
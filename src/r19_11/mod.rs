@@ -1,19 +1,63 @@
+#[cfg(feature = "tokio")]
+mod codec;
 mod common;
+#[cfg(feature = "std")]
+mod fibex;
+#[cfg(feature = "std")]
+mod file_writer;
 mod generate_log;
 mod generate_service;
 mod header;
+mod non_verbose;
+#[cfg(feature = "std")]
+mod nw_reassembler;
+mod nw_segment;
+#[cfg(feature = "std")]
+mod owned_message;
 mod parse_service;
 mod parse_log;
 mod payload;
 mod payload_headers;
+#[cfg(feature = "std")]
+mod pcap;
 mod provider;
+mod rewrite;
+mod serial;
+mod storage;
+mod stream;
+#[cfg(feature = "std")]
+mod time_sync;
+#[cfg(feature = "std")]
+mod udp;
 
+#[cfg(feature = "tokio")]
+pub use codec::*;
 pub use common::*;
+#[cfg(feature = "std")]
+pub use fibex::*;
+#[cfg(feature = "std")]
+pub use file_writer::*;
 pub use generate_log::*;
 pub use generate_service::*;
 pub use header::*;
+pub use non_verbose::*;
+#[cfg(feature = "std")]
+pub use nw_reassembler::*;
+pub use nw_segment::*;
+#[cfg(feature = "std")]
+pub use owned_message::*;
 pub use parse_service::*;
 pub use parse_log::*;
 pub use payload::*;
 pub use payload_headers::*;
+#[cfg(feature = "std")]
+pub use pcap::*;
 pub use provider::*;
+pub use rewrite::*;
+pub use serial::*;
+pub use storage::*;
+pub use stream::*;
+#[cfg(feature = "std")]
+pub use time_sync::*;
+#[cfg(feature = "std")]
+pub use udp::*;
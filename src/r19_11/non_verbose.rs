@@ -0,0 +1,432 @@
+//! # DLT Non-Verbose Mode (Message Catalogs)
+//!
+//! In non-verbose mode (VERB=0) a log message carries no type information. The
+//! payload starts with a 32-bit message ID, followed by the packed argument values:
+//!
+//! ```text
+//! ┌──────────────────┬──────────┬──────────┬─────┐
+//! │ Message ID (4B)  │ Arg 1    │ Arg 2    │ ... │
+//! └──────────────────┴──────────┴──────────┴─────┘
+//! ```
+//!
+//! Bools, integers and floats are written as their value bytes only. Strings and
+//! raw data are prefixed with a 16-bit length (strings include the null terminator).
+//! Message ID, values and lengths follow the byte order of the message (MSBF flag).
+//!
+//! The meaning of each message ID (format text and argument types) comes from a
+//! message catalog, e.g. a FIBEX file exported by the ECU build. Any type
+//! implementing [`DltMessageCatalog`] can be used for decoding; [`DltFrameCatalog`]
+//! wraps a static slice of frames.
+//!
+//! The same catalog drives message generation on the ECU side via
+//! `DltMessageBuilder::generate_non_verbose_message`, and can be exported with
+//! `write_fibex` (`std` feature) so that viewers can decode the messages.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//!
+//! const FRAMES: &[DltNonVerboseFrame] = &[DltNonVerboseFrame {
+//!     message_id: 1001,
+//!     app_id: *b"APP1",
+//!     context_id: *b"CTX1",
+//!     log_level: MtinTypeDltLog::DltLogInfo,
+//!     text: "Coolant temperature",
+//!     args: &[DltArgType::I16],
+//! }];
+//! const CATALOG: DltFrameCatalog = DltFrameCatalog::new(FRAMES);
+//!
+//! let data: &[u8] = &[/* DLT packet bytes */];
+//! let message = DltHeaderParser::new(data).parse_message().unwrap();
+//!
+//! let decoded = decode_non_verbose(&CATALOG, &message).unwrap();
+//! print!("{}", decoded.text());
+//! for arg in decoded.args() {
+//!     print!(" {:?}", arg.unwrap());
+//! }
+//! ```
+
+use crate::r19_11::*;
+
+// ========================================
+// Argument Types
+// ========================================
+
+/// Type of a non-verbose argument as described by the message catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DltArgType {
+    Bool,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+    /// Length-prefixed, null-terminated string with ASCII coding
+    AsciiString,
+    /// Length-prefixed, null-terminated string with UTF-8 coding
+    Utf8String,
+    /// Length-prefixed raw bytes
+    Raw,
+}
+
+impl DltArgType {
+    /// Payload type of the argument (as it would appear in verbose mode)
+    pub fn payload_type(self) -> PayloadType {
+        match self {
+            DltArgType::Bool => PayloadType::Bool,
+            DltArgType::I8 | DltArgType::I16 | DltArgType::I32 | DltArgType::I64 => {
+                PayloadType::Signed
+            }
+            DltArgType::U8 | DltArgType::U16 | DltArgType::U32 | DltArgType::U64 => {
+                PayloadType::Unsigned
+            }
+            DltArgType::F32 | DltArgType::F64 => PayloadType::Float,
+            DltArgType::AsciiString | DltArgType::Utf8String => PayloadType::String,
+            DltArgType::Raw => PayloadType::Raw,
+        }
+    }
+
+    /// Size of the value in bytes, or `None` for length-prefixed types
+    pub fn fixed_size(self) -> Option<usize> {
+        match self {
+            DltArgType::Bool | DltArgType::I8 | DltArgType::U8 => Some(1),
+            DltArgType::I16 | DltArgType::U16 => Some(2),
+            DltArgType::I32 | DltArgType::U32 | DltArgType::F32 => Some(4),
+            DltArgType::I64 | DltArgType::U64 | DltArgType::F64 => Some(8),
+            DltArgType::AsciiString | DltArgType::Utf8String | DltArgType::Raw => None,
+        }
+    }
+}
+
+// ========================================
+// Message Catalog
+// ========================================
+
+/// Description of one non-verbose message (a FIBEX frame)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DltNonVerboseFrame<'a> {
+    /// Message ID at the start of the payload
+    pub message_id: u32,
+    /// Application ID of the message
+    pub app_id: [u8; DLT_ID_SIZE],
+    /// Context ID of the message
+    pub context_id: [u8; DLT_ID_SIZE],
+    /// Log level of the message
+    pub log_level: MtinTypeDltLog,
+    /// Format text shown in place of the message ID
+    pub text: &'a str,
+    /// Argument types in payload order
+    pub args: &'a [DltArgType],
+}
+
+/// Lookup of non-verbose message descriptions by message ID
+pub trait DltMessageCatalog {
+    /// Description of the message with the given ID, if known
+    fn frame(&self, message_id: u32) -> Option<DltNonVerboseFrame<'_>>;
+}
+
+/// Message catalog backed by a static slice of frames
+///
+/// Can be built in a `const` context, so catalogs can live in flash on
+/// microcontrollers.
+#[derive(Debug, Clone, Copy)]
+pub struct DltFrameCatalog<'a> {
+    frames: &'a [DltNonVerboseFrame<'a>],
+}
+
+impl<'a> DltFrameCatalog<'a> {
+    /// Create a catalog from a slice of frames
+    pub const fn new(frames: &'a [DltNonVerboseFrame<'a>]) -> Self {
+        Self { frames }
+    }
+
+    /// All frames in the catalog
+    pub fn frames(&self) -> &'a [DltNonVerboseFrame<'a>] {
+        self.frames
+    }
+
+    /// Number of frames in the catalog
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if the catalog has no frames
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+impl DltMessageCatalog for DltFrameCatalog<'_> {
+    fn frame(&self, message_id: u32) -> Option<DltNonVerboseFrame<'_>> {
+        self.frames
+            .iter()
+            .find(|frame| frame.message_id == message_id)
+            .copied()
+    }
+}
+
+// ========================================
+// Non-Verbose Encoding
+// ========================================
+
+/// Write a non-verbose payload: the frame's message ID followed by the packed arguments
+///
+/// `args` must match the frame's argument types one to one (`DltValue::String`
+/// for string arguments).
+///
+/// # Returns
+/// - `Ok(usize)`: Number of payload bytes written
+/// - `Err(PayloadError::InvalidData)`: Wrong number of arguments or data too long
+/// - `Err(PayloadError::InvalidType)`: An argument doesn't match its catalog type
+/// - `Err(PayloadError::BufferTooSmall)`: Buffer too small
+pub fn write_non_verbose_payload(
+    buffer: &mut [u8],
+    frame: &DltNonVerboseFrame,
+    args: &[DltValue],
+    endian: DltEndian,
+) -> Result<usize, PayloadError> {
+    if args.len() != frame.args.len() {
+        return Err(PayloadError::InvalidData);
+    }
+
+    let mut writer = NonVerboseWriter {
+        buffer,
+        position: 0,
+        endian,
+    };
+    writer.write_ordered(&frame.message_id.to_le_bytes())?;
+    for (arg_type, value) in frame.args.iter().zip(args) {
+        writer.write_arg(*arg_type, value)?;
+    }
+    Ok(writer.position)
+}
+
+/// Cursor for writing packed non-verbose arguments
+struct NonVerboseWriter<'b> {
+    buffer: &'b mut [u8],
+    position: usize,
+    endian: DltEndian,
+}
+
+impl NonVerboseWriter<'_> {
+    /// Write bytes as-is
+    fn write_bytes(&mut self, data: &[u8]) -> Result<(), PayloadError> {
+        let end = self.position + data.len();
+        if end > self.buffer.len() {
+            return Err(PayloadError::BufferTooSmall);
+        }
+        self.buffer[self.position..end].copy_from_slice(data);
+        self.position = end;
+        Ok(())
+    }
+
+    /// Write a little-endian value in the configured byte order
+    fn write_ordered(&mut self, le_bytes: &[u8]) -> Result<(), PayloadError> {
+        let mut bytes = [0u8; 8];
+        let bytes = &mut bytes[..le_bytes.len()];
+        bytes.copy_from_slice(le_bytes);
+        if self.endian == DltEndian::Big {
+            bytes.reverse();
+        }
+        self.write_bytes(bytes)
+    }
+
+    /// Write a 16-bit length followed by the data (and a null terminator for strings)
+    fn write_length_prefixed(&mut self, data: &[u8], terminate: bool) -> Result<(), PayloadError> {
+        let len = data.len() + terminate as usize;
+        if len > u16::MAX as usize {
+            return Err(PayloadError::InvalidData);
+        }
+        self.write_ordered(&(len as u16).to_le_bytes())?;
+        self.write_bytes(data)?;
+        if terminate {
+            self.write_bytes(&[0])?;
+        }
+        Ok(())
+    }
+
+    /// Write one argument, checking it against its catalog type
+    fn write_arg(&mut self, arg_type: DltArgType, value: &DltValue) -> Result<(), PayloadError> {
+        match (arg_type, value) {
+            (DltArgType::Bool, DltValue::Bool(v)) => self.write_bytes(&[*v as u8]),
+            (DltArgType::I8, DltValue::I8(v)) => self.write_ordered(&v.to_le_bytes()),
+            (DltArgType::I16, DltValue::I16(v)) => self.write_ordered(&v.to_le_bytes()),
+            (DltArgType::I32, DltValue::I32(v)) => self.write_ordered(&v.to_le_bytes()),
+            (DltArgType::I64, DltValue::I64(v)) => self.write_ordered(&v.to_le_bytes()),
+            (DltArgType::U8, DltValue::U8(v)) => self.write_ordered(&v.to_le_bytes()),
+            (DltArgType::U16, DltValue::U16(v)) => self.write_ordered(&v.to_le_bytes()),
+            (DltArgType::U32, DltValue::U32(v)) => self.write_ordered(&v.to_le_bytes()),
+            (DltArgType::U64, DltValue::U64(v)) => self.write_ordered(&v.to_le_bytes()),
+            (DltArgType::F32, DltValue::F32(v)) => self.write_ordered(&v.to_le_bytes()),
+            (DltArgType::F64, DltValue::F64(v)) => self.write_ordered(&v.to_le_bytes()),
+            (DltArgType::AsciiString | DltArgType::Utf8String, DltValue::String(v)) => {
+                self.write_length_prefixed(v.as_bytes(), true)
+            }
+            (DltArgType::Raw, DltValue::Raw(v)) => self.write_length_prefixed(v, false),
+            _ => Err(PayloadError::InvalidType),
+        }
+    }
+}
+
+// ========================================
+// Non-Verbose Decoding
+// ========================================
+
+/// Read the message ID at the start of a non-verbose payload
+///
+/// # Returns
+/// - `Ok(u32)`: Message ID in the message byte order
+/// - `Err(PayloadError::InvalidType)`: Message is in verbose mode
+/// - `Err(PayloadError::BufferTooSmall)`: Payload shorter than 4 bytes
+pub fn read_message_id(message: &DltMessage) -> Result<u32, PayloadError> {
+    if message.extended_header.is_some_and(|ext| ext.is_verbose()) {
+        return Err(PayloadError::InvalidType);
+    }
+    let bytes: [u8; 4] = message
+        .payload
+        .get(..4)
+        .ok_or(PayloadError::BufferTooSmall)?
+        .try_into()
+        .map_err(|_| PayloadError::BufferTooSmall)?;
+    Ok(if message.header_type.MSBF {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    })
+}
+
+/// Decode a non-verbose message using a message catalog
+///
+/// # Returns
+/// - `Ok(DltNonVerbosePayload)`: Format text and lazily decoded arguments
+/// - `Err(PayloadError::UnknownMessageId)`: Message ID not in the catalog
+/// - `Err(PayloadError::InvalidType)`: Message is in verbose mode
+/// - `Err(PayloadError::BufferTooSmall)`: Payload shorter than 4 bytes
+pub fn decode_non_verbose<'c, 'a, C: DltMessageCatalog + ?Sized>(
+    catalog: &'c C,
+    message: &DltMessage<'a>,
+) -> Result<DltNonVerbosePayload<'c, 'a>, PayloadError> {
+    let message_id = read_message_id(message)?;
+    let frame = catalog
+        .frame(message_id)
+        .ok_or(PayloadError::UnknownMessageId(message_id))?;
+    let endian = if message.header_type.MSBF {
+        DltEndian::Big
+    } else {
+        DltEndian::Little
+    };
+    Ok(DltNonVerbosePayload {
+        frame,
+        data: &message.payload[4..],
+        endian,
+    })
+}
+
+/// A non-verbose payload matched against its catalog entry
+#[derive(Debug, Clone, Copy)]
+pub struct DltNonVerbosePayload<'c, 'a> {
+    frame: DltNonVerboseFrame<'c>,
+    data: &'a [u8],
+    endian: DltEndian,
+}
+
+impl<'c, 'a> DltNonVerbosePayload<'c, 'a> {
+    /// Message ID of the payload
+    pub fn message_id(&self) -> u32 {
+        self.frame.message_id
+    }
+
+    /// Format text from the catalog
+    pub fn text(&self) -> &'c str {
+        self.frame.text
+    }
+
+    /// Catalog entry of the message
+    pub fn frame(&self) -> &DltNonVerboseFrame<'c> {
+        &self.frame
+    }
+
+    /// Argument data following the message ID
+    pub fn raw_data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Decode the arguments as described by the catalog
+    ///
+    /// Values are the same as [`PayloadParser::read_next`] returns for the
+    /// equivalent verbose message: strings without their null terminator, raw
+    /// data with all of its bytes.
+    pub fn args(&self) -> DltNonVerboseArgs<'c, 'a> {
+        DltNonVerboseArgs {
+            types: self.frame.args.iter(),
+            data: self.data,
+            endian: self.endian,
+        }
+    }
+}
+
+/// Iterator over the decoded arguments of a [`DltNonVerbosePayload`]
+pub struct DltNonVerboseArgs<'c, 'a> {
+    types: core::slice::Iter<'c, DltArgType>,
+    data: &'a [u8],
+    endian: DltEndian,
+}
+
+impl<'a> DltNonVerboseArgs<'_, 'a> {
+    /// Bytes not consumed by the arguments decoded so far
+    pub fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Take `count` bytes from the argument data
+    fn take(&mut self, count: usize) -> Result<&'a [u8], PayloadError> {
+        if self.data.len() < count {
+            return Err(PayloadError::BufferTooSmall);
+        }
+        let (bytes, rest) = self.data.split_at(count);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    /// Decode one argument of the given type
+    fn decode(&mut self, arg_type: DltArgType) -> Result<DltValue<'a>, PayloadError> {
+        if let Some(size) = arg_type.fixed_size() {
+            let bytes = self.take(size)?;
+            return decode_scalar(arg_type.payload_type(), bytes, self.endian)
+                .ok_or(PayloadError::UnsupportedLength);
+        }
+
+        let len = self.take(2)?;
+        let len = read_u16_field([len[0], len[1]], self.endian) as usize;
+        let data = self.take(len)?;
+        let coding = match arg_type {
+            DltArgType::AsciiString => DltStringCoding::Ascii,
+            DltArgType::Utf8String => DltStringCoding::Utf8,
+            _ => return Ok(DltValue::Raw(data)),
+        };
+
+        Ok(DltValue::String(DltString::new(
+            data.strip_suffix(&[0]).unwrap_or(data),
+            coding,
+        )))
+    }
+}
+
+impl<'a> Iterator for DltNonVerboseArgs<'_, 'a> {
+    type Item = Result<DltValue<'a>, PayloadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let arg_type = *self.types.next()?;
+        let value = self.decode(arg_type);
+        if value.is_err() {
+            self.types = [].iter();
+        }
+        Some(value)
+    }
+}
@@ -0,0 +1,327 @@
+//! # Segmented Network Trace Reassembly (requires `std` feature)
+//!
+//! Collects the start, chunk and end messages of segmented network trace
+//! transfers (see [`DltNwSegment`]) from a stream of parsed messages and yields
+//! the complete payload once the end message arrives.
+//!
+//! Transfers are identified by ECU ID and stream handle, so several transfers
+//! can be in progress at the same time. Chunks may arrive in any order. A
+//! transfer that receives no message for longer than the configured timeout is
+//! dropped.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//! use std::time::Duration;
+//!
+//! let mut reassembler = DltNwReassembler::new().with_timeout(Duration::from_secs(5));
+//!
+//! let stream: &[u8] = &[/* received DLT messages */];
+//! let mut parser = DltHeaderParser::new(stream);
+//! while let Ok(message) = parser.parse_message() {
+//!     match reassembler.push(&message) {
+//!         Ok(Some(trace)) => println!("Handle {:#x}: {} bytes", trace.handle, trace.payload.len()),
+//!         Ok(None) => {}
+//!         Err(e) => eprintln!("Dropped transfer: {}", e),
+//!     }
+//! }
+//! ```
+
+use crate::r19_11::*;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Default time after which an incomplete transfer is dropped
+pub const DLT_NW_REASSEMBLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default upper limit for the announced payload length (16 MiB)
+pub const DLT_NW_REASSEMBLY_MAX_PAYLOAD: usize = 16 * 1024 * 1024;
+
+// ========================================
+// Reassembly Error Type
+// ========================================
+
+/// Error while reassembling a segmented network trace transfer
+///
+/// Except for `InvalidSegment`, the affected transfer is dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DltNwReassemblyError {
+    /// The message has a segment identifier but malformed arguments
+    InvalidSegment,
+    /// Chunk or end message for a handle without a start message
+    UnknownHandle(u32),
+    /// The start message announces more data than the configured limit
+    PayloadTooLarge { handle: u32, length: u32 },
+    /// Chunk sequence number or size doesn't match the start message
+    SegmentOutOfRange { handle: u32, sequence: u16 },
+    /// End message received before all chunks
+    Incomplete { handle: u32, received: u16, expected: u16 },
+}
+
+impl fmt::Display for DltNwReassemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DltNwReassemblyError::InvalidSegment => write!(f, "malformed segment message"),
+            DltNwReassemblyError::UnknownHandle(handle) => {
+                write!(f, "segment for unknown handle {:#x}", handle)
+            }
+            DltNwReassemblyError::PayloadTooLarge { handle, length } => {
+                write!(f, "handle {:#x}: payload of {} bytes exceeds limit", handle, length)
+            }
+            DltNwReassemblyError::SegmentOutOfRange { handle, sequence } => {
+                write!(f, "handle {:#x}: segment {} out of range", handle, sequence)
+            }
+            DltNwReassemblyError::Incomplete {
+                handle,
+                received,
+                expected,
+            } => write!(
+                f,
+                "handle {:#x}: ended after {} of {} segments",
+                handle, received, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DltNwReassemblyError {}
+
+// ========================================
+// Reassembled Transfer
+// ========================================
+
+/// A complete network trace payload reassembled from its segments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DltNwTrace {
+    /// ECU ID of the sender, if present in the messages
+    pub ecu_id: Option<[u8; DLT_ID_SIZE]>,
+    /// Stream handle of the transfer
+    pub handle: u32,
+    /// Network trace type of the start message
+    pub trace_type: Option<MtinTypeDltNwTrace>,
+    /// Bus-specific header from the start message
+    pub header: Vec<u8>,
+    /// Complete payload
+    pub payload: Vec<u8>,
+}
+
+/// Transfer in progress
+struct DltNwTransfer {
+    trace: DltNwTrace,
+    segment_size: usize,
+    received: Vec<bool>,
+    received_count: u16,
+    last_activity: Instant,
+}
+
+// ========================================
+// Reassembler
+// ========================================
+
+/// Reassembles segmented network trace transfers
+pub struct DltNwReassembler {
+    timeout: Duration,
+    max_payload: usize,
+    transfers: HashMap<(Option<[u8; DLT_ID_SIZE]>, u32), DltNwTransfer>,
+    expired: u64,
+}
+
+impl Default for DltNwReassembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DltNwReassembler {
+    /// Create a new reassembler
+    ///
+    /// Default configuration:
+    /// - Timeout: [`DLT_NW_REASSEMBLY_TIMEOUT`]
+    /// - Maximum payload: [`DLT_NW_REASSEMBLY_MAX_PAYLOAD`]
+    pub fn new() -> Self {
+        Self {
+            timeout: DLT_NW_REASSEMBLY_TIMEOUT,
+            max_payload: DLT_NW_REASSEMBLY_MAX_PAYLOAD,
+            transfers: HashMap::new(),
+            expired: 0,
+        }
+    }
+
+    // ========================================
+    // Configuration Methods (Builder Pattern)
+    // ========================================
+
+    /// Drop transfers that receive no message for longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Reject transfers announcing a payload larger than `bytes`
+    pub fn with_max_payload_size(mut self, bytes: usize) -> Self {
+        self.max_payload = bytes;
+        self
+    }
+
+    // ========================================
+    // Reassembly
+    // ========================================
+
+    /// Process a received message, using the current time for timeouts
+    ///
+    /// # Returns
+    /// - `Ok(Some(DltNwTrace))`: The message completed a transfer
+    /// - `Ok(None)`: Segment stored, or not a segmented network trace message
+    /// - `Err(DltNwReassemblyError)`: The segment could not be used
+    pub fn push(&mut self, message: &DltMessage) -> Result<Option<DltNwTrace>, DltNwReassemblyError> {
+        self.push_at(message, Instant::now())
+    }
+
+    /// Process a received message at the given time
+    ///
+    /// Transfers that timed out before `now` are dropped first.
+    pub fn push_at(
+        &mut self,
+        message: &DltMessage,
+        now: Instant,
+    ) -> Result<Option<DltNwTrace>, DltNwReassemblyError> {
+        self.expire_at(now);
+
+        let segment = match parse_nw_segment(message) {
+            Ok(Some(segment)) => segment,
+            Ok(None) => return Ok(None),
+            Err(_) => return Err(DltNwReassemblyError::InvalidSegment),
+        };
+        let key = (message.ecu_id, segment.handle());
+
+        match segment {
+            DltNwSegment::Start {
+                handle,
+                header,
+                payload_length,
+                segment_count,
+                segment_size,
+            } => {
+                let length = payload_length as usize;
+                let covered = segment_count as usize * segment_size as usize;
+                if length > self.max_payload {
+                    self.transfers.remove(&key);
+                    return Err(DltNwReassemblyError::PayloadTooLarge {
+                        handle,
+                        length: payload_length,
+                    });
+                }
+                if covered < length || (segment_count > 0 && covered - length >= segment_size as usize) {
+                    self.transfers.remove(&key);
+                    return Err(DltNwReassemblyError::InvalidSegment);
+                }
+
+                // A new start message replaces an unfinished transfer with the same handle
+                self.transfers.insert(
+                    key,
+                    DltNwTransfer {
+                        trace: DltNwTrace {
+                            ecu_id: message.ecu_id,
+                            handle,
+                            trace_type: message.extended_header.and_then(|ext| ext.nw_trace_type()),
+                            header: header.to_vec(),
+                            payload: vec![0; length],
+                        },
+                        segment_size: segment_size as usize,
+                        received: vec![false; segment_count as usize],
+                        received_count: 0,
+                        last_activity: now,
+                    },
+                );
+                Ok(None)
+            }
+            DltNwSegment::Chunk {
+                handle,
+                sequence,
+                data,
+            } => {
+                let transfer = self
+                    .transfers
+                    .get_mut(&key)
+                    .ok_or(DltNwReassemblyError::UnknownHandle(handle))?;
+
+                let start = sequence as usize * transfer.segment_size;
+                let end = start + data.len();
+                let is_last = sequence as usize + 1 == transfer.received.len();
+                let size_ok = if is_last {
+                    end == transfer.trace.payload.len()
+                } else {
+                    data.len() == transfer.segment_size
+                };
+                if sequence as usize >= transfer.received.len() || !size_ok {
+                    self.transfers.remove(&key);
+                    return Err(DltNwReassemblyError::SegmentOutOfRange { handle, sequence });
+                }
+
+                transfer.trace.payload[start..end].copy_from_slice(data);
+                if !transfer.received[sequence as usize] {
+                    transfer.received[sequence as usize] = true;
+                    transfer.received_count += 1;
+                }
+                transfer.last_activity = now;
+                Ok(None)
+            }
+            DltNwSegment::End { handle } => {
+                let transfer = self
+                    .transfers
+                    .remove(&key)
+                    .ok_or(DltNwReassemblyError::UnknownHandle(handle))?;
+
+                let expected = transfer.received.len() as u16;
+                if transfer.received_count != expected {
+                    return Err(DltNwReassemblyError::Incomplete {
+                        handle,
+                        received: transfer.received_count,
+                        expected,
+                    });
+                }
+                Ok(Some(transfer.trace))
+            }
+        }
+    }
+
+    /// Drop transfers that timed out, using the current time
+    pub fn expire(&mut self) -> Vec<u32> {
+        self.expire_at(Instant::now())
+    }
+
+    /// Drop transfers without activity for longer than the timeout at `now`
+    ///
+    /// # Returns
+    /// Handles of the dropped transfers
+    pub fn expire_at(&mut self, now: Instant) -> Vec<u32> {
+        let timeout = self.timeout;
+        let mut dropped = Vec::new();
+        self.transfers.retain(|(_, handle), transfer| {
+            let alive = now.saturating_duration_since(transfer.last_activity) <= timeout;
+            if !alive {
+                dropped.push(*handle);
+            }
+            alive
+        });
+        self.expired += dropped.len() as u64;
+        dropped
+    }
+
+    // ========================================
+    // Inspection
+    // ========================================
+
+    /// Number of transfers currently in progress
+    pub fn pending(&self) -> usize {
+        self.transfers.len()
+    }
+
+    /// Total number of transfers dropped because of a timeout
+    pub fn expired_count(&self) -> u64 {
+        self.expired
+    }
+}
@@ -0,0 +1,307 @@
+//! # Segmented Network Trace Messages
+//!
+//! Network trace payloads that do not fit into a single DLT message (Ethernet
+//! frames, SOME/IP messages, ...) are split into a sequence of verbose network
+//! trace messages, all carrying the same 32-bit stream handle:
+//!
+//! ```text
+//! Start: "NWST", handle (u32), header (raw), payload length (u32),
+//!        segment count (u16), segment size (u16)
+//! Chunk: "NWCH", handle (u32), sequence number (u16), data (raw)
+//! End:   "NWEN", handle (u32)
+//! ```
+//!
+//! Identifiers are ASCII strings; header and data are RAWD arguments whose
+//! length is exactly the number of bytes, without a null terminator.
+//! Sequence numbers start at 0. Every chunk except the last one carries exactly
+//! `segment size` bytes. This is the layout written by dlt-daemon's
+//! `dlt_user_trace_network_segmented()` and understood by dlt-viewer.
+//!
+//! [`DltNwSegmenter`] generates the messages for one payload, [`parse_nw_segment`]
+//! decodes a single received message. Collecting segments into complete payloads
+//! is done by `DltNwReassembler` (`std` feature).
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//!
+//! let frame = [0u8; 4000];
+//! let mut builder = DltMessageBuilder::new()
+//!     .with_app_id(b"APP1")
+//!     .with_context_id(b"ETH0");
+//! let mut segmenter = DltNwSegmenter::new(
+//!     MtinTypeDltNwTrace::DltNwTraceEthernet,
+//!     0x1234,
+//!     &[],
+//!     &frame,
+//! );
+//!
+//! let mut buffer = [0u8; 1200];
+//! while let Some(size) = segmenter.next_message(&mut builder, &mut buffer) {
+//!     let size = size.unwrap();
+//!     // send &buffer[..size]
+//! }
+//! ```
+
+use crate::r19_11::*;
+
+// ========================================
+// Segmented Trace Constants
+// ========================================
+
+/// Identifier of the message starting a segmented transfer
+pub const DLT_TRACE_NW_START: &str = "NWST";
+/// Identifier of a message carrying one segment
+pub const DLT_TRACE_NW_SEGMENT: &str = "NWCH";
+/// Identifier of the message ending a segmented transfer
+pub const DLT_TRACE_NW_END: &str = "NWEN";
+
+/// Default segment size (as used by dlt-daemon)
+pub const DLT_MAX_TRACE_SEGMENT_SIZE: u16 = 1010;
+
+// ========================================
+// Parsed Segment
+// ========================================
+
+/// One message of a segmented network trace transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DltNwSegment<'a> {
+    /// Start of a transfer ("NWST")
+    Start {
+        /// Stream handle shared by all messages of the transfer
+        handle: u32,
+        /// Bus-specific header of the traced frame
+        header: &'a [u8],
+        /// Total payload length in bytes
+        payload_length: u32,
+        /// Number of chunk messages that follow
+        segment_count: u16,
+        /// Size of every chunk except the last one
+        segment_size: u16,
+    },
+    /// One chunk of payload data ("NWCH")
+    Chunk {
+        /// Stream handle shared by all messages of the transfer
+        handle: u32,
+        /// Sequence number, starting at 0
+        sequence: u16,
+        /// Payload data of this chunk
+        data: &'a [u8],
+    },
+    /// End of a transfer ("NWEN")
+    End {
+        /// Stream handle shared by all messages of the transfer
+        handle: u32,
+    },
+}
+
+impl<'a> DltNwSegment<'a> {
+    /// Stream handle of the transfer this message belongs to
+    pub fn handle(&self) -> u32 {
+        match self {
+            DltNwSegment::Start { handle, .. }
+            | DltNwSegment::Chunk { handle, .. }
+            | DltNwSegment::End { handle } => *handle,
+        }
+    }
+}
+
+/// Decode a segmented network trace message
+///
+/// # Returns
+/// - `Ok(Some(DltNwSegment))`: Start, chunk or end message
+/// - `Ok(None)`: Not a segmented network trace message (other message type,
+///   non-verbose, or the first argument is not one of the identifiers)
+/// - `Err(PayloadError)`: Identifier found but the remaining arguments are malformed
+pub fn parse_nw_segment<'a>(
+    message: &DltMessage<'a>,
+) -> Result<Option<DltNwSegment<'a>>, PayloadError> {
+    let Some(ext) = message.extended_header else {
+        return Ok(None);
+    };
+    if ext.message_type() != MstpType::DltTypeNwTrace || !ext.is_verbose() {
+        return Ok(None);
+    }
+
+    let mut parser = PayloadParser::from_message(message);
+    let Ok(identifier) = parser.read_string() else {
+        return Ok(None);
+    };
+
+    let segment = match identifier {
+        DLT_TRACE_NW_START => DltNwSegment::Start {
+            handle: parser.read_u32()?,
+            header: parser.read_raw()?,
+            payload_length: parser.read_u32()?,
+            segment_count: parser.read_u16()?,
+            segment_size: parser.read_u16()?,
+        },
+        DLT_TRACE_NW_SEGMENT => DltNwSegment::Chunk {
+            handle: parser.read_u32()?,
+            sequence: parser.read_u16()?,
+            data: parser.read_raw()?,
+        },
+        DLT_TRACE_NW_END => DltNwSegment::End {
+            handle: parser.read_u32()?,
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(segment))
+}
+
+// ========================================
+// Segment Generator
+// ========================================
+
+/// Splits one network trace payload into start, chunk and end messages
+///
+/// Call [`DltNwSegmenter::next_message`] until it returns `None`. Each call
+/// writes one complete DLT message using the given builder, so message counter,
+/// IDs and byte order follow the builder's configuration.
+pub struct DltNwSegmenter<'p> {
+    trace_type: MtinTypeDltNwTrace,
+    handle: u32,
+    header: &'p [u8],
+    payload: &'p [u8],
+    segment_size: u16,
+    /// Index of the next message: 0 = start, 1..=count = chunks, count + 1 = end
+    next: usize,
+}
+
+impl<'p> DltNwSegmenter<'p> {
+    /// Create a segmenter for `payload` using [`DLT_MAX_TRACE_SEGMENT_SIZE`]
+    pub fn new(
+        trace_type: MtinTypeDltNwTrace,
+        handle: u32,
+        header: &'p [u8],
+        payload: &'p [u8],
+    ) -> Self {
+        Self {
+            trace_type,
+            handle,
+            header,
+            payload,
+            segment_size: DLT_MAX_TRACE_SEGMENT_SIZE,
+            next: 0,
+        }
+    }
+
+    /// Set the number of payload bytes per chunk message
+    pub fn with_segment_size(mut self, segment_size: u16) -> Self {
+        self.segment_size = segment_size.max(1);
+        self
+    }
+
+    /// Number of chunk messages
+    pub fn segment_count(&self) -> usize {
+        self.payload.len().div_ceil(self.segment_size as usize)
+    }
+
+    /// Total number of messages (start + chunks + end)
+    pub fn message_count(&self) -> usize {
+        self.segment_count() + 2
+    }
+
+    /// Whether all messages have been generated
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.message_count()
+    }
+
+    /// Write the next message of the transfer into `buffer`
+    ///
+    /// # Returns
+    /// - `Some(Ok(usize))`: Size of the generated message
+    /// - `Some(Err(DltError::BufferTooSmall))`: Message doesn't fit (retry with a larger buffer)
+    /// - `Some(Err(DltError::InvalidParameter))`: Payload too large for 16-bit
+    ///   segment numbering or 32-bit length
+    /// - `None`: All messages have been generated
+    pub fn next_message(
+        &mut self,
+        builder: &mut DltMessageBuilder,
+        buffer: &mut [u8],
+    ) -> Option<Result<usize, DltError>> {
+        if self.is_finished() {
+            return None;
+        }
+
+        let result = if self.next == 0 {
+            self.write_start(builder, buffer)
+        } else if self.next <= self.segment_count() {
+            self.write_chunk(builder, buffer, self.next - 1)
+        } else {
+            self.write_end(builder, buffer)
+        };
+
+        if result.is_ok() {
+            self.next += 1;
+        }
+        Some(result)
+    }
+
+    fn write_start(
+        &self,
+        builder: &mut DltMessageBuilder,
+        buffer: &mut [u8],
+    ) -> Result<usize, DltError> {
+        let payload_length =
+            u32::try_from(self.payload.len()).map_err(|_| DltError::InvalidParameter)?;
+        let segment_count =
+            u16::try_from(self.segment_count()).map_err(|_| DltError::InvalidParameter)?;
+
+        builder._generate_verbose_message(
+            buffer,
+            MstpType::DltTypeNwTrace,
+            self.trace_type.to_bits(),
+            6,
+            |args| {
+                args.add_string_with_coding(DLT_TRACE_NW_START, DltStringCoding::Ascii)?;
+                args.add_u32(self.handle)?;
+                args.add_raw(self.header)?;
+                args.add_u32(payload_length)?;
+                args.add_u16(segment_count)?;
+                args.add_u16(self.segment_size)
+            },
+        )
+    }
+
+    fn write_chunk(
+        &self,
+        builder: &mut DltMessageBuilder,
+        buffer: &mut [u8],
+        sequence: usize,
+    ) -> Result<usize, DltError> {
+        let start = sequence * self.segment_size as usize;
+        let end = (start + self.segment_size as usize).min(self.payload.len());
+
+        builder._generate_verbose_message(
+            buffer,
+            MstpType::DltTypeNwTrace,
+            self.trace_type.to_bits(),
+            4,
+            |args| {
+                args.add_string_with_coding(DLT_TRACE_NW_SEGMENT, DltStringCoding::Ascii)?;
+                args.add_u32(self.handle)?;
+                args.add_u16(sequence as u16)?;
+                args.add_raw(&self.payload[start..end])
+            },
+        )
+    }
+
+    fn write_end(
+        &self,
+        builder: &mut DltMessageBuilder,
+        buffer: &mut [u8],
+    ) -> Result<usize, DltError> {
+        builder._generate_verbose_message(
+            buffer,
+            MstpType::DltTypeNwTrace,
+            self.trace_type.to_bits(),
+            2,
+            |args| {
+                args.add_string_with_coding(DLT_TRACE_NW_END, DltStringCoding::Ascii)?;
+                args.add_u32(self.handle)
+            },
+        )
+    }
+}
@@ -0,0 +1,134 @@
+//! # Owned Messages (requires `std` feature)
+//!
+//! [`DltMessage`] borrows its payload from the parsed buffer, which ties it to
+//! the lifetime of that buffer. [`DltOwnedMessage`] holds the same fields with
+//! an owned payload, so messages can be stored, queued, modified and forwarded
+//! independently of the receive buffer.
+//!
+//! Both types are written back to bytes with the same layout they were parsed
+//! from (see [`DltMessage::write`]): parse → write is byte-exact. The HTYP flags
+//! and the length field follow the fields, so after changing e.g. the ECU ID or
+//! the payload the encoded message is still consistent.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//!
+//! let received: &[u8] = &[/* DLT message bytes */];
+//!
+//! let mut message = DltOwnedMessage::parse(received).unwrap();
+//! message.ecu_id = Some(*b"GW01");
+//! if let Some(extended) = message.extended_header.as_mut() {
+//!     extended.apid = *b"FWD1";
+//! }
+//!
+//! let forwarded: Vec<u8> = message.to_bytes().unwrap();
+//! ```
+
+use crate::r19_11::*;
+
+// ========================================
+// Owned Message
+// ========================================
+
+/// DLT message that owns its payload
+///
+/// Fields have the same meaning as in [`DltMessage`]. When writing, the HTYP
+/// flags UEH/WEID/WSID/WTMS and the length field are derived from the fields;
+/// `standard_header.len` and `header_type` are only updated by
+/// [`DltOwnedMessage::as_message`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DltOwnedMessage {
+    /// Whether the message includes a serial header
+    pub has_serial_header: bool,
+    /// Standard header (HTYP version/MSBF, message counter)
+    pub standard_header: DltStandardHeader,
+    /// Decoded header type flags, as parsed
+    pub header_type: DltHTYP,
+    /// ECU ID (written if present)
+    pub ecu_id: Option<[u8; DLT_ID_SIZE]>,
+    /// Session ID (written if present)
+    pub session_id: Option<u32>,
+    /// Timestamp in 0.1ms units (written if present)
+    pub timestamp: Option<u32>,
+    /// Extended header (written if present)
+    pub extended_header: Option<DltExtendedHeader>,
+    /// Message payload (raw bytes)
+    pub payload: Vec<u8>,
+}
+
+impl DltOwnedMessage {
+    /// Parse a message from the start of `data` and copy it
+    ///
+    /// # Returns
+    /// - `Ok(DltOwnedMessage)`: Successfully parsed message
+    /// - `Err(DltHeaderError)`: Parsing failed (see [`DltHeaderParser::parse_message`])
+    pub fn parse(data: &[u8]) -> Result<Self, DltHeaderError> {
+        DltHeaderParser::new(data).parse_message().map(Self::from)
+    }
+
+    /// Borrowed view of the message, with HTYP and length matching the fields
+    pub fn as_message(&self) -> DltMessage<'_> {
+        let mut message = DltMessage {
+            has_serial_header: self.has_serial_header,
+            standard_header: self.standard_header,
+            header_type: self.header_type,
+            ecu_id: self.ecu_id,
+            session_id: self.session_id,
+            timestamp: self.timestamp,
+            extended_header: self.extended_header,
+            payload: &self.payload,
+        };
+        message.sync_header();
+        message
+    }
+
+    /// Size of the encoded message, including the serial header if present
+    pub fn encoded_len(&self) -> usize {
+        self.as_message().encoded_len()
+    }
+
+    /// Write the message into the start of `buffer`
+    ///
+    /// # Returns
+    /// - `Ok(usize)`: Number of bytes written
+    /// - `Err(DltError::BufferTooSmall)`: Buffer shorter than [`DltOwnedMessage::encoded_len`]
+    /// - `Err(DltError::InvalidParameter)`: Message too long for the 16-bit length field
+    pub fn write(&self, buffer: &mut [u8]) -> Result<usize, DltError> {
+        self.as_message().write(buffer)
+    }
+
+    /// Encode the message into a new vector
+    ///
+    /// # Returns
+    /// - `Ok(Vec<u8>)`: Encoded message
+    /// - `Err(DltError::InvalidParameter)`: Message too long for the 16-bit length field
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DltError> {
+        let message = self.as_message();
+        let mut bytes = vec![0u8; message.encoded_len()];
+        message.write(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl From<DltMessage<'_>> for DltOwnedMessage {
+    fn from(message: DltMessage<'_>) -> Self {
+        Self {
+            has_serial_header: message.has_serial_header,
+            standard_header: message.standard_header,
+            header_type: message.header_type,
+            ecu_id: message.ecu_id,
+            session_id: message.session_id,
+            timestamp: message.timestamp,
+            extended_header: message.extended_header,
+            payload: message.payload.to_vec(),
+        }
+    }
+}
+
+impl From<&DltMessage<'_>> for DltOwnedMessage {
+    fn from(message: &DltMessage<'_>) -> Self {
+        Self::from(*message)
+    }
+}
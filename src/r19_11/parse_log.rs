@@ -33,7 +33,8 @@
 //! // Check if it's a verbose log message
 //! if let Some(ext_hdr) = message.extended_header {
 //!     if ext_hdr.is_verbose() {
-//!         let mut payload_parser = PayloadParser::new(message.payload);
+//!         // Honors the message byte order (MSBF flag)
+//!         let mut payload_parser = PayloadParser::from_message(&message);
 //!         
 //!         // Parse first argument
 //!         match payload_parser.read_next() {
@@ -46,6 +47,7 @@
 //! ```
 
 use crate::r19_11::common::DltError;
+use crate::r19_11::{DltEndian, DltMessage};
 
 // ========================================
 // Payload Type Enumerations
@@ -65,6 +67,7 @@ pub enum PayloadType {
     FixedPoint,
     TraceInfo,
     Struct,
+    /// SCOD bits; see [`DltStringCoding`] for decoding them
     StringCoding,
     Reserved,
     Invalid,
@@ -106,7 +109,26 @@ impl TypeLength {
 }
 
 impl PayloadType {
+    /// Determine the argument type from a type info field
+    ///
+    /// Container types (ARAY, STRU) and TRAI take precedence over the basic type
+    /// bits, since an array of UINT carries both the ARAY and the UINT flag.
+    /// VARI, FIXP and SCOD are modifiers and never returned on their own.
     pub fn parse(type_info: u32) -> Option<Self> {
+        if type_info & (1 << 8) != 0 {
+            return Some(PayloadType::Array);
+        }
+        if type_info & (1 << 14) != 0 {
+            return Some(PayloadType::Struct);
+        }
+        if type_info & (1 << 13) != 0 {
+            return Some(PayloadType::TraceInfo);
+        }
+        Self::parse_basic(type_info)
+    }
+
+    /// Determine the basic type (BOOL, SINT, UINT, FLOA, STRG, RAWD), ignoring containers
+    pub fn parse_basic(type_info: u32) -> Option<Self> {
         if type_info & (1 << 4) != 0 {
             return Some(PayloadType::Bool);
         }
@@ -119,30 +141,12 @@ impl PayloadType {
         if type_info & (1 << 7) != 0 {
             return Some(PayloadType::Float);
         }
-        if type_info & (1 << 8) != 0 {
-            return Some(PayloadType::Array);
-        }
         if type_info & (1 << 9) != 0 {
             return Some(PayloadType::String);
         }
         if type_info & (1 << 10) != 0 {
             return Some(PayloadType::Raw);
         }
-        if type_info & (1 << 11) != 0 {
-            return Some(PayloadType::VariableInfo);
-        }
-        if type_info & (1 << 12) != 0 {
-            return Some(PayloadType::FixedPoint);
-        }
-        if type_info & (1 << 13) != 0 {
-            return Some(PayloadType::TraceInfo);
-        }
-        if type_info & (1 << 14) != 0 {
-            return Some(PayloadType::Struct);
-        }
-        if type_info & (1 << 15) != 0 {
-            return Some(PayloadType::StringCoding);
-        }
         None
     }
 
@@ -175,6 +179,8 @@ pub enum PayloadError {
     InvalidType,
     InvalidData,
     UnsupportedLength,
+    /// Non-verbose message ID not found in the message catalog
+    UnknownMessageId(u32),
 }
 
 // ========================================
@@ -197,8 +203,510 @@ pub enum DltValue<'a> {
     U128(u128),
     F32(f32),
     F64(f64),
-    String(&'a str),
+    /// String with its coding (STRG with SCOD)
+    String(DltString<'a>),
     Raw(&'a [u8]),
+    /// Array of bools, integers or floats (ARAY)
+    Array(DltArray<'a>),
+    /// Struct of nested typed entries (STRU)
+    Struct(DltStruct<'a>),
+    /// Fixed-point integer with quantization and offset (FIXP)
+    FixedPoint(DltFixedPoint),
+    /// Trace info: name of the originating module or function (TRAI)
+    TraceInfo(&'a str),
+}
+
+// ========================================
+// Fixed-Point Value (FIXP)
+// ========================================
+
+/// A fixed-point argument: raw integer plus scaling to its physical value
+///
+/// `physical = raw * quantization + offset`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DltFixedPoint {
+    /// Raw integer value as transmitted
+    pub raw: i128,
+    /// Whether the raw value is signed (SINT) or unsigned (UINT)
+    pub signed: bool,
+    /// Size of the raw value
+    pub type_length: TypeLength,
+    /// Quantization (scale factor)
+    pub quantization: f32,
+    /// Offset added after scaling
+    pub offset: i64,
+}
+
+impl DltFixedPoint {
+    /// Physical value: `raw * quantization + offset`
+    pub fn physical(&self) -> f64 {
+        self.raw as f64 * self.quantization as f64 + self.offset as f64
+    }
+
+    /// Raw value in its transmitted integer type (e.g. `DltValue::I16`)
+    pub fn raw_value(&self) -> DltValue<'static> {
+        let raw = self.raw;
+        match (self.signed, self.type_length) {
+            (true, TypeLength::Bit8) => DltValue::I8(raw as i8),
+            (true, TypeLength::Bit16) => DltValue::I16(raw as i16),
+            (true, TypeLength::Bit32) => DltValue::I32(raw as i32),
+            (true, _) => DltValue::I64(raw as i64),
+            (false, TypeLength::Bit8) => DltValue::U8(raw as u8),
+            (false, TypeLength::Bit16) => DltValue::U16(raw as u16),
+            (false, TypeLength::Bit32) => DltValue::U32(raw as u32),
+            (false, _) => DltValue::U64(raw as u64),
+        }
+    }
+}
+
+// ========================================
+// String Coding (SCOD) and Coded Strings
+// ========================================
+
+/// Character encoding of a string argument (SCOD, type info bits 15-17)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DltStringCoding {
+    /// ASCII (legacy ECUs often send Latin-1 with this coding)
+    Ascii,
+    /// UTF-8
+    Utf8,
+    /// Reserved coding value (2-7)
+    Reserved(u8),
+}
+
+impl DltStringCoding {
+    /// Mask of the SCOD bits in the type info field
+    pub const MASK: u32 = 0x07 << 15;
+
+    /// Extract the string coding from a type info field
+    pub fn from_type_info(type_info: u32) -> Self {
+        match ((type_info & Self::MASK) >> 15) as u8 {
+            0 => DltStringCoding::Ascii,
+            1 => DltStringCoding::Utf8,
+            value => DltStringCoding::Reserved(value),
+        }
+    }
+
+    /// Type info bits for this coding
+    pub fn to_bits(self) -> u32 {
+        let value = match self {
+            DltStringCoding::Ascii => 0,
+            DltStringCoding::Utf8 => 1,
+            DltStringCoding::Reserved(value) => value & 0x07,
+        };
+        (value as u32) << 15
+    }
+}
+
+/// A string argument's bytes together with its coding
+///
+/// Parsed string arguments keep their coding, so ASCII and UTF-8 text can be
+/// told apart. The bytes need not be valid UTF-8, so text from legacy ECUs
+/// (e.g. Latin-1 sent with ASCII coding) can still be displayed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DltString<'a> {
+    bytes: &'a [u8],
+    coding: DltStringCoding,
+}
+
+impl<'a> DltString<'a> {
+    /// Create a string from its bytes (without null terminator) and coding
+    pub const fn new(bytes: &'a [u8], coding: DltStringCoding) -> Self {
+        Self { bytes, coding }
+    }
+
+    /// ASCII-coded string from text
+    pub const fn ascii(text: &'a str) -> Self {
+        Self::new(text.as_bytes(), DltStringCoding::Ascii)
+    }
+
+    /// UTF-8-coded string from text
+    pub const fn utf8(text: &'a str) -> Self {
+        Self::new(text.as_bytes(), DltStringCoding::Utf8)
+    }
+
+    /// String coding from the type info
+    pub fn coding(&self) -> DltStringCoding {
+        self.coding
+    }
+
+    /// Raw string bytes (without null terminator)
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Text as `&str`, if the bytes are valid UTF-8
+    ///
+    /// Pure ASCII is valid UTF-8, so this succeeds for any well-formed ASCII string.
+    pub fn as_str(&self) -> Option<&'a str> {
+        core::str::from_utf8(self.bytes).ok()
+    }
+
+    /// Length in bytes
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether the string is empty
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Characters of the string, decoded lossily
+    ///
+    /// Valid UTF-8 is decoded as such. Otherwise ASCII-coded strings are decoded
+    /// as Latin-1 (every byte maps to one character) and invalid UTF-8 sequences
+    /// are replaced with U+FFFD.
+    pub fn chars(&self) -> DltStringChars<'a> {
+        let latin1 = self.coding == DltStringCoding::Ascii && self.as_str().is_none();
+        DltStringChars {
+            latin1,
+            bytes: self.bytes,
+            chunks: self.bytes.utf8_chunks(),
+            current: "".chars(),
+            invalid: false,
+        }
+    }
+}
+
+impl core::fmt::Display for DltString<'_> {
+    /// Writes the lossily decoded text (see [`DltString::chars`])
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(text) = self.as_str() {
+            return f.write_str(text);
+        }
+        for c in self.chars() {
+            core::fmt::Write::write_char(f, c)?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the lossily decoded characters of a [`DltString`]
+pub struct DltStringChars<'a> {
+    /// Decode every byte as one Latin-1 character
+    latin1: bool,
+    /// Remaining bytes (Latin-1 mode)
+    bytes: &'a [u8],
+    /// Remaining UTF-8 chunks (UTF-8 mode)
+    chunks: core::str::Utf8Chunks<'a>,
+    /// Characters of the current valid chunk
+    current: core::str::Chars<'a>,
+    /// Whether the current chunk ended with an invalid sequence
+    invalid: bool,
+}
+
+impl Iterator for DltStringChars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.latin1 {
+            let (&byte, rest) = self.bytes.split_first()?;
+            self.bytes = rest;
+            return Some(char::from(byte));
+        }
+
+        loop {
+            if let Some(c) = self.current.next() {
+                return Some(c);
+            }
+            if self.invalid {
+                self.invalid = false;
+                return Some(char::REPLACEMENT_CHARACTER);
+            }
+            let chunk = self.chunks.next()?;
+            self.current = chunk.valid().chars();
+            self.invalid = !chunk.invalid().is_empty();
+        }
+    }
+}
+
+// ========================================
+// Parsed Argument (Value + Variable Info)
+// ========================================
+
+/// A parsed argument together with its variable info (VARI)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DltArgument<'a> {
+    /// Variable name, if the VARI flag is set and a name is present
+    pub name: Option<&'a str>,
+    /// Unit (numeric types and arrays only), if present
+    pub unit: Option<&'a str>,
+    /// Argument value
+    pub value: DltValue<'a>,
+}
+
+// ========================================
+// Array Value (ARAY)
+// ========================================
+
+/// Borrowed view of an array argument
+///
+/// Elements are decoded on access; nothing is copied out of the payload.
+/// Multi-dimensional arrays are stored in row-major order.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DltArray<'a> {
+    /// Variable name (VARI)
+    name: Option<&'a str>,
+    /// Unit (VARI)
+    unit: Option<&'a str>,
+    /// Quantization and offset (FIXP)
+    fixed_point: Option<(f32, i64)>,
+    /// Element type (Bool, Signed, Unsigned or Float)
+    element_type: PayloadType,
+    /// Element size
+    type_length: TypeLength,
+    /// Raw dimension lengths (u16 each, payload byte order)
+    dimensions: &'a [u8],
+    /// Raw element data
+    data: &'a [u8],
+    /// Byte order of dimensions and elements
+    endian: DltEndian,
+}
+
+impl<'a> DltArray<'a> {
+    /// Variable name, if present
+    pub fn name(&self) -> Option<&'a str> {
+        self.name
+    }
+
+    /// Unit, if present
+    pub fn unit(&self) -> Option<&'a str> {
+        self.unit
+    }
+
+    /// Quantization and offset, if the elements are fixed-point (FIXP)
+    pub fn fixed_point(&self) -> Option<(f32, i64)> {
+        self.fixed_point
+    }
+
+    /// Physical value of the element at `index` (fixed-point arrays only)
+    pub fn get_physical(&self, index: usize) -> Option<f64> {
+        let (quantization, offset) = self.fixed_point?;
+        let raw = scalar_to_i128(self.get(index)?)?;
+        Some(raw as f64 * quantization as f64 + offset as f64)
+    }
+
+    /// Element type (Bool, Signed, Unsigned or Float)
+    pub fn element_type(&self) -> PayloadType {
+        self.element_type
+    }
+
+    /// Element size
+    pub fn type_length(&self) -> TypeLength {
+        self.type_length
+    }
+
+    /// Number of dimensions
+    pub fn dimension_count(&self) -> usize {
+        self.dimensions.len() / 2
+    }
+
+    /// Number of entries in dimension `index`
+    pub fn dimension(&self, index: usize) -> Option<u16> {
+        let bytes = self.dimensions.get(index * 2..index * 2 + 2)?;
+        Some(read_u16_field([bytes[0], bytes[1]], self.endian))
+    }
+
+    /// Iterate over the lengths of all dimensions
+    pub fn dimensions(&self) -> impl Iterator<Item = u16> + 'a {
+        let endian = self.endian;
+        self.dimensions
+            .chunks_exact(2)
+            .map(move |bytes| read_u16_field([bytes[0], bytes[1]], endian))
+    }
+
+    /// Total number of elements
+    pub fn len(&self) -> usize {
+        self.data.len() / self.type_length.to_bytes()
+    }
+
+    /// Check if the array has no elements
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Get the element at flat (row-major) position `index`
+    pub fn get(&self, index: usize) -> Option<DltValue<'a>> {
+        let size = self.type_length.to_bytes();
+        let bytes = self.data.get(index * size..(index + 1) * size)?;
+        decode_scalar(self.element_type, bytes, self.endian)
+    }
+
+    /// Get the element at a multi-dimensional position (one index per dimension)
+    pub fn get_at(&self, indices: &[usize]) -> Option<DltValue<'a>> {
+        if indices.len() != self.dimension_count() {
+            return None;
+        }
+        let mut flat = 0usize;
+        for (index, dimension) in indices.iter().zip(self.dimensions()) {
+            if *index >= dimension as usize {
+                return None;
+            }
+            flat = flat * dimension as usize + index;
+        }
+        self.get(flat)
+    }
+
+    /// Iterate over all elements in row-major order
+    pub fn iter(&self) -> DltArrayIter<'a> {
+        DltArrayIter {
+            array: *self,
+            index: 0,
+        }
+    }
+
+    /// Raw element data in payload byte order
+    pub fn raw_data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+/// Iterator over the elements of a [`DltArray`]
+pub struct DltArrayIter<'a> {
+    array: DltArray<'a>,
+    index: usize,
+}
+
+impl<'a> Iterator for DltArrayIter<'a> {
+    type Item = DltValue<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.array.get(self.index)?;
+        self.index += 1;
+        Some(value)
+    }
+}
+
+// ========================================
+// Struct Value (STRU)
+// ========================================
+
+/// Maximum nesting depth of struct arguments (builder and parser)
+pub const DLT_MAX_STRUCT_DEPTH: usize = 8;
+
+/// Borrowed view of a struct argument
+///
+/// The entries are stored as regular typed arguments and decoded on iteration.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DltStruct<'a> {
+    /// Variable name (VARI)
+    name: Option<&'a str>,
+    /// Number of entries
+    entry_count: u16,
+    /// Encoded entries (type info + data each)
+    data: &'a [u8],
+    /// Byte order of the entries
+    endian: DltEndian,
+}
+
+impl<'a> DltStruct<'a> {
+    /// Variable name, if present
+    pub fn name(&self) -> Option<&'a str> {
+        self.name
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entry_count as usize
+    }
+
+    /// Check if the struct has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entry_count == 0
+    }
+
+    /// Iterate over the entries (nested structs can be iterated recursively)
+    pub fn fields(&self) -> DltStructFields<'a> {
+        DltStructFields {
+            parser: PayloadParser::with_endian(self.data, self.endian),
+            remaining: self.entry_count,
+        }
+    }
+
+    /// Payload parser positioned at the first entry
+    ///
+    /// Use [`PayloadParser::read_argument`] on it to access entry names and units.
+    pub fn parser(&self) -> PayloadParser<'a> {
+        PayloadParser::with_endian(self.data, self.endian)
+    }
+
+    /// Encoded entries in payload byte order
+    pub fn raw_data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+/// Iterator over the entries of a [`DltStruct`]
+pub struct DltStructFields<'a> {
+    parser: PayloadParser<'a>,
+    remaining: u16,
+}
+
+impl<'a> Iterator for DltStructFields<'a> {
+    type Item = Result<DltValue<'a>, PayloadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let entry = self.parser.read_next();
+        if entry.is_err() {
+            self.remaining = 0;
+        }
+        Some(entry)
+    }
+}
+
+/// Read a u16 field in the given byte order
+pub(crate) fn read_u16_field(bytes: [u8; 2], endian: DltEndian) -> u16 {
+    match endian {
+        DltEndian::Big => u16::from_be_bytes(bytes),
+        DltEndian::Little => u16::from_le_bytes(bytes),
+    }
+}
+
+/// Convert a decoded integer value to i128
+fn scalar_to_i128(value: DltValue) -> Option<i128> {
+    match value {
+        DltValue::I8(v) => Some(v as i128),
+        DltValue::I16(v) => Some(v as i128),
+        DltValue::I32(v) => Some(v as i128),
+        DltValue::I64(v) => Some(v as i128),
+        DltValue::U8(v) => Some(v as i128),
+        DltValue::U16(v) => Some(v as i128),
+        DltValue::U32(v) => Some(v as i128),
+        DltValue::U64(v) => Some(v as i128),
+        _ => None,
+    }
+}
+
+/// Decode a fixed-size bool, integer or float from `bytes`
+pub(crate) fn decode_scalar<'a>(ptype: PayloadType, bytes: &[u8], endian: DltEndian) -> Option<DltValue<'a>> {
+    let mut le = [0u8; 16];
+    let le = &mut le[..bytes.len()];
+    le.copy_from_slice(bytes);
+    if endian == DltEndian::Big {
+        le.reverse();
+    }
+
+    let value = match (ptype, le.len()) {
+        (PayloadType::Bool, 1) => DltValue::Bool(le[0] != 0),
+        (PayloadType::Signed, 1) => DltValue::I8(le[0] as i8),
+        (PayloadType::Signed, 2) => DltValue::I16(i16::from_le_bytes(le.try_into().ok()?)),
+        (PayloadType::Signed, 4) => DltValue::I32(i32::from_le_bytes(le.try_into().ok()?)),
+        (PayloadType::Signed, 8) => DltValue::I64(i64::from_le_bytes(le.try_into().ok()?)),
+        (PayloadType::Unsigned, 1) => DltValue::U8(le[0]),
+        (PayloadType::Unsigned, 2) => DltValue::U16(u16::from_le_bytes(le.try_into().ok()?)),
+        (PayloadType::Unsigned, 4) => DltValue::U32(u32::from_le_bytes(le.try_into().ok()?)),
+        (PayloadType::Unsigned, 8) => DltValue::U64(u64::from_le_bytes(le.try_into().ok()?)),
+        (PayloadType::Unsigned, 16) => DltValue::U128(u128::from_le_bytes(le.try_into().ok()?)),
+        (PayloadType::Float, 4) => DltValue::F32(f32::from_le_bytes(le.try_into().ok()?)),
+        (PayloadType::Float, 8) => DltValue::F64(f64::from_le_bytes(le.try_into().ok()?)),
+        _ => return None,
+    };
+    Some(value)
 }
 
 // ========================================
@@ -227,12 +735,43 @@ pub enum DltValue<'a> {
 pub struct PayloadParser<'a> {
     data: &'a [u8],
     position: usize,
+    /// Byte order of type info, length fields and values
+    endian: DltEndian,
+    /// Current struct nesting depth while reading
+    depth: usize,
 }
 
 impl<'a> PayloadParser<'a> {
-    /// Create a new payload parser from raw payload data
+    /// Create a new payload parser from raw payload data (little-endian)
     pub fn new(data: &'a [u8]) -> Self {
-        Self { data, position: 0 }
+        Self::with_endian(data, DltEndian::Little)
+    }
+
+    /// Create a new payload parser for payload data in the given byte order
+    pub fn with_endian(data: &'a [u8], endian: DltEndian) -> Self {
+        Self {
+            data,
+            position: 0,
+            endian,
+            depth: 0,
+        }
+    }
+
+    /// Create a payload parser for a parsed message's payload
+    ///
+    /// The byte order is taken from the MSBF flag of the standard header.
+    pub fn from_message(message: &DltMessage<'a>) -> Self {
+        let endian = if message.header_type.MSBF {
+            DltEndian::Big
+        } else {
+            DltEndian::Little
+        };
+        Self::with_endian(message.payload, endian)
+    }
+
+    /// Get the byte order used for decoding
+    pub fn endian(&self) -> DltEndian {
+        self.endian
     }
 
     /// Get number of remaining bytes
@@ -254,11 +793,23 @@ impl<'a> PayloadParser<'a> {
         Ok(slice)
     }
 
-    /// Read and parse the next type info field
-    pub fn read_type_info(&mut self) -> Result<(PayloadType, TypeLength), PayloadError> {
-        let bytes = self.read_bytes(4)?;
-        let type_info = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    /// Read an N-byte field and return it in little-endian byte order
+    fn read_ordered<const N: usize>(&mut self) -> Result<[u8; N], PayloadError> {
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(self.read_bytes(N)?);
+        if self.endian == DltEndian::Big {
+            bytes.reverse();
+        }
+        Ok(bytes)
+    }
 
+    /// Read a 16-bit length field in the payload byte order
+    fn read_length(&mut self) -> Result<usize, PayloadError> {
+        Ok(u16::from_le_bytes(self.read_ordered()?) as usize)
+    }
+
+    /// Decode a type info value into payload type and type length
+    fn decode_type_info(type_info: u32) -> Result<(PayloadType, TypeLength), PayloadError> {
         let type_length = match type_info & 0x0F {
             0x00 => TypeLength::NotDefined,
             0x01 => TypeLength::Bit8,
@@ -274,261 +825,434 @@ impl<'a> PayloadParser<'a> {
         Ok((payload_type, type_length))
     }
 
-    /// Read a boolean value
-    pub fn read_bool(&mut self) -> Result<bool, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Bool || tlen != TypeLength::Bit8 {
+    /// Read and parse the next type info field
+    pub fn read_type_info(&mut self) -> Result<(PayloadType, TypeLength), PayloadError> {
+        let type_info = self.read_raw_type_info()?;
+        Self::decode_type_info(type_info)
+    }
+
+    /// Read the next type info field without decoding its flags
+    fn read_raw_type_info(&mut self) -> Result<u32, PayloadError> {
+        Ok(u32::from_le_bytes(self.read_ordered()?))
+    }
+
+    /// Read the type info of a bool/integer/float argument and skip its variable info
+    fn read_scalar_type_info(
+        &mut self,
+        expected_type: PayloadType,
+        expected_length: TypeLength,
+    ) -> Result<(), PayloadError> {
+        let type_info = self.read_raw_type_info()?;
+        let (ptype, tlen) = Self::decode_type_info(type_info)?;
+        if ptype != expected_type || tlen != expected_length {
             return Err(PayloadError::InvalidType);
         }
+        self.read_variable_info(type_info, ptype != PayloadType::Bool)?;
+        self.read_fixed_point_info(type_info, ptype, tlen)?;
+        Ok(())
+    }
+
+    /// Read quantization and offset (FIXP) of an integer argument, if the flag is set
+    ///
+    /// The offset is 32 bits wide for 8/16/32-bit values and 64 bits for 64-bit values.
+    fn read_fixed_point_info(
+        &mut self,
+        type_info: u32,
+        ptype: PayloadType,
+        tlen: TypeLength,
+    ) -> Result<Option<(f32, i64)>, PayloadError> {
+        if type_info & PayloadType::FixedPoint.to_bit() == 0 {
+            return Ok(None);
+        }
+        if !matches!(ptype, PayloadType::Signed | PayloadType::Unsigned) {
+            return Err(PayloadError::InvalidType);
+        }
+
+        let quantization = f32::from_le_bytes(self.read_ordered()?);
+        let offset = match tlen {
+            TypeLength::Bit8 | TypeLength::Bit16 | TypeLength::Bit32 => {
+                i32::from_le_bytes(self.read_ordered()?) as i64
+            }
+            TypeLength::Bit64 => i64::from_le_bytes(self.read_ordered()?),
+            _ => return Err(PayloadError::UnsupportedLength),
+        };
+        Ok(Some((quantization, offset)))
+    }
+
+    /// Read the variable info (VARI) of an argument, if the flag is set
+    ///
+    /// Numeric types and arrays carry name and unit (both lengths first, then both
+    /// strings); bool, string, raw and struct arguments carry a name only.
+    fn read_variable_info(
+        &mut self,
+        type_info: u32,
+        with_unit: bool,
+    ) -> Result<(Option<&'a str>, Option<&'a str>), PayloadError> {
+        if type_info & PayloadType::VariableInfo.to_bit() == 0 {
+            return Ok((None, None));
+        }
+
+        let name_len = self.read_length()?;
+        let unit_len = if with_unit { self.read_length()? } else { 0 };
+        let name = self.read_name(name_len)?;
+        let unit = self.read_name(unit_len)?;
+        Ok((name, unit))
+    }
+
+    /// Read a null-terminated name or unit string of `len` bytes (0 = absent)
+    fn read_name(&mut self, len: usize) -> Result<Option<&'a str>, PayloadError> {
+        if len == 0 {
+            return Ok(None);
+        }
+        let bytes = self.read_bytes(len)?;
+        let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+        core::str::from_utf8(bytes)
+            .map(Some)
+            .map_err(|_| PayloadError::InvalidData)
+    }
+
+    /// Read a string or raw argument: returns its name, data (without null terminator)
+    /// and type info
+    ///
+    /// Layout after the type info: length (u16), optional variable info (name), then
+    /// the data. For strings and trace info the length includes a null terminator;
+    /// raw data (RAWD) has none, so all `length` bytes are returned.
+    fn read_string_parts(
+        &mut self,
+        expected_type: PayloadType,
+    ) -> Result<(Option<&'a str>, &'a [u8], u32), PayloadError> {
+        let type_info = self.read_raw_type_info()?;
+        let (ptype, _) = Self::decode_type_info(type_info)?;
+        if ptype != expected_type {
+            return Err(PayloadError::InvalidType);
+        }
+
+        let len = self.read_length()?;
+        let (name, _) = self.read_variable_info(type_info, false)?;
+
+        if ptype == PayloadType::Raw {
+            return Ok((name, self.read_bytes(len)?, type_info));
+        }
+        if len == 0 {
+            return Err(PayloadError::InvalidData);
+        }
+
+        // Read data (including null terminator)
+        let data = self.read_bytes(len)?;
+
+        // Verify null terminator
+        if data[len - 1] != 0 {
+            return Err(PayloadError::InvalidData);
+        }
+
+        Ok((name, &data[..len - 1], type_info))
+    }
+
+    /// Read a boolean value
+    pub fn read_bool(&mut self) -> Result<bool, PayloadError> {
+        self.read_scalar_type_info(PayloadType::Bool, TypeLength::Bit8)?;
         let bytes = self.read_bytes(1)?;
         Ok(bytes[0] != 0)
     }
 
     /// Read a signed 8-bit integer
     pub fn read_i8(&mut self) -> Result<i8, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Signed || tlen != TypeLength::Bit8 {
-            return Err(PayloadError::InvalidType);
-        }
-        let bytes = self.read_bytes(1)?;
-        Ok(i8::from_le_bytes([bytes[0]]))
+        self.read_scalar_type_info(PayloadType::Signed, TypeLength::Bit8)?;
+        Ok(i8::from_le_bytes(self.read_ordered()?))
     }
 
     /// Read a signed 16-bit integer
     pub fn read_i16(&mut self) -> Result<i16, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Signed || tlen != TypeLength::Bit16 {
-            return Err(PayloadError::InvalidType);
-        }
-        let bytes = self.read_bytes(2)?;
-        Ok(i16::from_le_bytes([bytes[0], bytes[1]]))
+        self.read_scalar_type_info(PayloadType::Signed, TypeLength::Bit16)?;
+        Ok(i16::from_le_bytes(self.read_ordered()?))
     }
 
     /// Read a signed 32-bit integer
     pub fn read_i32(&mut self) -> Result<i32, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Signed || tlen != TypeLength::Bit32 {
-            return Err(PayloadError::InvalidType);
-        }
-        let bytes = self.read_bytes(4)?;
-        Ok(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        self.read_scalar_type_info(PayloadType::Signed, TypeLength::Bit32)?;
+        Ok(i32::from_le_bytes(self.read_ordered()?))
     }
 
     /// Read a signed 64-bit integer
     pub fn read_i64(&mut self) -> Result<i64, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Signed || tlen != TypeLength::Bit64 {
-            return Err(PayloadError::InvalidType);
-        }
-        let bytes = self.read_bytes(8)?;
-        Ok(i64::from_le_bytes([
-            bytes[0], bytes[1], bytes[2], bytes[3],
-            bytes[4], bytes[5], bytes[6], bytes[7],
-        ]))
+        self.read_scalar_type_info(PayloadType::Signed, TypeLength::Bit64)?;
+        Ok(i64::from_le_bytes(self.read_ordered()?))
     }
 
     /// Read an unsigned 8-bit integer
     pub fn read_u8(&mut self) -> Result<u8, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Unsigned || tlen != TypeLength::Bit8 {
-            return Err(PayloadError::InvalidType);
-        }
+        self.read_scalar_type_info(PayloadType::Unsigned, TypeLength::Bit8)?;
         let bytes = self.read_bytes(1)?;
         Ok(bytes[0])
     }
 
     /// Read an unsigned 16-bit integer
     pub fn read_u16(&mut self) -> Result<u16, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Unsigned || tlen != TypeLength::Bit16 {
-            return Err(PayloadError::InvalidType);
-        }
-        let bytes = self.read_bytes(2)?;
-        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+        self.read_scalar_type_info(PayloadType::Unsigned, TypeLength::Bit16)?;
+        Ok(u16::from_le_bytes(self.read_ordered()?))
     }
 
     /// Read an unsigned 32-bit integer
     pub fn read_u32(&mut self) -> Result<u32, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Unsigned || tlen != TypeLength::Bit32 {
-            return Err(PayloadError::InvalidType);
-        }
-        let bytes = self.read_bytes(4)?;
-        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        self.read_scalar_type_info(PayloadType::Unsigned, TypeLength::Bit32)?;
+        Ok(u32::from_le_bytes(self.read_ordered()?))
     }
 
     /// Read an unsigned 64-bit integer
     pub fn read_u64(&mut self) -> Result<u64, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Unsigned || tlen != TypeLength::Bit64 {
-            return Err(PayloadError::InvalidType);
-        }
-        let bytes = self.read_bytes(8)?;
-        Ok(u64::from_le_bytes([
-            bytes[0], bytes[1], bytes[2], bytes[3],
-            bytes[4], bytes[5], bytes[6], bytes[7],
-        ]))
+        self.read_scalar_type_info(PayloadType::Unsigned, TypeLength::Bit64)?;
+        Ok(u64::from_le_bytes(self.read_ordered()?))
     }
 
     /// Read an unsigned 128-bit integer
     pub fn read_u128(&mut self) -> Result<u128, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Unsigned || tlen != TypeLength::Bit128 {
-            return Err(PayloadError::InvalidType);
-        }
-        let bytes = self.read_bytes(16)?;
-        Ok(u128::from_le_bytes([
-            bytes[0], bytes[1], bytes[2], bytes[3],
-            bytes[4], bytes[5], bytes[6], bytes[7],
-            bytes[8], bytes[9], bytes[10], bytes[11],
-            bytes[12], bytes[13], bytes[14], bytes[15],
-        ]))
+        self.read_scalar_type_info(PayloadType::Unsigned, TypeLength::Bit128)?;
+        Ok(u128::from_le_bytes(self.read_ordered()?))
     }
 
     /// Read a 32-bit float
     pub fn read_f32(&mut self) -> Result<f32, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Float || tlen != TypeLength::Bit32 {
-            return Err(PayloadError::InvalidType);
-        }
-        let bytes = self.read_bytes(4)?;
-        Ok(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        self.read_scalar_type_info(PayloadType::Float, TypeLength::Bit32)?;
+        Ok(f32::from_le_bytes(self.read_ordered()?))
     }
 
     /// Read a 64-bit float
     pub fn read_f64(&mut self) -> Result<f64, PayloadError> {
-        let (ptype, tlen) = self.read_type_info()?;
-        if ptype != PayloadType::Float || tlen != TypeLength::Bit64 {
+        self.read_scalar_type_info(PayloadType::Float, TypeLength::Bit64)?;
+        Ok(f64::from_le_bytes(self.read_ordered()?))
+    }
+
+    /// Read a fixed-point (FIXP) integer argument
+    ///
+    /// Layout after the type info: optional variable info, quantization (f32),
+    /// offset (i32, or i64 for 64-bit values), then the raw integer.
+    pub fn read_fixed_point(&mut self) -> Result<DltFixedPoint, PayloadError> {
+        let type_info = self.peek_raw_type_info()?;
+        if type_info & PayloadType::FixedPoint.to_bit() == 0 {
             return Err(PayloadError::InvalidType);
         }
-        let bytes = self.read_bytes(8)?;
-        Ok(f64::from_le_bytes([
-            bytes[0], bytes[1], bytes[2], bytes[3],
-            bytes[4], bytes[5], bytes[6], bytes[7],
-        ]))
+        match self.read_argument()?.value {
+            DltValue::FixedPoint(value) => Ok(value),
+            _ => Err(PayloadError::InvalidType),
+        }
     }
 
     /// Read a string
+    ///
+    /// Fails with `PayloadError::InvalidData` if the string is not valid UTF-8;
+    /// use [`PayloadParser::read_coded_string`] for legacy (e.g. Latin-1) strings.
     pub fn read_string(&mut self) -> Result<&'a str, PayloadError> {
-        let (ptype, _) = self.read_type_info()?;
-        if ptype != PayloadType::String {
+        let (_, string_data, _) = self.read_string_parts(PayloadType::String)?;
+
+        // Convert to str (excluding null terminator)
+        core::str::from_utf8(string_data).map_err(|_| PayloadError::InvalidData)
+    }
+
+    /// Read a string with its coding (SCOD), without requiring valid UTF-8
+    pub fn read_coded_string(&mut self) -> Result<DltString<'a>, PayloadError> {
+        let (_, string_data, type_info) = self.read_string_parts(PayloadType::String)?;
+        Ok(DltString::new(
+            string_data,
+            DltStringCoding::from_type_info(type_info),
+        ))
+    }
+
+    /// Read a trace info argument (TRAI): the originating module or function name
+    ///
+    /// Layout after the type info: length (u16, includes null terminator), then the
+    /// null-terminated name.
+    pub fn read_trace_info(&mut self) -> Result<&'a str, PayloadError> {
+        let (_, name, _) = self.read_string_parts(PayloadType::TraceInfo)?;
+        core::str::from_utf8(name).map_err(|_| PayloadError::InvalidData)
+    }
+
+    /// Read raw bytes (RAWD): all `length` bytes, there is no null terminator
+    pub fn read_raw(&mut self) -> Result<&'a [u8], PayloadError> {
+        let (_, raw_data, _) = self.read_string_parts(PayloadType::Raw)?;
+        Ok(raw_data)
+    }
+
+    /// Read an array (ARAY) of bools, integers or floats
+    ///
+    /// Layout after the type info: number of dimensions (u16), the length of each
+    /// dimension (u16 each), optional variable info (name, unit), then all elements
+    /// in row-major order.
+    pub fn read_array(&mut self) -> Result<DltArray<'a>, PayloadError> {
+        let type_info = self.read_raw_type_info()?;
+        let (ptype, type_length) = Self::decode_type_info(type_info)?;
+        if ptype != PayloadType::Array {
             return Err(PayloadError::InvalidType);
         }
 
-        // Read length (includes null terminator)
-        let len_bytes = self.read_bytes(2)?;
-        let len = u16::from_le_bytes([len_bytes[0], len_bytes[1]]) as usize;
-
-        if len == 0 {
-            return Err(PayloadError::InvalidData);
+        let element_type = PayloadType::parse_basic(type_info).ok_or(PayloadError::InvalidType)?;
+        if !matches!(
+            element_type,
+            PayloadType::Bool | PayloadType::Signed | PayloadType::Unsigned | PayloadType::Float
+        ) {
+            return Err(PayloadError::InvalidType);
+        }
+        let element_size = type_length.to_bytes();
+        if decode_scalar(element_type, &[0u8; 16][..element_size], self.endian).is_none() {
+            return Err(PayloadError::UnsupportedLength);
         }
 
-        // Read string data (including null terminator)
-        let string_data = self.read_bytes(len)?;
+        let dimension_count = self.read_length()?;
+        let dimensions = self.read_bytes(dimension_count * 2)?;
+        let (name, unit) = self.read_variable_info(type_info, true)?;
+        let fixed_point = self.read_fixed_point_info(type_info, element_type, type_length)?;
 
-        // Verify null terminator
-        if string_data[len - 1] != 0 {
-            return Err(PayloadError::InvalidData);
+        let mut element_count = if dimension_count == 0 { 0usize } else { 1 };
+        for bytes in dimensions.chunks_exact(2) {
+            let dimension = read_u16_field([bytes[0], bytes[1]], self.endian) as usize;
+            element_count = element_count
+                .checked_mul(dimension)
+                .ok_or(PayloadError::InvalidData)?;
         }
+        let data_len = element_count
+            .checked_mul(element_size)
+            .ok_or(PayloadError::InvalidData)?;
+        let data = self.read_bytes(data_len)?;
 
-        // Convert to str (excluding null terminator)
-        core::str::from_utf8(&string_data[..len - 1]).map_err(|_| PayloadError::InvalidData)
+        Ok(DltArray {
+            name,
+            unit,
+            fixed_point,
+            element_type,
+            type_length,
+            dimensions,
+            data,
+            endian: self.endian,
+        })
     }
 
-    /// Read raw bytes
-    pub fn read_raw(&mut self) -> Result<&'a [u8], PayloadError> {
-        let (ptype, _) = self.read_type_info()?;
-        if ptype != PayloadType::Raw {
+    /// Read a struct (STRU) argument
+    ///
+    /// Layout after the type info: number of entries (u16), optional variable info
+    /// (name), then each entry as a complete typed argument. Entries are validated
+    /// (skipped) but not decoded.
+    pub fn read_struct(&mut self) -> Result<DltStruct<'a>, PayloadError> {
+        let type_info = self.read_raw_type_info()?;
+        let (ptype, _) = Self::decode_type_info(type_info)?;
+        if ptype != PayloadType::Struct {
             return Err(PayloadError::InvalidType);
         }
-
-        // Read length (includes null terminator)
-        let len_bytes = self.read_bytes(2)?;
-        let len = u16::from_le_bytes([len_bytes[0], len_bytes[1]]) as usize;
-
-        if len == 0 {
+        if self.depth >= DLT_MAX_STRUCT_DEPTH {
             return Err(PayloadError::InvalidData);
         }
 
-        // Read raw data (including null terminator)
-        let raw_data = self.read_bytes(len)?;
+        let entry_count = self.read_length()? as u16;
+        let (name, _) = self.read_variable_info(type_info, false)?;
+        let start = self.position;
 
-        // Verify null terminator
-        if raw_data[len - 1] != 0 {
-            return Err(PayloadError::InvalidData);
-        }
+        self.depth += 1;
+        let skipped = (0..entry_count).try_for_each(|_| self.skip_argument());
+        self.depth -= 1;
+        skipped?;
 
-        // Return data (excluding null terminator)
-        Ok(&raw_data[..len - 1])
+        Ok(DltStruct {
+            name,
+            entry_count,
+            data: &self.data[start..self.position],
+            endian: self.endian,
+        })
     }
 
     /// Peek at the next type info without consuming it
     pub fn peek_type_info(&self) -> Result<(PayloadType, TypeLength), PayloadError> {
+        Self::decode_type_info(self.peek_raw_type_info()?)
+    }
+
+    /// Peek at the next type info field without decoding its flags
+    fn peek_raw_type_info(&self) -> Result<u32, PayloadError> {
         if self.position + 4 > self.data.len() {
             return Err(PayloadError::BufferTooSmall);
         }
 
-        let bytes = &self.data[self.position..self.position + 4];
-        let type_info = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
-
-        let type_length = match type_info & 0x0F {
-            0x00 => TypeLength::NotDefined,
-            0x01 => TypeLength::Bit8,
-            0x02 => TypeLength::Bit16,
-            0x03 => TypeLength::Bit32,
-            0x04 => TypeLength::Bit64,
-            0x05 => TypeLength::Bit128,
-            _ => return Err(PayloadError::InvalidType),
-        };
-
-        let payload_type = PayloadType::parse(type_info).ok_or(PayloadError::InvalidType)?;
-
-        Ok((payload_type, type_length))
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(&self.data[self.position..self.position + 4]);
+        Ok(match self.endian {
+            DltEndian::Big => u32::from_be_bytes(bytes),
+            DltEndian::Little => u32::from_le_bytes(bytes),
+        })
     }
 
     /// Parse the next argument automatically based on its type info
     /// This is the primary method for parsing unknown payload types from incoming packets
+    ///
+    /// Variable info (name/unit) is dropped; use [`PayloadParser::read_argument`] to keep it.
     pub fn read_next(&mut self) -> Result<DltValue<'a>, PayloadError> {
-        let (ptype, tlen) = self.peek_type_info()?;
-        
+        Ok(self.read_argument()?.value)
+    }
+
+    /// Parse the next argument including its variable info (VARI name and unit)
+    pub fn read_argument(&mut self) -> Result<DltArgument<'a>, PayloadError> {
+        let type_info = self.peek_raw_type_info()?;
+        let (ptype, tlen) = Self::decode_type_info(type_info)?;
+
         match ptype {
-            PayloadType::Bool => {
-                let val = self.read_bool()?;
-                Ok(DltValue::Bool(val))
-            }
-            PayloadType::Signed => {
-                match tlen {
-                    TypeLength::Bit8 => Ok(DltValue::I8(self.read_i8()?)),
-                    TypeLength::Bit16 => Ok(DltValue::I16(self.read_i16()?)),
-                    TypeLength::Bit32 => Ok(DltValue::I32(self.read_i32()?)),
-                    TypeLength::Bit64 => Ok(DltValue::I64(self.read_i64()?)),
-                    _ => Err(PayloadError::UnsupportedLength),
-                }
-            }
-            PayloadType::Unsigned => {
-                match tlen {
-                    TypeLength::Bit8 => Ok(DltValue::U8(self.read_u8()?)),
-                    TypeLength::Bit16 => Ok(DltValue::U16(self.read_u16()?)),
-                    TypeLength::Bit32 => Ok(DltValue::U32(self.read_u32()?)),
-                    TypeLength::Bit64 => Ok(DltValue::U64(self.read_u64()?)),
-                    TypeLength::Bit128 => Ok(DltValue::U128(self.read_u128()?)),
-                    _ => Err(PayloadError::UnsupportedLength),
+            PayloadType::Bool | PayloadType::Signed | PayloadType::Unsigned | PayloadType::Float => {
+                self.read_raw_type_info()?;
+                let (name, unit) = self.read_variable_info(type_info, ptype != PayloadType::Bool)?;
+                let fixed_point = self.read_fixed_point_info(type_info, ptype, tlen)?;
+                let error = if ptype == PayloadType::Bool {
+                    PayloadError::InvalidType
+                } else {
+                    PayloadError::UnsupportedLength
+                };
+                let size = tlen.to_bytes();
+                if decode_scalar(ptype, &[0u8; 16][..size], self.endian).is_none() {
+                    return Err(error);
                 }
-            }
-            PayloadType::Float => {
-                match tlen {
-                    TypeLength::Bit32 => Ok(DltValue::F32(self.read_f32()?)),
-                    TypeLength::Bit64 => Ok(DltValue::F64(self.read_f64()?)),
-                    _ => Err(PayloadError::UnsupportedLength),
+                let bytes = self.read_bytes(size)?;
+                let mut value = decode_scalar(ptype, bytes, self.endian).ok_or(error)?;
+                if let Some((quantization, offset)) = fixed_point {
+                    value = DltValue::FixedPoint(DltFixedPoint {
+                        raw: scalar_to_i128(value).ok_or(PayloadError::InvalidType)?,
+                        signed: ptype == PayloadType::Signed,
+                        type_length: tlen,
+                        quantization,
+                        offset,
+                    });
                 }
+                Ok(DltArgument { name, unit, value })
             }
             PayloadType::String => {
-                let val = self.read_string()?;
-                Ok(DltValue::String(val))
+                let (name, data, type_info) = self.read_string_parts(PayloadType::String)?;
+                let string = DltString::new(data, DltStringCoding::from_type_info(type_info));
+                Ok(DltArgument {
+                    name,
+                    unit: None,
+                    value: DltValue::String(string),
+                })
             }
             PayloadType::Raw => {
-                let val = self.read_raw()?;
-                Ok(DltValue::Raw(val))
+                let (name, data, _) = self.read_string_parts(PayloadType::Raw)?;
+                Ok(DltArgument {
+                    name,
+                    unit: None,
+                    value: DltValue::Raw(data),
+                })
+            }
+            PayloadType::TraceInfo => Ok(DltArgument {
+                name: None,
+                unit: None,
+                value: DltValue::TraceInfo(self.read_trace_info()?),
+            }),
+            PayloadType::Array => {
+                let array = self.read_array()?;
+                Ok(DltArgument {
+                    name: array.name(),
+                    unit: array.unit(),
+                    value: DltValue::Array(array),
+                })
+            }
+            PayloadType::Struct => {
+                let record = self.read_struct()?;
+                Ok(DltArgument {
+                    name: record.name(),
+                    unit: None,
+                    value: DltValue::Struct(record),
+                })
             }
             _ => Err(PayloadError::InvalidType),
         }
@@ -553,17 +1277,32 @@ impl<'a> PayloadParser<'a> {
 
     /// Skip the next argument without parsing it
     pub fn skip_argument(&mut self) -> Result<(), PayloadError> {
+        let type_info = self.peek_raw_type_info()?;
+        match Self::decode_type_info(type_info)?.0 {
+            PayloadType::Array => {
+                self.read_array()?;
+                return Ok(());
+            }
+            PayloadType::Struct => {
+                self.read_struct()?;
+                return Ok(());
+            }
+            _ => {}
+        }
+
         let (ptype, tlen) = self.read_type_info()?;
 
         match ptype {
             PayloadType::Bool | PayloadType::Signed | PayloadType::Unsigned | PayloadType::Float => {
+                self.read_variable_info(type_info, ptype != PayloadType::Bool)?;
+                self.read_fixed_point_info(type_info, ptype, tlen)?;
                 let size = tlen.to_bytes();
                 self.read_bytes(size)?;
             }
-            PayloadType::String | PayloadType::Raw => {
+            PayloadType::String | PayloadType::Raw | PayloadType::TraceInfo => {
                 // Read length field
-                let len_bytes = self.read_bytes(2)?;
-                let len = u16::from_le_bytes([len_bytes[0], len_bytes[1]]) as usize;
+                let len = self.read_length()?;
+                self.read_variable_info(type_info, false)?;
                 // Skip data
                 self.read_bytes(len)?;
             }
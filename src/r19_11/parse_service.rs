@@ -41,6 +41,9 @@
 
 use crate::r19_11::*;
 
+/// Parsed SetLogChannelAssignment request: (app_id, ctx_id, log_channel, operation)
+pub type LogChannelAssignmentRequest = ([u8; 4], [u8; 4], [u8; 4], LogChannelAssignment);
+
 // ========================================
 // GetLogInfo Data Structures
 // ========================================
@@ -258,6 +261,61 @@ impl<'a> DltServiceParser<'a> {
         Ok((app_id, ctx_id))
     }
 
+    /// Parse SetLogChannelAssignment request (0x20)
+    ///
+    /// Returns: (app_id, ctx_id, log_channel, operation)
+    pub fn parse_set_log_channel_assignment_request(
+        &self,
+    ) -> Result<LogChannelAssignmentRequest, DltError> {
+        // Expected: 4 (service ID) + 4 (app) + 4 (ctx) + 4 (channel) + 1 (operation) = 17 bytes
+        if self.data.len() < 17 {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let mut app_id = [0u8; 4];
+        app_id.copy_from_slice(&self.data[4..8]);
+
+        let mut ctx_id = [0u8; 4];
+        ctx_id.copy_from_slice(&self.data[8..12]);
+
+        let mut log_channel = [0u8; 4];
+        log_channel.copy_from_slice(&self.data[12..16]);
+
+        let operation = LogChannelAssignment::from_u8(self.data[16]).ok_or(DltError::InvalidParameter)?;
+
+        Ok((app_id, ctx_id, log_channel, operation))
+    }
+
+    /// Parse SetLogChannelThreshold request (0x21)
+    ///
+    /// Returns: (log_channel, log_level, trace_status)
+    pub fn parse_set_log_channel_threshold_request(&self) -> Result<([u8; 4], i8, i8), DltError> {
+        // Expected: 4 (service ID) + 4 (channel) + 1 (level) + 1 (trace status) = 10 bytes
+        if self.data.len() < 10 {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let mut log_channel = [0u8; 4];
+        log_channel.copy_from_slice(&self.data[4..8]);
+
+        Ok((log_channel, self.data[8] as i8, self.data[9] as i8))
+    }
+
+    /// Parse GetLogChannelThreshold request (0x22)
+    ///
+    /// Returns: log_channel
+    pub fn parse_get_log_channel_threshold_request(&self) -> Result<[u8; 4], DltError> {
+        // Expected: 4 (service ID) + 4 (channel) = 8 bytes
+        if self.data.len() < 8 {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let mut log_channel = [0u8; 4];
+        log_channel.copy_from_slice(&self.data[4..8]);
+
+        Ok(log_channel)
+    }
+
     // ========================================
     // Service-Specific Response Parsers
     // ========================================
@@ -400,6 +458,34 @@ impl<'a> DltServiceParser<'a> {
         Ok((status, trace_status))
     }
 
+    /// Parse GetLogChannelThreshold response (0x22)
+    ///
+    /// Returns: (status, log_level, trace_status)
+    pub fn parse_get_log_channel_threshold_response(&self) -> Result<(ServiceStatus, i8, i8), DltError> {
+        // Expected: 4 (service ID) + 1 (status) + 1 (level) + 1 (trace status) = 7 bytes
+        if self.data.len() < 7 {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let status = ServiceStatus::from_u8(self.data[4]).ok_or(DltError::InvalidParameter)?;
+
+        Ok((status, self.data[5] as i8, self.data[6] as i8))
+    }
+
+    /// Parse SyncTimeStamp response (0x24)
+    ///
+    /// Returns: (status, time_stamp)
+    pub fn parse_sync_time_stamp_response(&self) -> Result<(ServiceStatus, DltSyncTimeStamp), DltError> {
+        // Expected: 4 (service ID) + 1 (status) + 11 (time stamp) = 16 bytes
+        if self.data.len() < 5 + DltSyncTimeStamp::SIZE {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let status = ServiceStatus::from_u8(self.data[4]).ok_or(DltError::InvalidParameter)?;
+
+        Ok((status, DltSyncTimeStamp::read(&self.data[5..])))
+    }
+
     /// Parse BufferOverflowNotification response (0x23)
     ///
     /// Returns: (status, overflow_counter)
@@ -13,6 +13,7 @@
 
 // Re-export parser types from parse_log
 pub use crate::r19_11::parse_log::{DltValue, PayloadError, PayloadParser, PayloadType, TypeLength};
+use crate::r19_11::{DltEndian, DltString, DltStringCoding, DLT_MAX_STRUCT_DEPTH};
 
 // ========================================
 // Payload Builder (remains here)
@@ -20,20 +21,44 @@ pub use crate::r19_11::parse_log::{DltValue, PayloadError, PayloadParser, Payloa
 
 /// DLT Payload Builder for no_std environments
 /// Uses a fixed-size buffer to avoid heap allocations
+///
+/// Type info, length fields and values are written in the configured byte order,
+/// which must match the MSBF flag of the message header.
 pub struct PayloadBuilder<'a> {
     buffer: &'a mut [u8],
     position: usize,
+    endian: DltEndian,
+    /// Positions of the entry count fields of open structs
+    struct_offsets: [usize; DLT_MAX_STRUCT_DEPTH],
+    /// Number of entries written into each open struct
+    struct_entries: [u16; DLT_MAX_STRUCT_DEPTH],
+    /// Number of currently open structs
+    struct_depth: usize,
 }
 
 impl<'a> PayloadBuilder<'a> {
-    /// Create a new payload builder with the given buffer
+    /// Create a new payload builder with the given buffer (little-endian)
     pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self::with_endian(buffer, DltEndian::Little)
+    }
+
+    /// Create a new payload builder writing in the given byte order
+    pub fn with_endian(buffer: &'a mut [u8], endian: DltEndian) -> Self {
         Self {
             buffer,
             position: 0,
+            endian,
+            struct_offsets: [0; DLT_MAX_STRUCT_DEPTH],
+            struct_entries: [0; DLT_MAX_STRUCT_DEPTH],
+            struct_depth: 0,
         }
     }
 
+    /// Get the byte order used for encoding
+    pub fn endian(&self) -> DltEndian {
+        self.endian
+    }
+
     /// Get the number of bytes written
     pub fn len(&self) -> usize {
         self.position
@@ -52,6 +77,7 @@ impl<'a> PayloadBuilder<'a> {
     /// Reset the builder to reuse the buffer
     pub fn reset(&mut self) {
         self.position = 0;
+        self.struct_depth = 0;
     }
 
     /// Write raw bytes to the buffer
@@ -64,6 +90,14 @@ impl<'a> PayloadBuilder<'a> {
         Ok(())
     }
 
+    /// Write a little-endian encoded field in the configured byte order
+    fn write_ordered<const N: usize>(&mut self, mut bytes: [u8; N]) -> Result<(), PayloadError> {
+        if self.endian == DltEndian::Big {
+            bytes.reverse();
+        }
+        self.write_bytes(&bytes)
+    }
+
     /// Build Type Info field (4 bytes)
     /// Bits 0-3: Type Length (TYLE)
     /// Bit 4: Type Bool (Bool)
@@ -85,8 +119,29 @@ impl<'a> PayloadBuilder<'a> {
         type_length: TypeLength,
     ) -> Result<(), PayloadError> {
         let type_info: u32 = (type_length as u32) | payload_type.to_bit();
-        self.write_bytes(&type_info.to_le_bytes())?;
-        Ok(())
+        self.write_raw_type_info(type_info)
+    }
+
+    /// Write a complete type info value, starting a new argument
+    ///
+    /// Every argument starts here, so this is where entries of open structs are counted.
+    fn write_raw_type_info(&mut self, type_info: u32) -> Result<(), PayloadError> {
+        if self.struct_depth > 0 {
+            let entries = &mut self.struct_entries[self.struct_depth - 1];
+            *entries = entries.checked_add(1).ok_or(PayloadError::InvalidData)?;
+        }
+        self.write_ordered(type_info.to_le_bytes())
+    }
+
+    /// Write an element of `T::TYPE_LENGTH` bytes in the configured byte order
+    fn write_element<T: DltArrayElement>(&mut self, value: T) -> Result<(), PayloadError> {
+        let mut bytes = [0u8; 16];
+        let bytes = &mut bytes[..T::TYPE_LENGTH.to_bytes()];
+        value.write_le(bytes);
+        if self.endian == DltEndian::Big {
+            bytes.reverse();
+        }
+        self.write_bytes(bytes)
     }
 
     /// Add a boolean value (8 bit)
@@ -99,83 +154,116 @@ impl<'a> PayloadBuilder<'a> {
     /// Add a signed 8-bit integer
     pub fn add_i8(&mut self, value: i8) -> Result<(), PayloadError> {
         self.write_type_info(PayloadType::Signed, TypeLength::Bit8)?;
-        self.write_bytes(&value.to_le_bytes())?;
+        self.write_ordered(value.to_le_bytes())?;
         Ok(())
     }
 
     /// Add a signed 16-bit integer
     pub fn add_i16(&mut self, value: i16) -> Result<(), PayloadError> {
         self.write_type_info(PayloadType::Signed, TypeLength::Bit16)?;
-        self.write_bytes(&value.to_le_bytes())?;
+        self.write_ordered(value.to_le_bytes())?;
         Ok(())
     }
 
     /// Add a signed 32-bit integer
     pub fn add_i32(&mut self, value: i32) -> Result<(), PayloadError> {
         self.write_type_info(PayloadType::Signed, TypeLength::Bit32)?;
-        self.write_bytes(&value.to_le_bytes())?;
+        self.write_ordered(value.to_le_bytes())?;
         Ok(())
     }
 
     /// Add a signed 64-bit integer
     pub fn add_i64(&mut self, value: i64) -> Result<(), PayloadError> {
         self.write_type_info(PayloadType::Signed, TypeLength::Bit64)?;
-        self.write_bytes(&value.to_le_bytes())?;
+        self.write_ordered(value.to_le_bytes())?;
         Ok(())
     }
 
     /// Add an unsigned 8-bit integer
     pub fn add_u8(&mut self, value: u8) -> Result<(), PayloadError> {
         self.write_type_info(PayloadType::Unsigned, TypeLength::Bit8)?;
-        self.write_bytes(&value.to_le_bytes())?;
+        self.write_ordered(value.to_le_bytes())?;
         Ok(())
     }
 
     /// Add an unsigned 16-bit integer
     pub fn add_u16(&mut self, value: u16) -> Result<(), PayloadError> {
         self.write_type_info(PayloadType::Unsigned, TypeLength::Bit16)?;
-        self.write_bytes(&value.to_le_bytes())?;
+        self.write_ordered(value.to_le_bytes())?;
         Ok(())
     }
 
     /// Add an unsigned 32-bit integer
     pub fn add_u32(&mut self, value: u32) -> Result<(), PayloadError> {
         self.write_type_info(PayloadType::Unsigned, TypeLength::Bit32)?;
-        self.write_bytes(&value.to_le_bytes())?;
+        self.write_ordered(value.to_le_bytes())?;
         Ok(())
     }
 
     /// Add an unsigned 64-bit integer
     pub fn add_u64(&mut self, value: u64) -> Result<(), PayloadError> {
         self.write_type_info(PayloadType::Unsigned, TypeLength::Bit64)?;
-        self.write_bytes(&value.to_le_bytes())?;
+        self.write_ordered(value.to_le_bytes())?;
         Ok(())
     }
 
     /// Add a 32-bit float
     pub fn add_f32(&mut self, value: f32) -> Result<(), PayloadError> {
         self.write_type_info(PayloadType::Float, TypeLength::Bit32)?;
-        self.write_bytes(&value.to_le_bytes())?;
+        self.write_ordered(value.to_le_bytes())?;
         Ok(())
     }
 
     /// Add a 64-bit float
     pub fn add_f64(&mut self, value: f64) -> Result<(), PayloadError> {
         self.write_type_info(PayloadType::Float, TypeLength::Bit64)?;
-        self.write_bytes(&value.to_le_bytes())?;
+        self.write_ordered(value.to_le_bytes())?;
         Ok(())
     }
 
     /// Add a string
     /// For strings, the Type Length field is usually set to NotDefined (0x00)
     /// and the actual length is encoded in the payload
+    ///
+    /// Rust strings are UTF-8, so the string is sent with UTF-8 coding (SCOD = 1);
+    /// use [`PayloadBuilder::add_string_with_coding`] to mark it as ASCII. Earlier
+    /// versions sent ASCII coding (SCOD = 0), see `CHANGELOG.md`.
     pub fn add_string(&mut self, value: &str) -> Result<(), PayloadError> {
+        self.add_string_with_coding(value, DltStringCoding::Utf8)
+    }
+
+    /// Add a string with the given string coding (SCOD)
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dlt_protocol::r19_11::*;
+    ///
+    /// let mut buffer = [0u8; 64];
+    /// let mut builder = PayloadBuilder::new(&mut buffer);
+    /// builder.add_string_with_coding("Grüße", DltStringCoding::Utf8).unwrap();
+    /// ```
+    pub fn add_string_with_coding(
+        &mut self,
+        value: &str,
+        coding: DltStringCoding,
+    ) -> Result<(), PayloadError> {
+        self.add_coded_string(DltString::new(value.as_bytes(), coding))
+    }
+
+    /// Add a string from raw bytes and coding, e.g. Latin-1 text with ASCII coding
+    ///
+    /// The bytes are written as-is and must not contain the null terminator.
+    pub fn add_coded_string(&mut self, value: DltString) -> Result<(), PayloadError> {
+        let len = value.len() + 1; // +1 for null terminator
+        if len > u16::MAX as usize {
+            return Err(PayloadError::InvalidData);
+        }
+
         // Type info with TYLE = 0 (not defined) for variable length strings
-        self.write_type_info(PayloadType::String, TypeLength::NotDefined)?;
+        self.write_raw_type_info(PayloadType::String.to_bit() | value.coding().to_bits())?;
 
         // Write string length (2 bytes)
-        let len = (value.len() as u16) + 1; // +1 for null terminator
-        self.write_bytes(&len.to_le_bytes())?;
+        self.write_ordered((len as u16).to_le_bytes())?;
 
         // Write string data (null-terminated)
         self.write_bytes(value.as_bytes())?;
@@ -183,26 +271,488 @@ impl<'a> PayloadBuilder<'a> {
         Ok(())
     }
 
-    /// Add raw bytes
+    /// Add a trace info argument (TRAI) naming the originating module or function
+    ///
+    /// Used by application trace messages to record where they were emitted,
+    /// e.g. `"can_tp::send_frame"`.
+    pub fn add_trace_info(&mut self, name: &str) -> Result<(), PayloadError> {
+        let len = name.len() + 1; // +1 for null terminator
+        if len > u16::MAX as usize {
+            return Err(PayloadError::InvalidData);
+        }
+
+        self.write_type_info(PayloadType::TraceInfo, TypeLength::NotDefined)?;
+        self.write_ordered((len as u16).to_le_bytes())?;
+        self.write_bytes(name.as_bytes())?;
+        self.write_bytes(&[0])?; // null terminator
+        Ok(())
+    }
+
+    /// Add raw bytes (RAWD)
+    ///
+    /// Unlike strings, raw data has no null terminator: the length field is
+    /// exactly `data.len()`.
     pub fn add_raw(&mut self, data: &[u8]) -> Result<(), PayloadError> {
+        let len = u16::try_from(data.len()).map_err(|_| PayloadError::InvalidData)?;
+
         // Type info with TYLE = 0 (not defined) for variable length raw data
         self.write_type_info(PayloadType::Raw, TypeLength::NotDefined)?;
 
         // Write data length (2 bytes)
-        let len = (data.len() as u16) + 1; // +1 for null terminator
-        self.write_bytes(&len.to_le_bytes())?;
+        self.write_ordered(len.to_le_bytes())?;
 
         // Write raw data
-        self.write_bytes(data)?;
-        self.write_bytes(&[0])?; // null terminator
-        Ok(())
+        self.write_bytes(data)
     }
 
     /// Add a 128-bit value (generic)
     pub fn add_u128(&mut self, value: u128) -> Result<(), PayloadError> {
         self.write_type_info(PayloadType::Unsigned, TypeLength::Bit128)?;
-        self.write_bytes(&value.to_le_bytes())?;
+        self.write_ordered(value.to_le_bytes())?;
         Ok(())
     }
+
+    /// Add an array (ARAY) of bools, integers or floats
+    ///
+    /// `dimensions` holds the number of entries per dimension (e.g. `&[3]` for a
+    /// vector, `&[2, 3]` for a 2x3 matrix). `values` holds all elements in
+    /// row-major order and must contain exactly the product of the dimensions.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dlt_protocol::r19_11::*;
+    ///
+    /// let mut buffer = [0u8; 128];
+    /// let mut builder = PayloadBuilder::new(&mut buffer);
+    /// builder.add_array(&[2, 3], &[1i16, 2, 3, 4, 5, 6]).unwrap();
+    /// ```
+    pub fn add_array<T: DltArrayElement>(
+        &mut self,
+        dimensions: &[u16],
+        values: &[T],
+    ) -> Result<(), PayloadError> {
+        self.write_array(None, dimensions, values)
+    }
+
+    /// Add an array (ARAY) with variable info (name and unit)
+    ///
+    /// An empty `unit` is encoded as "no unit".
+    pub fn add_named_array<T: DltArrayElement>(
+        &mut self,
+        name: &str,
+        unit: &str,
+        dimensions: &[u16],
+        values: &[T],
+    ) -> Result<(), PayloadError> {
+        self.write_array(Some((name, unit)), dimensions, values)
+    }
+
+    /// Write an array argument with optional variable info
+    fn write_array<T: DltArrayElement>(
+        &mut self,
+        variable_info: Option<(&str, &str)>,
+        dimensions: &[u16],
+        values: &[T],
+    ) -> Result<(), PayloadError> {
+        let element_count = if dimensions.is_empty() {
+            0
+        } else {
+            dimensions.iter().map(|&d| d as usize).product()
+        };
+        if element_count != values.len() || dimensions.len() > u16::MAX as usize {
+            return Err(PayloadError::InvalidData);
+        }
+
+        let mut type_info: u32 =
+            (T::TYPE_LENGTH as u32) | T::PAYLOAD_TYPE.to_bit() | PayloadType::Array.to_bit();
+        if variable_info.is_some() {
+            type_info |= PayloadType::VariableInfo.to_bit();
+        }
+        self.write_raw_type_info(type_info)?;
+
+        self.write_ordered((dimensions.len() as u16).to_le_bytes())?;
+        for dimension in dimensions {
+            self.write_ordered(dimension.to_le_bytes())?;
+        }
+        if let Some((name, unit)) = variable_info {
+            self.write_variable_info(name, Some(unit))?;
+        }
+        for value in values {
+            self.write_element(*value)?;
+        }
+        Ok(())
+    }
+
+    /// Add a value with variable info (VARI), e.g. "speed" in "km/h"
+    ///
+    /// Numeric values carry both name and unit; for bool, string and raw values
+    /// only the name is encoded and `unit` is ignored. An empty `unit` is encoded
+    /// as "no unit". Arrays and structs have dedicated methods
+    /// ([`PayloadBuilder::add_named_array`], [`PayloadBuilder::open_named_struct`]);
+    /// trace info has no variable info and is rejected with `InvalidType`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dlt_protocol::r19_11::*;
+    ///
+    /// let mut buffer = [0u8; 128];
+    /// let mut builder = PayloadBuilder::new(&mut buffer);
+    /// builder.add_named("speed", "km/h", DltValue::U16(87)).unwrap();
+    /// builder.add_named("gear", "", DltValue::String(DltString::utf8("D"))).unwrap();
+    /// ```
+    pub fn add_named(&mut self, name: &str, unit: &str, value: DltValue) -> Result<(), PayloadError> {
+        match value {
+            DltValue::Bool(v) => self.write_named_scalar(name, unit, v),
+            DltValue::I8(v) => self.write_named_scalar(name, unit, v),
+            DltValue::I16(v) => self.write_named_scalar(name, unit, v),
+            DltValue::I32(v) => self.write_named_scalar(name, unit, v),
+            DltValue::I64(v) => self.write_named_scalar(name, unit, v),
+            DltValue::U8(v) => self.write_named_scalar(name, unit, v),
+            DltValue::U16(v) => self.write_named_scalar(name, unit, v),
+            DltValue::U32(v) => self.write_named_scalar(name, unit, v),
+            DltValue::U64(v) => self.write_named_scalar(name, unit, v),
+            DltValue::U128(v) => self.write_named_scalar(name, unit, v),
+            DltValue::F32(v) => self.write_named_scalar(name, unit, v),
+            DltValue::F64(v) => self.write_named_scalar(name, unit, v),
+            DltValue::String(v) => self.write_named_data(
+                name,
+                PayloadType::String.to_bit() | v.coding().to_bits(),
+                v.as_bytes(),
+                true,
+            ),
+            DltValue::Raw(v) => self.write_named_data(name, PayloadType::Raw.to_bit(), v, false),
+            DltValue::FixedPoint(v) => {
+                let size = v.type_length.to_bytes();
+                let ptype = if v.signed { PayloadType::Signed } else { PayloadType::Unsigned };
+                self.write_fixed_point(
+                    Some((name, unit)),
+                    ptype,
+                    v.type_length,
+                    &v.raw.to_le_bytes()[..size],
+                    v.quantization,
+                    v.offset,
+                )
+            }
+            DltValue::Array(_) | DltValue::Struct(_) | DltValue::TraceInfo(_) => {
+                Err(PayloadError::InvalidType)
+            }
+        }
+    }
+
+    /// Add a fixed-point integer (FIXP): `physical = value * quantization + offset`
+    ///
+    /// Only signed and unsigned integers up to 64 bits are allowed (see
+    /// [`DltFixedPointElement`]). For 8/16/32-bit values the offset is
+    /// transmitted as 32 bits and must fit into an `i32`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dlt_protocol::r19_11::*;
+    ///
+    /// let mut buffer = [0u8; 64];
+    /// let mut builder = PayloadBuilder::new(&mut buffer);
+    /// // 1234 * 0.1 - 40 = 83.4
+    /// builder.add_fixed_point(1234u16, 0.1, -40).unwrap();
+    /// ```
+    pub fn add_fixed_point<T: DltFixedPointElement>(
+        &mut self,
+        value: T,
+        quantization: f32,
+        offset: i64,
+    ) -> Result<(), PayloadError> {
+        let mut raw = [0u8; 16];
+        let raw = &mut raw[..T::TYPE_LENGTH.to_bytes()];
+        value.write_le(raw);
+        self.write_fixed_point(None, T::PAYLOAD_TYPE, T::TYPE_LENGTH, raw, quantization, offset)
+    }
+
+    /// Add a fixed-point integer (FIXP) with variable info (name and unit)
+    pub fn add_named_fixed_point<T: DltFixedPointElement>(
+        &mut self,
+        name: &str,
+        unit: &str,
+        value: T,
+        quantization: f32,
+        offset: i64,
+    ) -> Result<(), PayloadError> {
+        let mut raw = [0u8; 16];
+        let raw = &mut raw[..T::TYPE_LENGTH.to_bytes()];
+        value.write_le(raw);
+        self.write_fixed_point(
+            Some((name, unit)),
+            T::PAYLOAD_TYPE,
+            T::TYPE_LENGTH,
+            raw,
+            quantization,
+            offset,
+        )
+    }
+
+    /// Write a fixed-point argument from its little-endian raw bytes
+    fn write_fixed_point(
+        &mut self,
+        variable_info: Option<(&str, &str)>,
+        payload_type: PayloadType,
+        type_length: TypeLength,
+        raw_le: &[u8],
+        quantization: f32,
+        offset: i64,
+    ) -> Result<(), PayloadError> {
+        if !matches!(payload_type, PayloadType::Signed | PayloadType::Unsigned) {
+            return Err(PayloadError::InvalidType);
+        }
+        let offset_32 = match type_length {
+            TypeLength::Bit8 | TypeLength::Bit16 | TypeLength::Bit32 => {
+                Some(i32::try_from(offset).map_err(|_| PayloadError::InvalidData)?)
+            }
+            TypeLength::Bit64 => None,
+            _ => return Err(PayloadError::UnsupportedLength),
+        };
+
+        let mut type_info: u32 =
+            (type_length as u32) | payload_type.to_bit() | PayloadType::FixedPoint.to_bit();
+        if variable_info.is_some() {
+            type_info |= PayloadType::VariableInfo.to_bit();
+        }
+        self.write_raw_type_info(type_info)?;
+
+        if let Some((name, unit)) = variable_info {
+            self.write_variable_info(name, Some(unit))?;
+        }
+        self.write_ordered(quantization.to_le_bytes())?;
+        match offset_32 {
+            Some(offset) => self.write_ordered(offset.to_le_bytes())?,
+            None => self.write_ordered(offset.to_le_bytes())?,
+        }
+
+        let mut raw = [0u8; 16];
+        let raw = &mut raw[..raw_le.len()];
+        raw.copy_from_slice(raw_le);
+        if self.endian == DltEndian::Big {
+            raw.reverse();
+        }
+        self.write_bytes(raw)
+    }
+
+    /// Write a bool/integer/float argument with variable info
+    fn write_named_scalar<T: DltArrayElement>(
+        &mut self,
+        name: &str,
+        unit: &str,
+        value: T,
+    ) -> Result<(), PayloadError> {
+        let type_info: u32 = (T::TYPE_LENGTH as u32)
+            | T::PAYLOAD_TYPE.to_bit()
+            | PayloadType::VariableInfo.to_bit();
+        self.write_raw_type_info(type_info)?;
+
+        let unit = if T::PAYLOAD_TYPE == PayloadType::Bool {
+            None
+        } else {
+            Some(unit)
+        };
+        self.write_variable_info(name, unit)?;
+        self.write_element(value)
+    }
+
+    /// Write a string/raw argument with a variable name
+    ///
+    /// Strings are `null_terminated`, raw data is not.
+    fn write_named_data(
+        &mut self,
+        name: &str,
+        type_info: u32,
+        data: &[u8],
+        null_terminated: bool,
+    ) -> Result<(), PayloadError> {
+        let len = data.len() + usize::from(null_terminated);
+        if len > u16::MAX as usize {
+            return Err(PayloadError::InvalidData);
+        }
+
+        let type_info: u32 = type_info | PayloadType::VariableInfo.to_bit();
+        self.write_raw_type_info(type_info)?;
+        self.write_ordered((len as u16).to_le_bytes())?;
+        self.write_variable_info(name, None)?;
+        self.write_bytes(data)?;
+        if null_terminated {
+            self.write_bytes(&[0])?;
+        }
+        Ok(())
+    }
+
+    /// Write variable info: name length, [unit length], name, [unit]
+    ///
+    /// Lengths include the null terminator; empty strings are encoded with length 0.
+    fn write_variable_info(&mut self, name: &str, unit: Option<&str>) -> Result<(), PayloadError> {
+        let encoded_len = |text: &str| -> Result<u16, PayloadError> {
+            match text.len() {
+                0 => Ok(0),
+                n if n < u16::MAX as usize => Ok(n as u16 + 1),
+                _ => Err(PayloadError::InvalidData),
+            }
+        };
+
+        let name_len = encoded_len(name)?;
+        self.write_ordered(name_len.to_le_bytes())?;
+        if let Some(unit) = unit {
+            self.write_ordered(encoded_len(unit)?.to_le_bytes())?;
+        }
+
+        for text in core::iter::once(name).chain(unit) {
+            if !text.is_empty() {
+                self.write_bytes(text.as_bytes())?;
+                self.write_bytes(&[0])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Open a struct argument (STRU)
+    ///
+    /// All arguments added until the matching [`PayloadBuilder::close_struct`] become
+    /// entries of the struct. Structs can be nested up to `DLT_MAX_STRUCT_DEPTH` levels.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dlt_protocol::r19_11::*;
+    ///
+    /// let mut buffer = [0u8; 128];
+    /// let mut builder = PayloadBuilder::new(&mut buffer);
+    /// builder.open_struct().unwrap();
+    /// builder.add_u32(42).unwrap();
+    /// builder.add_string("wheel").unwrap();
+    /// builder.close_struct().unwrap();
+    /// ```
+    pub fn open_struct(&mut self) -> Result<(), PayloadError> {
+        self.write_struct_header(None)
+    }
+
+    /// Open a struct argument (STRU) with a variable name (VARI)
+    pub fn open_named_struct(&mut self, name: &str) -> Result<(), PayloadError> {
+        self.write_struct_header(Some(name))
+    }
+
+    /// Write the struct type info, a placeholder entry count and the optional name
+    fn write_struct_header(&mut self, name: Option<&str>) -> Result<(), PayloadError> {
+        if self.struct_depth >= DLT_MAX_STRUCT_DEPTH {
+            return Err(PayloadError::InvalidData);
+        }
+
+        let mut type_info = PayloadType::Struct.to_bit();
+        if name.is_some() {
+            type_info |= PayloadType::VariableInfo.to_bit();
+        }
+        self.write_raw_type_info(type_info)?;
+        let count_offset = self.position;
+        self.write_ordered(0u16.to_le_bytes())?;
+        if let Some(name) = name {
+            self.write_variable_info(name, None)?;
+        }
+
+        self.struct_offsets[self.struct_depth] = count_offset;
+        self.struct_entries[self.struct_depth] = 0;
+        self.struct_depth += 1;
+        Ok(())
+    }
+
+    /// Close the innermost open struct and write its entry count
+    pub fn close_struct(&mut self) -> Result<(), PayloadError> {
+        if self.struct_depth == 0 {
+            return Err(PayloadError::InvalidData);
+        }
+        self.struct_depth -= 1;
+
+        let offset = self.struct_offsets[self.struct_depth];
+        let entries = self.struct_entries[self.struct_depth];
+        let bytes = match self.endian {
+            DltEndian::Big => entries.to_be_bytes(),
+            DltEndian::Little => entries.to_le_bytes(),
+        };
+        self.buffer[offset..offset + 2].copy_from_slice(&bytes);
+        Ok(())
+    }
+
+    /// Number of currently open structs
+    pub fn struct_depth(&self) -> usize {
+        self.struct_depth
+    }
 }
 
+// ========================================
+// Array Element Types
+// ========================================
+
+/// Element type that can be stored in an array argument (ARAY)
+///
+/// Implemented for `bool`, `i8`-`i64`, `u8`-`u128`, `f32` and `f64`.
+pub trait DltArrayElement: Copy {
+    /// Basic type of the elements (Bool, Signed, Unsigned or Float)
+    const PAYLOAD_TYPE: PayloadType;
+    /// Size of one element
+    const TYPE_LENGTH: TypeLength;
+
+    /// Write the element in little-endian order into `out` (`TYPE_LENGTH` bytes)
+    fn write_le(self, out: &mut [u8]);
+}
+
+impl DltArrayElement for bool {
+    const PAYLOAD_TYPE: PayloadType = PayloadType::Bool;
+    const TYPE_LENGTH: TypeLength = TypeLength::Bit8;
+
+    fn write_le(self, out: &mut [u8]) {
+        out[0] = self as u8;
+    }
+}
+
+macro_rules! impl_array_element {
+    ($($ty:ty => $ptype:ident, $tlen:ident;)*) => {
+        $(
+            impl DltArrayElement for $ty {
+                const PAYLOAD_TYPE: PayloadType = PayloadType::$ptype;
+                const TYPE_LENGTH: TypeLength = TypeLength::$tlen;
+
+                fn write_le(self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_array_element! {
+    i8 => Signed, Bit8;
+    i16 => Signed, Bit16;
+    i32 => Signed, Bit32;
+    i64 => Signed, Bit64;
+    u8 => Unsigned, Bit8;
+    u16 => Unsigned, Bit16;
+    u32 => Unsigned, Bit32;
+    u64 => Unsigned, Bit64;
+    u128 => Unsigned, Bit128;
+    f32 => Float, Bit32;
+    f64 => Float, Bit64;
+}
+
+/// Integer type that can be the raw value of a fixed-point argument (FIXP)
+///
+/// Implemented for `i8`-`i64` and `u8`-`u64`, so floats, `bool` and 128-bit
+/// values are rejected at compile time:
+///
+/// ```compile_fail
+/// use dlt_protocol::r19_11::*;
+///
+/// let mut buffer = [0u8; 64];
+/// let mut builder = PayloadBuilder::new(&mut buffer);
+/// builder.add_fixed_point(1.5f32, 0.1, 0).unwrap();
+/// ```
+pub trait DltFixedPointElement: DltArrayElement {}
+
+impl DltFixedPointElement for i8 {}
+impl DltFixedPointElement for i16 {}
+impl DltFixedPointElement for i32 {}
+impl DltFixedPointElement for i64 {}
+impl DltFixedPointElement for u8 {}
+impl DltFixedPointElement for u16 {}
+impl DltFixedPointElement for u32 {}
+impl DltFixedPointElement for u64 {}
@@ -0,0 +1,930 @@
+//! # PCAP/PCAPNG Import (requires `std` feature)
+//!
+//! Extracts DLT messages from network captures (Wireshark, tcpdump) in the
+//! classic pcap format as well as pcapng.
+//!
+//! Supported layers:
+//! - Link: Ethernet (with VLAN tags), Linux cooked capture (SLL, SLL2), raw IP, BSD loopback
+//! - Network: IPv4 and IPv6 (fragmented packets are ignored)
+//! - Transport: UDP datagrams and TCP streams on the configured ports (3490 by default)
+//!
+//! TCP segments are reassembled per direction, including out-of-order and
+//! retransmitted segments, so messages split across segments are recovered.
+//! Captures that start in the middle of a connection are resynchronized on the
+//! first complete message that is followed by another plausible header.
+//!
+//! Every message is yielded with the capture timestamp of the packet that
+//! completed it, and can be turned into a [`DltStorageHeader`] to continue in
+//! the same pipeline as messages read from `.dlt` files.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//!
+//! let reader = DltPcapReader::from_file("trace.pcapng").unwrap().with_udp_port(3491);
+//! let mut writer = DltFileWriter::new("trace.dlt");
+//!
+//! for record in reader {
+//!     match record {
+//!         Ok(captured) => {
+//!             let message = captured.message();
+//!             println!("{:?} {:?}", captured.timestamp, message.extended_header);
+//!             writer.write_stored(&captured.storage_header(), captured.as_bytes()).unwrap();
+//!         }
+//!         Err(e) => eprintln!("{}", e),
+//!     }
+//! }
+//! ```
+
+use crate::r19_11::*;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+use std::time::Duration;
+
+/// Default DLT port for TCP and UDP
+pub const DLT_DEFAULT_PORT: u16 = 3490;
+
+/// Largest block or packet accepted before the file is considered corrupt
+const PCAP_MAX_BLOCK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Out-of-order TCP segments kept per direction before the gap is skipped
+const TCP_MAX_PENDING_SEGMENTS: usize = 64;
+
+// ========================================
+// PCAP Error Type
+// ========================================
+
+/// Error while reading a capture
+#[derive(Debug)]
+pub enum DltPcapError {
+    /// Reading the file failed (reading stops)
+    Io(io::Error),
+    /// The file is not a valid pcap/pcapng file (reading stops)
+    InvalidFormat(&'static str),
+    /// A UDP datagram could not be split into messages (reading continues)
+    Datagram {
+        /// Capture time of the packet
+        timestamp: Duration,
+        /// What was wrong with the datagram
+        error: DltDatagramError,
+    },
+}
+
+impl fmt::Display for DltPcapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DltPcapError::Io(e) => write!(f, "failed to read capture: {}", e),
+            DltPcapError::InvalidFormat(reason) => write!(f, "invalid capture file: {}", reason),
+            DltPcapError::Datagram { timestamp, error } => {
+                write!(f, "datagram at {:?}: {}", timestamp, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DltPcapError {}
+
+impl From<io::Error> for DltPcapError {
+    fn from(e: io::Error) -> Self {
+        DltPcapError::Io(e)
+    }
+}
+
+// ========================================
+// Captured Message
+// ========================================
+
+/// Transport protocol a message was captured from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DltTransport {
+    Tcp,
+    Udp,
+}
+
+/// A DLT message extracted from a capture
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DltPcapMessage {
+    /// Capture time (since 1970-01-01) of the packet that completed the message
+    pub timestamp: Duration,
+    /// Transport protocol
+    pub transport: DltTransport,
+    /// Sender address
+    pub source: SocketAddr,
+    /// Receiver address
+    pub destination: SocketAddr,
+    /// Complete message bytes (including a serial header, if any)
+    data: Vec<u8>,
+}
+
+impl DltPcapMessage {
+    /// Parse the message
+    pub fn message(&self) -> DltMessage<'_> {
+        DltHeaderParser::new(&self.data)
+            .parse_message()
+            .expect("message validated during extraction")
+    }
+
+    /// Message bytes, including a serial header if the stream used them
+    ///
+    /// `DltFileWriter` strips the serial header when writing the message.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Storage header with the capture time and the message's ECU ID
+    ///
+    /// Messages without ECU ID get "ECU\0", as in `DltFileWriter`.
+    pub fn storage_header(&self) -> DltStorageHeader {
+        let ecu_id = self.message().ecu_id.unwrap_or(*b"ECU\0");
+        DltStorageHeader::new(
+            self.timestamp.as_secs() as u32,
+            self.timestamp.subsec_micros() as i32,
+            &ecu_id,
+        )
+    }
+}
+
+// ========================================
+// Capture Formats
+// ========================================
+
+/// File format with its byte order
+#[derive(Debug, Clone, Copy)]
+enum CaptureFormat {
+    /// Classic pcap with timestamp fraction in nanoseconds (or microseconds)
+    Pcap { big_endian: bool, nanoseconds: bool, link_type: u32 },
+    /// pcapng section with the given byte order
+    PcapNg { big_endian: bool },
+}
+
+/// Interface described by a pcapng Interface Description Block
+#[derive(Debug, Clone, Copy)]
+struct CaptureInterface {
+    link_type: u32,
+    /// Timestamp units per second
+    units_per_second: u64,
+}
+
+/// One captured link-layer frame
+struct CapturedPacket {
+    timestamp: Duration,
+    link_type: u32,
+    data: Vec<u8>,
+}
+
+const PCAP_MAGIC_MICROS: u32 = 0xA1B2_C3D4;
+const PCAP_MAGIC_NANOS: u32 = 0xA1B2_3C4D;
+const PCAPNG_SECTION_HEADER: u32 = 0x0A0D_0D0A;
+const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+const PCAPNG_INTERFACE_DESCRIPTION: u32 = 1;
+const PCAPNG_PACKET: u32 = 2;
+const PCAPNG_SIMPLE_PACKET: u32 = 3;
+const PCAPNG_ENHANCED_PACKET: u32 = 6;
+const PCAPNG_OPTION_TSRESOL: u16 = 9;
+
+const LINKTYPE_NULL: u32 = 0;
+const LINKTYPE_ETHERNET: u32 = 1;
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_RAW_ALT: u32 = 12;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+const LINKTYPE_IPV4: u32 = 228;
+const LINKTYPE_IPV6: u32 = 229;
+const LINKTYPE_LINUX_SLL2: u32 = 276;
+
+fn read_u16(data: &[u8], big_endian: bool) -> u16 {
+    let bytes = [data[0], data[1]];
+    if big_endian { u16::from_be_bytes(bytes) } else { u16::from_le_bytes(bytes) }
+}
+
+fn read_u32(data: &[u8], big_endian: bool) -> u32 {
+    let bytes = [data[0], data[1], data[2], data[3]];
+    if big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) }
+}
+
+// ========================================
+// TCP Reassembly State
+// ========================================
+
+/// Reassembly state for one direction of a TCP connection
+#[derive(Default)]
+struct TcpFlow {
+    /// Sequence number of the next expected byte
+    next_seq: Option<u32>,
+    /// In-order stream data not yet consumed as messages
+    buffer: Vec<u8>,
+    /// Segments received ahead of `next_seq`
+    pending: Vec<(u32, Vec<u8>)>,
+    /// Whether the stream uses serial headers
+    serial: Option<bool>,
+    /// Message boundaries are known (the connection start was captured)
+    synchronized: bool,
+}
+
+impl TcpFlow {
+    /// Add a segment
+    ///
+    /// # Returns
+    /// Number of buffered bytes discarded because a gap in the stream was skipped
+    fn insert(&mut self, seq: u32, data: &[u8]) -> usize {
+        let next = *self.next_seq.get_or_insert(seq);
+        let offset = seq.wrapping_sub(next) as i32;
+
+        if offset > 0 {
+            self.pending.push((seq, data.to_vec()));
+            if self.pending.len() <= TCP_MAX_PENDING_SEGMENTS {
+                return 0;
+            }
+            // The missing data is not going to arrive: continue at the oldest pending segment
+            let oldest = self
+                .pending
+                .iter()
+                .map(|(seq, _)| *seq)
+                .min_by_key(|seq| seq.wrapping_sub(next))
+                .expect("pending segments");
+            let discarded = self.buffer.len();
+            self.buffer.clear();
+            self.synchronized = false;
+            self.next_seq = Some(oldest);
+            self.drain_pending();
+            return discarded;
+        }
+
+        // Skip bytes that were already received (retransmission)
+        let already = offset.unsigned_abs() as usize;
+        if already < data.len() {
+            self.append(&data[already..]);
+            self.drain_pending();
+        }
+        0
+    }
+
+    fn append(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+        self.next_seq = self.next_seq.map(|next| next.wrapping_add(data.len() as u32));
+    }
+
+    /// Append pending segments that became contiguous
+    fn drain_pending(&mut self) {
+        while let Some(next) = self.next_seq {
+            let Some(index) = self
+                .pending
+                .iter()
+                .position(|(seq, _)| seq.wrapping_sub(next) as i32 <= 0)
+            else {
+                break;
+            };
+            let (seq, data) = self.pending.swap_remove(index);
+            let already = next.wrapping_sub(seq) as usize;
+            if already < data.len() {
+                self.append(&data[already..]);
+            }
+        }
+    }
+
+    /// Take complete messages from the stream buffer
+    ///
+    /// Bytes that don't start a plausible message are skipped (counted in `skipped`).
+    fn take_messages(&mut self, skipped: &mut usize) -> Vec<Vec<u8>> {
+        let mut messages = Vec::new();
+        if !self.synchronized {
+            *skipped += self.synchronize();
+            if !self.synchronized {
+                return messages;
+            }
+        }
+        let mut position = 0;
+        while position < self.buffer.len() {
+            let rest = &self.buffer[position..];
+            match frame_size(rest, self.serial) {
+                Ok(size) if size <= rest.len() => {
+                    self.serial.get_or_insert(rest.starts_with(&DLT_SERIAL_HEADER_ARRAY));
+                    messages.push(rest[..size].to_vec());
+                    position += size;
+                }
+                Ok(_) | Err(DltHeaderError::BufferTooSmall) => break,
+                Err(_) => {
+                    position += 1;
+                    *skipped += 1;
+                }
+            }
+        }
+        self.buffer.drain(..position);
+        messages
+    }
+
+    /// Find the first message boundary in a stream whose start was not captured
+    ///
+    /// Headers without extended header are easily matched by chance, so a
+    /// candidate is only accepted if it is complete and followed by another
+    /// plausible header (or ends exactly at the end of the received data).
+    ///
+    /// # Returns
+    /// Number of bytes discarded
+    fn synchronize(&mut self) -> usize {
+        let mut keep_from = self.buffer.len();
+        for position in 0..self.buffer.len() {
+            let rest = &self.buffer[position..];
+            match frame_size(rest, None) {
+                Ok(size) if size <= rest.len() => {
+                    let next = &rest[size..];
+                    let chained = matches!(frame_size(next, None), Ok(_) | Err(DltHeaderError::BufferTooSmall));
+                    if next.is_empty() || chained {
+                        self.synchronized = true;
+                        self.buffer.drain(..position);
+                        return position;
+                    }
+                }
+                // May still become a complete message
+                Ok(_) | Err(DltHeaderError::BufferTooSmall) => keep_from = keep_from.min(position),
+                Err(_) => {}
+            }
+        }
+        self.buffer.drain(..keep_from);
+        keep_from
+    }
+}
+
+// ========================================
+// PCAP Reader
+// ========================================
+
+/// Reader extracting DLT messages from pcap and pcapng captures
+pub struct DltPcapReader<R: Read> {
+    reader: R,
+    format: CaptureFormat,
+    /// pcapng interfaces of the current section
+    interfaces: Vec<CaptureInterface>,
+    tcp_ports: HashSet<u16>,
+    udp_ports: HashSet<u16>,
+    flows: HashMap<(SocketAddr, SocketAddr), TcpFlow>,
+    queue: VecDeque<Result<DltPcapMessage, DltPcapError>>,
+    finished: bool,
+    packets: u64,
+    skipped: usize,
+}
+
+impl DltPcapReader<BufReader<File>> {
+    /// Open a capture file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, DltPcapError> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> DltPcapReader<R> {
+    /// Create a reader, detecting the format from the file header
+    ///
+    /// Default configuration: TCP and UDP on port [`DLT_DEFAULT_PORT`].
+    pub fn new(mut reader: R) -> Result<Self, DltPcapError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+
+        let mut this = Self {
+            reader,
+            format: CaptureFormat::PcapNg { big_endian: false },
+            interfaces: Vec::new(),
+            tcp_ports: HashSet::from([DLT_DEFAULT_PORT]),
+            udp_ports: HashSet::from([DLT_DEFAULT_PORT]),
+            flows: HashMap::new(),
+            queue: VecDeque::new(),
+            finished: false,
+            packets: 0,
+            skipped: 0,
+        };
+
+        if u32::from_le_bytes(magic) == PCAPNG_SECTION_HEADER {
+            this.read_section_header()?;
+        } else {
+            this.format = Self::pcap_format(magic, &mut this.reader)?;
+        }
+        Ok(this)
+    }
+
+    // ========================================
+    // Configuration Methods (Builder Pattern)
+    // ========================================
+
+    /// Also extract messages from TCP connections on `port`
+    pub fn with_tcp_port(mut self, port: u16) -> Self {
+        self.tcp_ports.insert(port);
+        self
+    }
+
+    /// Also extract messages from UDP datagrams to or from `port`
+    pub fn with_udp_port(mut self, port: u16) -> Self {
+        self.udp_ports.insert(port);
+        self
+    }
+
+    // ========================================
+    // Reading
+    // ========================================
+
+    /// Read the next message
+    ///
+    /// # Returns
+    /// - `Some(Ok(DltPcapMessage))`: Extracted message
+    /// - `Some(Err(DltPcapError::Datagram))`: Truncated or invalid UDP datagram, reading continues
+    /// - `Some(Err(_))`: I/O or format error, reading stops
+    /// - `None`: End of the capture
+    pub fn read_message(&mut self) -> Option<Result<DltPcapMessage, DltPcapError>> {
+        loop {
+            if let Some(item) = self.queue.pop_front() {
+                return Some(item);
+            }
+            if self.finished {
+                return None;
+            }
+            match self.read_packet() {
+                Ok(Some(packet)) => {
+                    self.packets += 1;
+                    self.process_packet(&packet);
+                }
+                Ok(None) => self.finished = true,
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+
+    /// Number of captured packets read so far
+    pub fn packet_count(&self) -> u64 {
+        self.packets
+    }
+
+    /// Number of TCP stream bytes skipped because they didn't form a valid message
+    pub fn skipped_bytes(&self) -> usize {
+        self.skipped
+    }
+
+    // ========================================
+    // File Formats
+    // ========================================
+
+    /// Parse the rest of a classic pcap global header
+    fn pcap_format(magic: [u8; 4], reader: &mut R) -> Result<CaptureFormat, DltPcapError> {
+        let (big_endian, nanoseconds) = match (u32::from_le_bytes(magic), u32::from_be_bytes(magic)) {
+            (PCAP_MAGIC_MICROS, _) => (false, false),
+            (PCAP_MAGIC_NANOS, _) => (false, true),
+            (_, PCAP_MAGIC_MICROS) => (true, false),
+            (_, PCAP_MAGIC_NANOS) => (true, true),
+            _ => return Err(DltPcapError::InvalidFormat("unknown file magic")),
+        };
+
+        let mut header = [0u8; 20];
+        reader.read_exact(&mut header)?;
+        let link_type = read_u32(&header[16..20], big_endian) & 0x0FFF_FFFF;
+        Ok(CaptureFormat::Pcap {
+            big_endian,
+            nanoseconds,
+            link_type,
+        })
+    }
+
+    /// Read the next packet, skipping non-packet blocks
+    fn read_packet(&mut self) -> Result<Option<CapturedPacket>, DltPcapError> {
+        loop {
+            match self.format {
+                CaptureFormat::Pcap {
+                    big_endian,
+                    nanoseconds,
+                    link_type,
+                } => return self.read_pcap_record(big_endian, nanoseconds, link_type),
+                CaptureFormat::PcapNg { big_endian } => match self.read_pcapng_block(big_endian)? {
+                    Some(Some(packet)) => return Ok(Some(packet)),
+                    Some(None) => {}
+                    None => return Ok(None),
+                },
+            }
+        }
+    }
+
+    /// Fill `buffer` completely, or return `false` at a clean end of file
+    fn read_or_eof(&mut self, buffer: &mut [u8]) -> Result<bool, DltPcapError> {
+        let mut filled = 0;
+        while filled < buffer.len() {
+            match self.reader.read(&mut buffer[filled..]) {
+                Ok(0) if filled == 0 => return Ok(false),
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(true)
+    }
+
+    fn read_body(&mut self, size: usize) -> Result<Vec<u8>, DltPcapError> {
+        if size > PCAP_MAX_BLOCK_SIZE {
+            return Err(DltPcapError::InvalidFormat("block too large"));
+        }
+        let mut body = vec![0u8; size];
+        self.reader.read_exact(&mut body)?;
+        Ok(body)
+    }
+
+    fn read_pcap_record(
+        &mut self,
+        big_endian: bool,
+        nanoseconds: bool,
+        link_type: u32,
+    ) -> Result<Option<CapturedPacket>, DltPcapError> {
+        let mut header = [0u8; 16];
+        if !self.read_or_eof(&mut header)? {
+            return Ok(None);
+        }
+
+        let seconds = read_u32(&header[0..4], big_endian) as u64;
+        let fraction = read_u32(&header[4..8], big_endian);
+        let captured = read_u32(&header[8..12], big_endian) as usize;
+        let nanos = if nanoseconds { fraction } else { fraction.saturating_mul(1000) };
+
+        Ok(Some(CapturedPacket {
+            timestamp: Duration::new(seconds, 0) + Duration::from_nanos(nanos as u64),
+            link_type,
+            data: self.read_body(captured)?,
+        }))
+    }
+
+    /// Read one pcapng block
+    ///
+    /// # Returns
+    /// `None` at end of file, `Some(None)` for blocks without a packet
+    fn read_pcapng_block(
+        &mut self,
+        big_endian: bool,
+    ) -> Result<Option<Option<CapturedPacket>>, DltPcapError> {
+        let mut block_type = [0u8; 4];
+        if !self.read_or_eof(&mut block_type)? {
+            return Ok(None);
+        }
+        if u32::from_le_bytes(block_type) == PCAPNG_SECTION_HEADER {
+            self.read_section_header()?;
+            return Ok(Some(None));
+        }
+
+        let block_type = read_u32(&block_type, big_endian);
+        let mut length = [0u8; 4];
+        self.reader.read_exact(&mut length)?;
+        let length = read_u32(&length, big_endian) as usize;
+        if length < 12 || !length.is_multiple_of(4) {
+            return Err(DltPcapError::InvalidFormat("invalid block length"));
+        }
+        // Body without type, length and trailing length
+        let body = self.read_body(length - 8)?;
+        let body = &body[..length - 12];
+
+        let packet = match block_type {
+            PCAPNG_INTERFACE_DESCRIPTION => {
+                self.read_interface(body, big_endian)?;
+                None
+            }
+            PCAPNG_ENHANCED_PACKET | PCAPNG_PACKET => {
+                if body.len() < 20 {
+                    return Err(DltPcapError::InvalidFormat("short packet block"));
+                }
+                let interface = if block_type == PCAPNG_PACKET {
+                    read_u16(&body[0..2], big_endian) as usize
+                } else {
+                    read_u32(&body[0..4], big_endian) as usize
+                };
+                let high = read_u32(&body[4..8], big_endian) as u64;
+                let low = read_u32(&body[8..12], big_endian) as u64;
+                let captured = read_u32(&body[12..16], big_endian) as usize;
+                let interface = *self
+                    .interfaces
+                    .get(interface)
+                    .ok_or(DltPcapError::InvalidFormat("unknown interface"))?;
+                let data = body
+                    .get(20..20 + captured)
+                    .ok_or(DltPcapError::InvalidFormat("packet exceeds block"))?;
+                Some(CapturedPacket {
+                    timestamp: Self::pcapng_timestamp(high << 32 | low, interface.units_per_second),
+                    link_type: interface.link_type,
+                    data: data.to_vec(),
+                })
+            }
+            PCAPNG_SIMPLE_PACKET => {
+                // No timestamp and always interface 0
+                let interface = *self
+                    .interfaces
+                    .first()
+                    .ok_or(DltPcapError::InvalidFormat("unknown interface"))?;
+                if body.len() < 4 {
+                    return Err(DltPcapError::InvalidFormat("short packet block"));
+                }
+                let original = read_u32(body, big_endian) as usize;
+                let data = &body[4..];
+                Some(CapturedPacket {
+                    timestamp: Duration::ZERO,
+                    link_type: interface.link_type,
+                    data: data[..original.min(data.len())].to_vec(),
+                })
+            }
+            _ => None,
+        };
+        Ok(Some(packet))
+    }
+
+    /// Read a section header block after its block type, resetting the section state
+    fn read_section_header(&mut self) -> Result<(), DltPcapError> {
+        let mut header = [0u8; 8];
+        self.reader.read_exact(&mut header)?;
+        let big_endian = match u32::from_le_bytes([header[4], header[5], header[6], header[7]]) {
+            PCAPNG_BYTE_ORDER_MAGIC => false,
+            magic if magic.swap_bytes() == PCAPNG_BYTE_ORDER_MAGIC => true,
+            _ => return Err(DltPcapError::InvalidFormat("invalid byte-order magic")),
+        };
+
+        let length = read_u32(&header[0..4], big_endian) as usize;
+        if length < 28 || !length.is_multiple_of(4) {
+            return Err(DltPcapError::InvalidFormat("invalid section header length"));
+        }
+        // Skip version, section length, options and trailing length
+        self.read_body(length - 12)?;
+
+        self.format = CaptureFormat::PcapNg { big_endian };
+        self.interfaces.clear();
+        Ok(())
+    }
+
+    fn read_interface(&mut self, body: &[u8], big_endian: bool) -> Result<(), DltPcapError> {
+        if body.len() < 8 {
+            return Err(DltPcapError::InvalidFormat("short interface block"));
+        }
+        let link_type = read_u16(&body[0..2], big_endian) as u32;
+        let mut units_per_second = 1_000_000;
+
+        let mut options = &body[8..];
+        while options.len() >= 4 {
+            let code = read_u16(&options[0..2], big_endian);
+            let length = read_u16(&options[2..4], big_endian) as usize;
+            let Some(value) = options.get(4..4 + length) else {
+                break;
+            };
+            if code == PCAPNG_OPTION_TSRESOL && length == 1 {
+                let exponent = (value[0] & 0x7F) as u32;
+                let base: u64 = if value[0] & 0x80 != 0 { 2 } else { 10 };
+                units_per_second = base.checked_pow(exponent).unwrap_or(1_000_000_000);
+            }
+            options = &options[(4 + length).next_multiple_of(4).min(options.len())..];
+        }
+
+        self.interfaces.push(CaptureInterface {
+            link_type,
+            units_per_second,
+        });
+        Ok(())
+    }
+
+    fn pcapng_timestamp(value: u64, units_per_second: u64) -> Duration {
+        let units = units_per_second.max(1);
+        let seconds = value / units;
+        let nanos = (value % units) as u128 * 1_000_000_000 / units as u128;
+        Duration::new(seconds, nanos as u32)
+    }
+
+    // ========================================
+    // Packet Decoding
+    // ========================================
+
+    /// Decode link, network and transport layers and extract DLT messages
+    fn process_packet(&mut self, packet: &CapturedPacket) {
+        let Some(ip) = link_payload(packet.link_type, &packet.data) else {
+            return;
+        };
+        let Some(ip) = IpPacket::parse(ip) else {
+            return;
+        };
+
+        match ip.protocol {
+            IP_PROTOCOL_TCP => self.process_tcp(packet.timestamp, &ip),
+            IP_PROTOCOL_UDP => self.process_udp(packet.timestamp, &ip),
+            _ => {}
+        }
+    }
+
+    fn process_udp(&mut self, timestamp: Duration, ip: &IpPacket) {
+        let Some((source, destination)) = ip.transport_ports(UDP_HEADER_SIZE) else {
+            return;
+        };
+        if !self.udp_ports.contains(&source.port()) && !self.udp_ports.contains(&destination.port()) {
+            return;
+        }
+
+        let segment = ip.payload;
+        let length = (u16::from_be_bytes([segment[4], segment[5]]) as usize).clamp(UDP_HEADER_SIZE, segment.len());
+        let datagram = DltDatagram::new(&segment[UDP_HEADER_SIZE..length], Some(source));
+        let mut frames = datagram.messages();
+        while let Some(frame) = frames.next_frame() {
+            let item = match frame {
+                Ok(data) => Ok(DltPcapMessage {
+                    timestamp,
+                    transport: DltTransport::Udp,
+                    source,
+                    destination,
+                    data: data.to_vec(),
+                }),
+                Err(error) => Err(DltPcapError::Datagram { timestamp, error }),
+            };
+            self.queue.push_back(item);
+        }
+    }
+
+    fn process_tcp(&mut self, timestamp: Duration, ip: &IpPacket) {
+        let Some((source, destination)) = ip.transport_ports(TCP_MIN_HEADER_SIZE) else {
+            return;
+        };
+        if !self.tcp_ports.contains(&source.port()) && !self.tcp_ports.contains(&destination.port()) {
+            return;
+        }
+
+        let segment = ip.payload;
+        let seq = u32::from_be_bytes([segment[4], segment[5], segment[6], segment[7]]);
+        let header_len = ((segment[12] >> 4) as usize * 4).min(segment.len());
+        let flags = segment[13];
+        let key = (source, destination);
+
+        if flags & TCP_FLAG_SYN != 0 {
+            // New connection: data starts after the SYN's sequence number
+            self.flows.insert(
+                key,
+                TcpFlow {
+                    next_seq: Some(seq.wrapping_add(1)),
+                    synchronized: true,
+                    ..TcpFlow::default()
+                },
+            );
+            return;
+        }
+        if ip.truncated {
+            // Part of the segment was not captured (snap length): the stream has a
+            // gap, start over and resynchronize on the next segment
+            if let Some(flow) = self.flows.remove(&key) {
+                self.skipped += flow.buffer.len();
+            }
+            return;
+        }
+
+        let flow = self.flows.entry(key).or_default();
+        let payload = &segment[header_len..];
+        if !payload.is_empty() {
+            self.skipped += flow.insert(seq, payload);
+        }
+        for data in flow.take_messages(&mut self.skipped) {
+            self.queue.push_back(Ok(DltPcapMessage {
+                timestamp,
+                transport: DltTransport::Tcp,
+                source,
+                destination,
+                data,
+            }));
+        }
+        if flags & (TCP_FLAG_FIN | TCP_FLAG_RST) != 0 {
+            self.flows.remove(&key);
+        }
+    }
+}
+
+// ========================================
+// Link and Network Layers
+// ========================================
+
+const IP_PROTOCOL_TCP: u8 = 6;
+const IP_PROTOCOL_UDP: u8 = 17;
+const UDP_HEADER_SIZE: usize = 8;
+const TCP_MIN_HEADER_SIZE: usize = 20;
+const TCP_FLAG_FIN: u8 = 0x01;
+const TCP_FLAG_SYN: u8 = 0x02;
+const TCP_FLAG_RST: u8 = 0x04;
+
+/// IP packet inside a link-layer frame
+fn link_payload(link_type: u32, data: &[u8]) -> Option<&[u8]> {
+    let be16 = |offset: usize| Some(u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?));
+    match link_type {
+        LINKTYPE_ETHERNET => {
+            let mut offset = 12;
+            let mut ethertype = be16(offset)?;
+            // Skip VLAN tags (802.1Q, 802.1ad)
+            while ethertype == 0x8100 || ethertype == 0x88A8 {
+                offset += 4;
+                ethertype = be16(offset)?;
+            }
+            match ethertype {
+                0x0800 | 0x86DD => data.get(offset + 2..),
+                _ => None,
+            }
+        }
+        LINKTYPE_LINUX_SLL => data.get(16..),
+        LINKTYPE_LINUX_SLL2 => data.get(20..),
+        LINKTYPE_NULL => data.get(4..),
+        LINKTYPE_RAW | LINKTYPE_RAW_ALT | LINKTYPE_IPV4 | LINKTYPE_IPV6 => Some(data),
+        _ => None,
+    }
+}
+
+/// Decoded IPv4 or IPv6 packet
+struct IpPacket<'a> {
+    protocol: u8,
+    source: IpAddr,
+    destination: IpAddr,
+    /// Transport segment (as far as captured)
+    payload: &'a [u8],
+    /// The capture ends before the end of the packet
+    truncated: bool,
+}
+
+impl<'a> IpPacket<'a> {
+    /// Parse an IP packet; fragments are not reassembled and return `None`
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let (protocol, source, destination, start, end) = match data.first()? >> 4 {
+            4 => {
+                let header_len = ((data[0] & 0x0F) as usize) * 4;
+                let total_len = u16::from_be_bytes(data.get(2..4)?.try_into().ok()?) as usize;
+                let fragment = u16::from_be_bytes(data.get(6..8)?.try_into().ok()?);
+                if fragment & 0x3FFF != 0 {
+                    return None;
+                }
+                let source: [u8; 4] = data.get(12..16)?.try_into().ok()?;
+                let destination: [u8; 4] = data.get(16..20)?.try_into().ok()?;
+                (
+                    data[9],
+                    IpAddr::V4(Ipv4Addr::from(source)),
+                    IpAddr::V4(Ipv4Addr::from(destination)),
+                    header_len,
+                    total_len,
+                )
+            }
+            6 => {
+                let payload_len = u16::from_be_bytes(data.get(4..6)?.try_into().ok()?) as usize;
+                let source: [u8; 16] = data.get(8..24)?.try_into().ok()?;
+                let destination: [u8; 16] = data.get(24..40)?.try_into().ok()?;
+                let mut next_header = data[6];
+                let mut offset = 40;
+                loop {
+                    match next_header {
+                        // Hop-by-hop, routing and destination options
+                        0 | 43 | 60 => {
+                            next_header = *data.get(offset)?;
+                            offset += (*data.get(offset + 1)? as usize + 1) * 8;
+                        }
+                        // Authentication header
+                        51 => {
+                            next_header = *data.get(offset)?;
+                            offset += (*data.get(offset + 1)? as usize + 2) * 4;
+                        }
+                        // Fragment header
+                        44 => return None,
+                        _ => break,
+                    }
+                }
+                (
+                    next_header,
+                    IpAddr::V6(Ipv6Addr::from(source)),
+                    IpAddr::V6(Ipv6Addr::from(destination)),
+                    offset,
+                    40 + payload_len,
+                )
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            protocol,
+            source,
+            destination,
+            payload: data.get(start..end.min(data.len()))?,
+            truncated: data.len() < end,
+        })
+    }
+
+    /// Source and destination socket addresses of a TCP/UDP segment
+    ///
+    /// # Returns
+    /// `None` if the segment is shorter than `min_header_len`
+    fn transport_ports(&self, min_header_len: usize) -> Option<(SocketAddr, SocketAddr)> {
+        let segment = self.payload;
+        if segment.len() < min_header_len {
+            return None;
+        }
+        Some((
+            SocketAddr::new(self.source, u16::from_be_bytes([segment[0], segment[1]])),
+            SocketAddr::new(self.destination, u16::from_be_bytes([segment[2], segment[3]])),
+        ))
+    }
+}
+
+impl<R: Read> Iterator for DltPcapReader<R> {
+    type Item = Result<DltPcapMessage, DltPcapError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_message()
+    }
+}
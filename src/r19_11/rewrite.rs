@@ -0,0 +1,234 @@
+//! # Message Rewriting
+//!
+//! When logs from several ECUs or test benches are merged, IDs collide and
+//! timestamps refer to different uptimes. [`DltMessageRewriter`] applies a set
+//! of rules to parsed messages:
+//!
+//! - Rename ECU, application and context IDs via lookup tables
+//! - Set or remove the standard header extra fields (ECU ID, session ID, timestamp)
+//! - Shift timestamps by a fixed offset
+//! - Add or remove the serial header
+//!
+//! The payload is not copied: [`DltMessageRewriter::rewrite`] returns a
+//! [`DltMessage`] borrowing the original payload, and [`DltMessage::write`]
+//! encodes it with HTYP and LEN matching the new fields.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//!
+//! const ECU_MAP: [([u8; 4], [u8; 4]); 2] = [(*b"ECU1", *b"BEN1"), (*b"ECU2", *b"BEN2")];
+//!
+//! let rewriter = DltMessageRewriter::new()
+//!     .with_ecu_id_map(&ECU_MAP)
+//!     .with_timestamp_offset(-10_000) // 1 second earlier
+//!     .without_serial_header();
+//!
+//! let received: &[u8] = &[/* DLT message bytes */];
+//! let message = DltHeaderParser::new(received).parse_message().unwrap();
+//!
+//! let mut buffer = [0u8; 1024];
+//! let size = rewriter.rewrite_into(&message, &mut buffer).unwrap();
+//! let rewritten = &buffer[..size];
+//! ```
+
+use crate::r19_11::*;
+
+/// ID lookup table: pairs of (original ID, new ID)
+pub type DltIdMap<'a> = &'a [([u8; DLT_ID_SIZE], [u8; DLT_ID_SIZE])];
+
+/// What to do with an optional header field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldRule<T> {
+    Keep,
+    Set(T),
+    Remove,
+}
+
+impl<T: Copy> FieldRule<T> {
+    fn apply(self, value: Option<T>) -> Option<T> {
+        match self {
+            FieldRule::Keep => value,
+            FieldRule::Set(new) => Some(new),
+            FieldRule::Remove => None,
+        }
+    }
+}
+
+/// Look up `id` in `map`, keeping it if there is no entry
+fn map_id(map: DltIdMap<'_>, id: [u8; DLT_ID_SIZE]) -> [u8; DLT_ID_SIZE] {
+    map.iter()
+        .find(|(from, _)| *from == id)
+        .map_or(id, |(_, to)| *to)
+}
+
+// ========================================
+// Message Rewriter
+// ========================================
+
+/// Rewrites header fields of parsed messages
+///
+/// Rules are configured with the builder methods; fields without a rule are
+/// kept unchanged. ID maps are applied first, then set/remove rules, then the
+/// timestamp offset.
+#[derive(Debug, Clone, Copy)]
+pub struct DltMessageRewriter<'a> {
+    ecu_id_map: DltIdMap<'a>,
+    app_id_map: DltIdMap<'a>,
+    context_id_map: DltIdMap<'a>,
+    ecu_id: FieldRule<[u8; DLT_ID_SIZE]>,
+    session_id: FieldRule<u32>,
+    timestamp: FieldRule<u32>,
+    /// Added to timestamps, in 0.1ms units
+    timestamp_offset: i64,
+    serial_header: Option<bool>,
+}
+
+impl Default for DltMessageRewriter<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> DltMessageRewriter<'a> {
+    /// Create a rewriter that leaves messages unchanged
+    pub const fn new() -> Self {
+        Self {
+            ecu_id_map: &[],
+            app_id_map: &[],
+            context_id_map: &[],
+            ecu_id: FieldRule::Keep,
+            session_id: FieldRule::Keep,
+            timestamp: FieldRule::Keep,
+            timestamp_offset: 0,
+            serial_header: None,
+        }
+    }
+
+    // ========================================
+    // Configuration Methods (Builder Pattern)
+    // ========================================
+
+    /// Rename ECU IDs (standard header and storage header) found in `map`
+    pub fn with_ecu_id_map(mut self, map: DltIdMap<'a>) -> Self {
+        self.ecu_id_map = map;
+        self
+    }
+
+    /// Rename application IDs found in `map`
+    pub fn with_app_id_map(mut self, map: DltIdMap<'a>) -> Self {
+        self.app_id_map = map;
+        self
+    }
+
+    /// Rename context IDs found in `map`
+    pub fn with_context_id_map(mut self, map: DltIdMap<'a>) -> Self {
+        self.context_id_map = map;
+        self
+    }
+
+    /// Set the ECU ID of every message, adding the field where missing
+    pub fn with_ecu_id(mut self, ecu_id: &[u8; DLT_ID_SIZE]) -> Self {
+        self.ecu_id = FieldRule::Set(*ecu_id);
+        self
+    }
+
+    /// Remove the ECU ID field
+    pub fn without_ecu_id(mut self) -> Self {
+        self.ecu_id = FieldRule::Remove;
+        self
+    }
+
+    /// Set the session ID of every message, adding the field where missing
+    pub fn with_session_id(mut self, session_id: u32) -> Self {
+        self.session_id = FieldRule::Set(session_id);
+        self
+    }
+
+    /// Remove the session ID field
+    pub fn without_session_id(mut self) -> Self {
+        self.session_id = FieldRule::Remove;
+        self
+    }
+
+    /// Set the timestamp (0.1ms units) of every message, adding the field where missing
+    pub fn with_timestamp(mut self, timestamp: u32) -> Self {
+        self.timestamp = FieldRule::Set(timestamp);
+        self
+    }
+
+    /// Remove the timestamp field
+    pub fn without_timestamp(mut self) -> Self {
+        self.timestamp = FieldRule::Remove;
+        self
+    }
+
+    /// Shift timestamps by `offset` (0.1ms units, may be negative)
+    ///
+    /// Results are clamped to the range of the timestamp field.
+    pub fn with_timestamp_offset(mut self, offset: i64) -> Self {
+        self.timestamp_offset = offset;
+        self
+    }
+
+    /// Prefix every message with a serial header ("DLS\x01")
+    pub fn with_serial_header(mut self) -> Self {
+        self.serial_header = Some(true);
+        self
+    }
+
+    /// Strip serial headers
+    pub fn without_serial_header(mut self) -> Self {
+        self.serial_header = Some(false);
+        self
+    }
+
+    // ========================================
+    // Rewriting
+    // ========================================
+
+    /// Apply the rules to a message
+    ///
+    /// The returned message borrows the original payload. Its standard header
+    /// (HTYP, LEN) and decoded header type are updated to match the new fields.
+    pub fn rewrite<'m>(&self, message: &DltMessage<'m>) -> DltMessage<'m> {
+        let mut rewritten = *message;
+
+        rewritten.ecu_id = self
+            .ecu_id
+            .apply(message.ecu_id.map(|id| map_id(self.ecu_id_map, id)));
+        rewritten.session_id = self.session_id.apply(message.session_id);
+        rewritten.timestamp = self.timestamp.apply(message.timestamp).map(|timestamp| {
+            (timestamp as i64 + self.timestamp_offset).clamp(0, u32::MAX as i64) as u32
+        });
+        if let Some(extended) = rewritten.extended_header.as_mut() {
+            extended.apid = map_id(self.app_id_map, extended.apid);
+            extended.ctid = map_id(self.context_id_map, extended.ctid);
+        }
+        if let Some(serial) = self.serial_header {
+            rewritten.has_serial_header = serial;
+        }
+
+        rewritten.sync_header();
+        rewritten
+    }
+
+    /// Apply the rules and write the result into the start of `buffer`
+    ///
+    /// # Returns
+    /// - `Ok(usize)`: Number of bytes written
+    /// - `Err(DltError::BufferTooSmall)`: Buffer too small for the rewritten message
+    /// - `Err(DltError::InvalidParameter)`: Rewritten message too long for the length field
+    pub fn rewrite_into(&self, message: &DltMessage<'_>, buffer: &mut [u8]) -> Result<usize, DltError> {
+        self.rewrite(message).write(buffer)
+    }
+
+    /// Apply the ECU ID map to a storage header (when rewriting DLT files)
+    pub fn rewrite_storage_header(&self, header: &DltStorageHeader) -> DltStorageHeader {
+        DltStorageHeader {
+            ecu_id: map_id(self.ecu_id_map, header.ecu_id),
+            ..*header
+        }
+    }
+}
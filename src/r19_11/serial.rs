@@ -0,0 +1,283 @@
+//! # Serial Stream Framing
+//!
+//! On serial links (UART) every message is prefixed with the serial header
+//! "DLS\x01". Line noise, a receiver that starts mid-message or a dropped byte
+//! leave bytes in the stream that don't belong to any message. [`DltSerialScanner`]
+//! searches for the serial header, validates the header that follows it and
+//! skips everything that doesn't form a plausible message.
+//!
+//! A candidate is accepted when:
+//! - The version is 1
+//! - The length field covers at least the headers announced by HTYP
+//! - The extended header (if present) has a known message type and type info
+//! - It parses completely and is followed by another serial header or the end
+//!   of the data; otherwise, if another valid serial frame starts inside the
+//!   candidate's length, the candidate lost bytes and scanning resumes there
+//!
+//! A message whose payload happens to contain a serial frame (e.g. forwarded
+//! serial traffic) is still accepted.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//!
+//! let received: &[u8] = &[/* bytes read from the UART */];
+//! let mut scanner = DltSerialScanner::new(received);
+//!
+//! while let Some(frame) = scanner.next_frame() {
+//!     if frame.skipped > 0 {
+//!         eprintln!("Discarded {} bytes", frame.skipped);
+//!     }
+//!     println!("Payload: {:?}", frame.message.payload);
+//! }
+//!
+//! // Keep the unconsumed tail (an incomplete message) for the next read
+//! let tail = &received[scanner.position()..];
+//! ```
+
+use crate::r19_11::*;
+
+// ========================================
+// Header Validation
+// ========================================
+
+/// Check that `data` starts with a plausible DLT v1 message header
+///
+/// `data` starts at the standard header (without serial header). Only the
+/// headers are inspected, the payload doesn't need to be available.
+///
+/// # Returns
+/// - `Ok(usize)`: Total message length from the length field
+/// - `Err(DltHeaderError::BufferTooSmall)`: Not enough bytes to decide
+/// - `Err(DltHeaderError::InvalidVersion)`: Version is not 1
+/// - `Err(DltHeaderError::InvalidLength)`: Length is smaller than the headers
+/// - `Err(DltHeaderError::InvalidMessageType)`: Implausible extended header
+pub fn check_message_header(data: &[u8]) -> Result<usize, DltHeaderError> {
+    let htyp = *data.first().ok_or(DltHeaderError::BufferTooSmall)?;
+    if (htyp & VERS_MASK) >> 5 != 1 {
+        return Err(DltHeaderError::InvalidVersion);
+    }
+    if data.len() < DLT_STANDARD_HEADER_SIZE {
+        return Err(DltHeaderError::BufferTooSmall);
+    }
+
+    let len = u16::from_be_bytes([data[2], data[3]]) as usize;
+    let mut header_size = DLT_STANDARD_HEADER_SIZE;
+    for (mask, size) in [(WEID_MASK, DLT_ID_SIZE), (WSID_MASK, 4), (WTMS_MASK, 4)] {
+        if htyp & mask != 0 {
+            header_size += size;
+        }
+    }
+    let extended_offset = header_size;
+    if htyp & UEH_MASK != 0 {
+        header_size += DLT_EXTENDED_HEADER_SIZE;
+    }
+    if len < header_size {
+        return Err(DltHeaderError::InvalidLength);
+    }
+
+    if htyp & UEH_MASK != 0 {
+        let msin = *data
+            .get(extended_offset)
+            .ok_or(DltHeaderError::BufferTooSmall)?;
+        let mtin = extract_msin_mtin(msin);
+        let valid = match MstpType::parse(extract_msin_mstp(msin)) {
+            MstpType::DltTypeLog => (1..=6).contains(&mtin),
+            MstpType::DltTypeAppTrace => (1..=5).contains(&mtin),
+            MstpType::DltTypeNwTrace => mtin != 0,
+            MstpType::DltTypeControl => (1..=2).contains(&mtin),
+            MstpType::Reserved(_) | MstpType::Invalid(_) => false,
+        };
+        if !valid {
+            return Err(DltHeaderError::InvalidMessageType);
+        }
+    }
+
+    Ok(len)
+}
+
+// ========================================
+// Serial Frame
+// ========================================
+
+/// A message found in a serial stream
+#[derive(Debug, Clone, Copy)]
+pub struct DltSerialFrame<'a> {
+    /// Parsed message
+    pub message: DltMessage<'a>,
+    /// Complete message bytes, including the serial header
+    pub data: &'a [u8],
+    /// Bytes discarded between the previous frame and this one
+    pub skipped: usize,
+}
+
+// ========================================
+// Serial Stream Scanner
+// ========================================
+
+/// Finds serial-header-framed messages in a byte stream, skipping garbage
+///
+/// Scanning stops at an incomplete message at the end of the data; bytes from
+/// [`DltSerialScanner::position`] on should be kept and completed by the next
+/// read. A trailing partial "DLS\x01" pattern is kept as well.
+pub struct DltSerialScanner<'a> {
+    data: &'a [u8],
+    position: usize,
+    skipped: usize,
+    skipped_since_frame: usize,
+}
+
+impl<'a> DltSerialScanner<'a> {
+    /// Create a new scanner over received serial data
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            position: 0,
+            skipped: 0,
+            skipped_since_frame: 0,
+        }
+    }
+
+    /// Find the next valid message
+    ///
+    /// # Returns
+    /// - `Some(DltSerialFrame)`: Message found (garbage before it was skipped)
+    /// - `None`: No complete message left in the data
+    pub fn next_frame(&mut self) -> Option<DltSerialFrame<'a>> {
+        loop {
+            let rest = &self.data[self.position..];
+            let Some(offset) = find_serial_header(rest) else {
+                // Keep a trailing partial pattern, it may complete with the next read
+                let keep = (1..DLT_SERIAL_HEADER_SIZE)
+                    .rev()
+                    .find(|&n| rest.ends_with(&DLT_SERIAL_HEADER_ARRAY[..n]))
+                    .unwrap_or(0);
+                self.skip(rest.len() - keep);
+                return None;
+            };
+            self.skip(offset);
+
+            let candidate = &self.data[self.position..];
+            let len = match check_message_header(&candidate[DLT_SERIAL_HEADER_SIZE..]) {
+                Ok(len) => len,
+                Err(DltHeaderError::BufferTooSmall) => return None,
+                Err(_) => {
+                    self.skip(1);
+                    continue;
+                }
+            };
+
+            let end = DLT_SERIAL_HEADER_SIZE + len;
+            if candidate.len() < end {
+                // A false sync claiming a large length would hold back every frame
+                // behind it: resync on a complete frame that is already buffered
+                if let Some(next) = find_complete_nested_frame(candidate) {
+                    self.skip(next);
+                    continue;
+                }
+                return None;
+            }
+            let data = &candidate[..end];
+            let parsed = DltHeaderParser::new(data).parse_message();
+            // A frame that doesn't parse or runs into garbage may have lost bytes
+            if (parsed.is_err() || !is_frame_boundary(&candidate[end..]))
+                && let Some(next) = find_nested_frame(candidate, end)
+            {
+                // Another frame starts inside this one: resync there
+                self.skip(next);
+                continue;
+            }
+
+            match parsed {
+                Ok(message) => {
+                    let skipped = core::mem::take(&mut self.skipped_since_frame);
+                    self.position += end;
+                    return Some(DltSerialFrame {
+                        message,
+                        data,
+                        skipped,
+                    });
+                }
+                Err(_) => self.skip(1),
+            }
+        }
+    }
+
+    /// Discard `count` bytes at the current position
+    fn skip(&mut self, count: usize) {
+        self.position += count;
+        self.skipped += count;
+        self.skipped_since_frame += count;
+    }
+
+    /// Get current scanning position (start of the unconsumed data)
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get remaining unconsumed data
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.position)
+    }
+
+    /// Total number of bytes discarded as garbage
+    pub fn skipped_bytes(&self) -> usize {
+        self.skipped
+    }
+}
+
+impl<'a> Iterator for DltSerialScanner<'a> {
+    type Item = DltSerialFrame<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_frame()
+    }
+}
+
+/// Offset of the first serial header pattern in `data`
+fn find_serial_header(data: &[u8]) -> Option<usize> {
+    data.windows(DLT_SERIAL_HEADER_SIZE)
+        .position(|window| window == DLT_SERIAL_HEADER_ARRAY)
+}
+
+/// Whether a frame may end right before `rest`: end of data, a serial header
+/// or the start of one
+fn is_frame_boundary(rest: &[u8]) -> bool {
+    let n = rest.len().min(DLT_SERIAL_HEADER_SIZE);
+    rest[..n] == DLT_SERIAL_HEADER_ARRAY[..n]
+}
+
+/// Offset of a serial header with a valid message header starting inside the
+/// first `end` bytes of `candidate` (after the candidate's own serial header)
+fn find_nested_frame(candidate: &[u8], end: usize) -> Option<usize> {
+    let mut from = 1;
+    while let Some(offset) = find_serial_header(&candidate[from..]) {
+        let start = from + offset;
+        if start >= end {
+            break;
+        }
+        if check_message_header(&candidate[start + DLT_SERIAL_HEADER_SIZE..]).is_ok() {
+            return Some(start);
+        }
+        from = start + 1;
+    }
+    None
+}
+
+/// Offset of a complete, parseable frame starting inside `candidate` (after the
+/// candidate's own serial header)
+fn find_complete_nested_frame(candidate: &[u8]) -> Option<usize> {
+    let mut from = 1;
+    while let Some(offset) = find_serial_header(&candidate[from..]) {
+        let start = from + offset;
+        if let Ok(len) = check_message_header(&candidate[start + DLT_SERIAL_HEADER_SIZE..])
+            && let Some(frame) = candidate.get(start..start + DLT_SERIAL_HEADER_SIZE + len)
+            && DltHeaderParser::new(frame).parse_message().is_ok()
+        {
+            return Some(start);
+        }
+        from = start + 1;
+    }
+    None
+}
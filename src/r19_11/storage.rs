@@ -0,0 +1,245 @@
+//! # DLT Storage Header and File Reader
+//!
+//! DLT log files (`.dlt`, as written by dlt-daemon and dlt-viewer) prefix every
+//! message with a 16-byte storage header that records when the message was stored:
+//!
+//! ```text
+//! Bytes 0-3:   Pattern "DLT" + 0x01
+//! Bytes 4-7:   Seconds since 1970-01-01 (u32, little-endian)
+//! Bytes 8-11:  Microseconds (i32, little-endian)
+//! Bytes 12-15: ECU ID
+//! ```
+//!
+//! The storage header is followed by a normal DLT message (standard header,
+//! optional extra fields, optional extended header, payload).
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//!
+//! let file: &[u8] = &[/* contents of a .dlt file */];
+//!
+//! for record in DltFileReader::new(file) {
+//!     match record {
+//!         Ok(stored) => {
+//!             println!(
+//!                 "{}.{:06} ECU: {:?}",
+//!                 stored.storage_header.seconds,
+//!                 stored.storage_header.microseconds,
+//!                 stored.storage_header.ecu_id,
+//!             );
+//!         }
+//!         Err(e) => eprintln!("Skipping corrupt record: {:?}", e),
+//!     }
+//! }
+//! ```
+
+use crate::r19_11::*;
+
+// ========================================
+// Storage Header Constants
+// ========================================
+
+/// Storage header pattern: "DLT" + 0x01
+pub const DLT_STORAGE_HEADER_ARRAY: [u8; 4] = [0x44, 0x4C, 0x54, 0x01];
+
+// ========================================
+// Storage Header
+// ========================================
+
+/// Storage header preceding each message in a DLT file (16 bytes)
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub struct DltStorageHeader {
+    /// Seconds since 1970-01-01 when the message was stored
+    pub seconds: u32,
+    /// Microseconds part of the storage time
+    pub microseconds: i32,
+    /// ECU ID of the storing device
+    pub ecu_id: [u8; DLT_ID_SIZE],
+}
+
+impl DltStorageHeader {
+    /// Create a storage header from a time and ECU ID
+    pub fn new(seconds: u32, microseconds: i32, ecu_id: &[u8; DLT_ID_SIZE]) -> Self {
+        Self {
+            seconds,
+            microseconds,
+            ecu_id: *ecu_id,
+        }
+    }
+
+    /// Parse a storage header from the start of `data`
+    ///
+    /// # Returns
+    /// - `Ok(DltStorageHeader)`: Successfully parsed header
+    /// - `Err(DltHeaderError::BufferTooSmall)`: Less than 16 bytes available
+    /// - `Err(DltHeaderError::InvalidStorageHeader)`: Pattern doesn't match "DLT\x01"
+    pub fn parse(data: &[u8]) -> Result<Self, DltHeaderError> {
+        if data.len() < DLT_STORAGE_HEADER_SIZE {
+            return Err(DltHeaderError::BufferTooSmall);
+        }
+        if data[0..4] != DLT_STORAGE_HEADER_ARRAY {
+            return Err(DltHeaderError::InvalidStorageHeader);
+        }
+
+        let seconds = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+        let microseconds = i32::from_le_bytes([data[8], data[9], data[10], data[11]]);
+        let mut ecu_id = [0u8; DLT_ID_SIZE];
+        ecu_id.copy_from_slice(&data[12..16]);
+
+        Ok(Self {
+            seconds,
+            microseconds,
+            ecu_id,
+        })
+    }
+
+    /// Write the storage header into the start of `buffer`
+    ///
+    /// # Returns
+    /// - `Ok(usize)`: Number of bytes written (always 16)
+    /// - `Err(DltError::BufferTooSmall)`: Buffer shorter than 16 bytes
+    pub fn write(&self, buffer: &mut [u8]) -> Result<usize, DltError> {
+        if buffer.len() < DLT_STORAGE_HEADER_SIZE {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        buffer[0..4].copy_from_slice(&DLT_STORAGE_HEADER_ARRAY);
+        buffer[4..8].copy_from_slice(&self.seconds.to_le_bytes());
+        buffer[8..12].copy_from_slice(&self.microseconds.to_le_bytes());
+        buffer[12..16].copy_from_slice(&self.ecu_id);
+
+        Ok(DLT_STORAGE_HEADER_SIZE)
+    }
+
+    /// Storage time in microseconds since 1970-01-01
+    pub fn timestamp_us(&self) -> i64 {
+        self.seconds as i64 * 1_000_000 + self.microseconds as i64
+    }
+}
+
+// ========================================
+// Stored Message
+// ========================================
+
+/// A message read from a DLT file together with its storage header
+#[derive(Debug, Clone, Copy)]
+pub struct DltStoredMessage<'a> {
+    /// Storage header preceding the message
+    pub storage_header: DltStorageHeader,
+    /// Parsed DLT message
+    pub message: DltMessage<'a>,
+}
+
+// ========================================
+// DLT File Reader
+// ========================================
+
+/// Reader for the contents of a DLT file
+///
+/// Iterates over storage-header-prefixed messages. When a record is corrupt
+/// (bad pattern, invalid header, truncated message) an error is yielded once and
+/// the reader resynchronizes on the next "DLT\x01" pattern.
+///
+/// # Example
+/// ```no_run
+/// use dlt_protocol::r19_11::*;
+///
+/// let file: &[u8] = &[/* contents of a .dlt file */];
+/// let mut reader = DltFileReader::new(file);
+///
+/// while let Some(record) = reader.next() {
+///     if let Ok(stored) = record {
+///         println!("Payload: {:?}", stored.message.payload);
+///     }
+/// }
+/// println!("Skipped {} bytes", reader.skipped_bytes());
+/// ```
+pub struct DltFileReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    skipped: usize,
+}
+
+impl<'a> DltFileReader<'a> {
+    /// Create a new reader over the contents of a DLT file
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            position: 0,
+            skipped: 0,
+        }
+    }
+
+    /// Read the next stored message
+    ///
+    /// # Returns
+    /// - `Some(Ok(DltStoredMessage))`: Successfully read record
+    /// - `Some(Err(DltHeaderError))`: Corrupt record (the reader has already resynchronized)
+    /// - `None`: End of data
+    pub fn read_message(&mut self) -> Option<Result<DltStoredMessage<'a>, DltHeaderError>> {
+        if self.position >= self.data.len() {
+            return None;
+        }
+
+        let record_start = self.position;
+        match self.parse_record() {
+            Ok(stored) => Some(Ok(stored)),
+            Err(e) => {
+                self.resync(record_start + 1);
+                Some(Err(e))
+            }
+        }
+    }
+
+    /// Parse the storage header and message at the current position
+    fn parse_record(&mut self) -> Result<DltStoredMessage<'a>, DltHeaderError> {
+        let data = &self.data[self.position..];
+        let storage_header = DltStorageHeader::parse(data)?;
+
+        let mut parser = DltHeaderParser::new(&data[DLT_STORAGE_HEADER_SIZE..]);
+        let message = parser.parse_message()?;
+        self.position += DLT_STORAGE_HEADER_SIZE + parser.position();
+
+        Ok(DltStoredMessage {
+            storage_header,
+            message,
+        })
+    }
+
+    /// Move to the next storage header pattern at or after `from`
+    fn resync(&mut self, from: usize) {
+        let next = self.data[from.min(self.data.len())..]
+            .windows(DLT_STORAGE_HEADER_ARRAY.len())
+            .position(|window| window == DLT_STORAGE_HEADER_ARRAY)
+            .map(|offset| from + offset)
+            .unwrap_or(self.data.len());
+
+        self.skipped += next - self.position;
+        self.position = next;
+    }
+
+    /// Get current reading position
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get remaining unread data
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.position)
+    }
+
+    /// Number of bytes skipped while resynchronizing after corrupt records
+    pub fn skipped_bytes(&self) -> usize {
+        self.skipped
+    }
+}
+
+impl<'a> Iterator for DltFileReader<'a> {
+    type Item = Result<DltStoredMessage<'a>, DltHeaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_message()
+    }
+}
@@ -0,0 +1,259 @@
+//! # Incremental Stream Decoding
+//!
+//! TCP and serial connections deliver data in arbitrary chunks: a read may end
+//! in the middle of a message or contain several messages at once.
+//! [`DltStreamDecoder`] buffers incoming chunks in a fixed-capacity buffer
+//! (no allocation) and hands out complete messages as soon as they are available.
+//!
+//! Messages may optionally be prefixed with a serial header ("DLS\x01"). When a
+//! header is invalid, the error is reported once and the decoder skips bytes
+//! until it finds a plausible header again (only at serial headers if the
+//! stream uses them). Messages larger than the buffer are reported and skipped.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//! use std::io::Read;
+//! use std::net::TcpStream;
+//!
+//! let mut stream = TcpStream::connect("localhost:3490").unwrap();
+//! let mut decoder = DltStreamDecoder::<4096>::new();
+//!
+//! loop {
+//!     let read = stream.read(decoder.read_buffer()).unwrap();
+//!     if read == 0 {
+//!         break;
+//!     }
+//!     decoder.commit(read);
+//!
+//!     loop {
+//!         match decoder.next_message() {
+//!             Ok(Some(message)) => println!("Payload: {:?}", message.payload),
+//!             Ok(None) => break, // need more data
+//!             Err(e) => eprintln!("Stream error: {:?}", e),
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::r19_11::*;
+
+// ========================================
+// Stream Error Type
+// ========================================
+
+/// Error while decoding a message stream
+///
+/// The decoder recovers from all errors; decoding can simply continue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DltStreamError {
+    /// Invalid message header, the decoder resynchronizes
+    InvalidHeader(DltHeaderError),
+    /// The message (with serial header) is larger than the buffer and is skipped
+    MessageTooLarge(usize),
+}
+
+// ========================================
+// Frame Size Detection
+// ========================================
+
+/// Size of the message at the start of `data`, including a serial header
+///
+/// `serial_stream` is whether the stream is known to use serial headers; if so,
+/// a message without one is rejected with `InvalidSerialHeader`.
+pub(crate) fn frame_size(data: &[u8], serial_stream: Option<bool>) -> Result<usize, DltHeaderError> {
+    if data.starts_with(&DLT_SERIAL_HEADER_ARRAY) {
+        check_message_header(&data[DLT_SERIAL_HEADER_SIZE..]).map(|len| DLT_SERIAL_HEADER_SIZE + len)
+    } else if DLT_SERIAL_HEADER_ARRAY.starts_with(data) {
+        // Possibly the start of a serial header
+        Err(DltHeaderError::BufferTooSmall)
+    } else if serial_stream == Some(true) {
+        Err(DltHeaderError::InvalidSerialHeader)
+    } else {
+        check_message_header(data)
+    }
+}
+
+// ========================================
+// Stream Decoder
+// ========================================
+
+/// Push-based decoder for DLT v1 messages arriving in arbitrary chunks
+///
+/// `N` is the buffer capacity and must be at least [`DLT_STREAM_MIN_CAPACITY`].
+/// Data is added with [`DltStreamDecoder::push`] or, to read directly into the
+/// decoder, [`DltStreamDecoder::read_buffer`] + [`DltStreamDecoder::commit`].
+pub struct DltStreamDecoder<const N: usize> {
+    buffer: [u8; N],
+    /// Start of unconsumed data
+    start: usize,
+    /// End of buffered data
+    end: usize,
+    /// Bytes of an oversized message still to be dropped
+    discard: usize,
+    /// Skipping bytes after an invalid header
+    resyncing: bool,
+    /// Whether the stream uses serial headers (learned from the first message)
+    serial: Option<bool>,
+    /// Total bytes skipped while resynchronizing
+    skipped: usize,
+}
+
+/// Smallest buffer capacity: serial header + all headers of a message
+pub const DLT_STREAM_MIN_CAPACITY: usize = DLT_SERIAL_HEADER_SIZE
+    + DLT_STANDARD_HEADER_SIZE
+    + DLT_STANDARD_HEADER_EXTRA_SIZE
+    + DLT_EXTENDED_HEADER_SIZE;
+
+impl<const N: usize> Default for DltStreamDecoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> DltStreamDecoder<N> {
+    /// Create an empty decoder
+    pub const fn new() -> Self {
+        const { assert!(N >= DLT_STREAM_MIN_CAPACITY, "stream buffer too small") };
+        Self {
+            buffer: [0; N],
+            start: 0,
+            end: 0,
+            discard: 0,
+            resyncing: false,
+            serial: None,
+            skipped: 0,
+        }
+    }
+
+    // ========================================
+    // Feeding Data
+    // ========================================
+
+    /// Append received bytes
+    ///
+    /// # Returns
+    /// Number of bytes accepted. Fewer than `data.len()` bytes are accepted when
+    /// the buffer is full; take messages out with [`DltStreamDecoder::next_message`]
+    /// and push the rest afterwards.
+    pub fn push(&mut self, data: &[u8]) -> usize {
+        let space = self.read_buffer();
+        let count = data.len().min(space.len());
+        space[..count].copy_from_slice(&data[..count]);
+        self.commit(count);
+        count
+    }
+
+    /// Free space at the end of the buffer to read into
+    ///
+    /// Call [`DltStreamDecoder::commit`] with the number of bytes written.
+    pub fn read_buffer(&mut self) -> &mut [u8] {
+        if self.start > 0 {
+            self.buffer.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+        }
+        &mut self.buffer[self.end..]
+    }
+
+    /// Mark `count` bytes written into [`DltStreamDecoder::read_buffer`] as received
+    pub fn commit(&mut self, count: usize) {
+        self.end = (self.end + count).min(N);
+
+        // Drop the remainder of an oversized message as it arrives
+        let dropped = self.discard.min(self.end - self.start);
+        self.start += dropped;
+        self.discard -= dropped;
+    }
+
+    // ========================================
+    // Taking Messages
+    // ========================================
+
+    /// Take the next complete message
+    ///
+    /// # Returns
+    /// - `Ok(Some(DltMessage))`: Complete message
+    /// - `Ok(None)`: More data needed
+    /// - `Err(DltStreamError)`: Invalid data was found and is being skipped
+    pub fn next_message(&mut self) -> Result<Option<DltMessage<'_>>, DltStreamError> {
+        match self.next_frame()? {
+            Some(frame) => DltHeaderParser::new(frame)
+                .parse_message()
+                .map(Some)
+                .map_err(DltStreamError::InvalidHeader),
+            None => Ok(None),
+        }
+    }
+
+    /// Take the bytes of the next complete message (including a serial header)
+    ///
+    /// Same as [`DltStreamDecoder::next_message`], without parsing the message.
+    pub fn next_frame(&mut self) -> Result<Option<&[u8]>, DltStreamError> {
+        loop {
+            let data = &self.buffer[self.start..self.end];
+            if data.is_empty() {
+                return Ok(None);
+            }
+
+            let serial = data.starts_with(&DLT_SERIAL_HEADER_ARRAY);
+            match frame_size(data, self.serial) {
+                Ok(size) => {
+                    if size > N {
+                        self.resyncing = false;
+                        self.discard = size;
+                        self.commit(0);
+                        return Err(DltStreamError::MessageTooLarge(size));
+                    }
+                    if data.len() < size {
+                        return Ok(None);
+                    }
+
+                    let frame_start = self.start;
+                    self.start += size;
+                    self.resyncing = false;
+                    self.serial.get_or_insert(serial);
+                    return Ok(Some(&self.buffer[frame_start..frame_start + size]));
+                }
+                Err(DltHeaderError::BufferTooSmall) => return Ok(None),
+                Err(e) => {
+                    self.start += 1;
+                    self.skipped += 1;
+                    if !self.resyncing {
+                        self.resyncing = true;
+                        return Err(DltStreamError::InvalidHeader(e));
+                    }
+                }
+            }
+        }
+    }
+
+    // ========================================
+    // Inspection
+    // ========================================
+
+    /// Number of buffered bytes not yet returned as messages
+    pub fn buffered(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Buffer capacity
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Total number of bytes skipped while resynchronizing
+    pub fn skipped_bytes(&self) -> usize {
+        self.skipped
+    }
+
+    /// Discard all buffered data (e.g. after reconnecting)
+    pub fn reset(&mut self) {
+        self.start = 0;
+        self.end = 0;
+        self.discard = 0;
+        self.resyncing = false;
+        self.serial = None;
+    }
+}
@@ -0,0 +1,187 @@
+//! # Time Correlation (requires `std` feature)
+//!
+//! The standard header timestamp is the ECU uptime in 0.1ms units. A
+//! SyncTimeStamp response (0x24) carries the absolute time of the ECU's time
+//! base, and its own header timestamp is the uptime at which that time was
+//! taken. [`DltTimeCorrelator`] keeps one such reference point per ECU ID and
+//! session ID and maps the uptime of subsequent messages onto wall-clock time.
+//!
+//! Messages whose session has no reference of its own use the latest
+//! reference received from the same ECU, since all sessions of an ECU share
+//! its uptime clock.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//!
+//! let mut correlator = DltTimeCorrelator::new();
+//!
+//! let stream: &[u8] = &[/* received DLT messages */];
+//! let mut parser = DltHeaderParser::new(stream);
+//! while let Ok(message) = parser.parse_message() {
+//!     if correlator.update(&message) {
+//!         continue; // SyncTimeStamp response
+//!     }
+//!     match correlator.absolute_time(&message) {
+//!         Some(time) => println!("{}.{:09}", time.as_secs(), time.subsec_nanos()),
+//!         None => println!("no time reference yet"),
+//!     }
+//! }
+//! ```
+
+use crate::r19_11::*;
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+// ========================================
+// Time Reference
+// ========================================
+
+/// Correlation point between ECU uptime and absolute time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DltTimeReference {
+    /// Uptime in 0.1ms units (header timestamp of the sync response)
+    pub uptime: u32,
+    /// Absolute time since 1970-01-01 at `uptime`
+    pub time: Duration,
+    /// Time base status of the sync response (`DLT_TIME_BASE_*`)
+    pub time_base_status: u8,
+}
+
+impl DltTimeReference {
+    /// Create a reference from a SyncTimeStamp response
+    pub fn new(uptime: u32, time_stamp: &DltSyncTimeStamp) -> Self {
+        Self {
+            uptime,
+            time: Duration::new(time_stamp.seconds, time_stamp.nanoseconds),
+            time_base_status: time_stamp.time_base_status,
+        }
+    }
+
+    /// Absolute time at `uptime` (0.1ms units)
+    ///
+    /// The uptime difference is taken modulo 2^32, so uptimes within about
+    /// 2.5 days before or after the reference are mapped correctly even across
+    /// a wrap of the timestamp field.
+    ///
+    /// # Returns
+    /// - `Some(Duration)`: Time since 1970-01-01
+    /// - `None`: Result before 1970-01-01
+    pub fn absolute_time(&self, uptime: u32) -> Option<Duration> {
+        let delta = uptime.wrapping_sub(self.uptime) as i32;
+        let offset = Duration::from_micros(delta.unsigned_abs() as u64 * 100);
+        if delta >= 0 {
+            self.time.checked_add(offset)
+        } else {
+            self.time.checked_sub(offset)
+        }
+    }
+}
+
+// ========================================
+// Time Correlator
+// ========================================
+
+/// Maps message uptimes to absolute time using SyncTimeStamp responses
+#[derive(Debug, Clone, Default)]
+pub struct DltTimeCorrelator {
+    /// Keyed by (ECU ID, session ID); `None` session is the ECU-wide reference
+    references: HashMap<(Option<[u8; DLT_ID_SIZE]>, Option<u32>), DltTimeReference>,
+}
+
+impl DltTimeCorrelator {
+    /// Create a correlator without references
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the reference of a SyncTimeStamp response
+    ///
+    /// Other messages, responses with a status other than `Ok` and responses
+    /// without a header timestamp are ignored.
+    ///
+    /// # Returns
+    /// - `true`: The message was a SyncTimeStamp response and a reference was recorded
+    /// - `false`: The message was ignored
+    pub fn update(&mut self, message: &DltMessage<'_>) -> bool {
+        let Some(uptime) = message.timestamp else {
+            return false;
+        };
+        let Some(time_stamp) = parse_sync_time_stamp(message) else {
+            return false;
+        };
+
+        self.set_reference(
+            message.ecu_id,
+            message.session_id,
+            DltTimeReference::new(uptime, &time_stamp),
+        );
+        true
+    }
+
+    /// Set the reference for an ECU and session, e.g. from an external time source
+    ///
+    /// The reference also becomes the ECU-wide fallback for sessions without
+    /// a reference of their own.
+    pub fn set_reference(
+        &mut self,
+        ecu_id: Option<[u8; DLT_ID_SIZE]>,
+        session_id: Option<u32>,
+        reference: DltTimeReference,
+    ) {
+        self.references.insert((ecu_id, session_id), reference);
+        self.references.insert((ecu_id, None), reference);
+    }
+
+    /// Reference for an ECU and session, falling back to the ECU-wide reference
+    pub fn reference(
+        &self,
+        ecu_id: Option<[u8; DLT_ID_SIZE]>,
+        session_id: Option<u32>,
+    ) -> Option<&DltTimeReference> {
+        self.references
+            .get(&(ecu_id, session_id))
+            .or_else(|| self.references.get(&(ecu_id, None)))
+    }
+
+    /// Absolute time of a message
+    ///
+    /// # Returns
+    /// - `Some(Duration)`: Time since 1970-01-01
+    /// - `None`: No header timestamp, no reference for the message's ECU, or
+    ///   a result before 1970-01-01
+    pub fn absolute_time(&self, message: &DltMessage<'_>) -> Option<Duration> {
+        let uptime = message.timestamp?;
+        self.reference(message.ecu_id, message.session_id)?
+            .absolute_time(uptime)
+    }
+
+    /// Remove all references
+    pub fn clear(&mut self) {
+        self.references.clear();
+    }
+}
+
+/// Time stamp of a successful SyncTimeStamp response message
+fn parse_sync_time_stamp(message: &DltMessage<'_>) -> Option<DltSyncTimeStamp> {
+    let msin = message.extended_header?.msin;
+    if !matches!(MstpType::parse(extract_msin_mstp(msin)), MstpType::DltTypeControl)
+        || !matches!(
+            MtinTypeDltControl::parse(extract_msin_mtin(msin)),
+            MtinTypeDltControl::DltControlResponse
+        )
+    {
+        return None;
+    }
+
+    let parser = DltServiceParser::new(message.payload);
+    if !matches!(parser.parse_service_id(), Ok(ServiceId::SyncTimeStamp)) {
+        return None;
+    }
+    match parser.parse_sync_time_stamp_response() {
+        Ok((ServiceStatus::Ok, time_stamp)) => Some(time_stamp),
+        _ => None,
+    }
+}
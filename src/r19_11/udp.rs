@@ -0,0 +1,293 @@
+//! # DLT over UDP (requires `std` feature)
+//!
+//! dlt-daemon can forward messages via UDP (usually multicast), and some ECUs
+//! send DLT over UDP directly. A datagram carries one or more complete
+//! messages back to back, each optionally prefixed with a serial header.
+//!
+//! - [`DltUdpSender`] sends built messages, one message per datagram, to a
+//!   unicast or multicast address.
+//! - [`DltUdpReceiver`] receives datagrams; [`DltDatagram::messages`] parses the
+//!   messages of a datagram. A datagram that ends inside a message is reported
+//!   as [`DltDatagramError::Truncated`], separately from invalid headers.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r19_11::*;
+//! use std::net::Ipv4Addr;
+//!
+//! let group = Ipv4Addr::new(239, 255, 42, 99);
+//! let mut receiver = DltUdpReceiver::join_multicast(group, 3490, Ipv4Addr::UNSPECIFIED).unwrap();
+//!
+//! loop {
+//!     let datagram = receiver.recv().unwrap();
+//!     for message in datagram.messages() {
+//!         match message {
+//!             Ok(message) => println!("{:?}: {:?}", datagram.source(), message.payload),
+//!             Err(DltDatagramError::Truncated { offset }) => {
+//!                 eprintln!("Datagram truncated at byte {}", offset)
+//!             }
+//!             Err(e) => eprintln!("Invalid datagram: {}", e),
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::r19_11::*;
+
+use std::fmt;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, ToSocketAddrs, UdpSocket};
+
+use socket2::{Domain, Protocol, Socket, Type};
+
+/// Receive buffer size: the largest possible UDP payload
+const DLT_UDP_MAX_DATAGRAM: usize = 65536;
+
+// ========================================
+// Datagram Error Type
+// ========================================
+
+/// Error while splitting a datagram into messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DltDatagramError {
+    /// The datagram ends inside the message starting at `offset`
+    Truncated { offset: usize },
+    /// The message at `offset` has an invalid header
+    InvalidHeader { offset: usize, error: DltHeaderError },
+}
+
+impl fmt::Display for DltDatagramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DltDatagramError::Truncated { offset } => {
+                write!(f, "datagram truncated inside message at byte {}", offset)
+            }
+            DltDatagramError::InvalidHeader { offset, error } => {
+                write!(f, "invalid message header at byte {}: {:?}", offset, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DltDatagramError {}
+
+// ========================================
+// Datagram
+// ========================================
+
+/// A received datagram holding one or more DLT messages
+#[derive(Debug, Clone, Copy)]
+pub struct DltDatagram<'a> {
+    data: &'a [u8],
+    source: Option<SocketAddr>,
+}
+
+impl<'a> DltDatagram<'a> {
+    /// Wrap datagram payload received from `source` (e.g. read from a capture)
+    pub fn new(data: &'a [u8], source: Option<SocketAddr>) -> Self {
+        Self { data, source }
+    }
+
+    /// Sender address, if known
+    pub fn source(&self) -> Option<SocketAddr> {
+        self.source
+    }
+
+    /// Raw datagram payload
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Iterate over the messages in the datagram
+    ///
+    /// Iteration stops after the first error.
+    pub fn messages(&self) -> DltDatagramMessages<'a> {
+        DltDatagramMessages {
+            data: self.data,
+            position: 0,
+        }
+    }
+
+    /// Whether the datagram ends inside a message
+    pub fn is_truncated(&self) -> bool {
+        self.messages()
+            .any(|message| matches!(message, Err(DltDatagramError::Truncated { .. })))
+    }
+}
+
+/// Iterator over the messages of a datagram
+pub struct DltDatagramMessages<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> DltDatagramMessages<'a> {
+    /// Bytes of the next message (including a serial header), without parsing it
+    pub fn next_frame(&mut self) -> Option<Result<&'a [u8], DltDatagramError>> {
+        let offset = self.position;
+        let rest = self.data.get(offset..).filter(|rest| !rest.is_empty())?;
+
+        let result = match frame_size(rest, None) {
+            Ok(size) if size <= rest.len() => {
+                self.position += size;
+                Ok(&rest[..size])
+            }
+            Ok(_) | Err(DltHeaderError::BufferTooSmall) => Err(DltDatagramError::Truncated { offset }),
+            Err(error) => Err(DltDatagramError::InvalidHeader { offset, error }),
+        };
+        if result.is_err() {
+            self.position = self.data.len();
+        }
+        Some(result)
+    }
+}
+
+impl<'a> Iterator for DltDatagramMessages<'a> {
+    type Item = Result<DltMessage<'a>, DltDatagramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.position;
+        let result = self.next_frame()?.and_then(|frame| {
+            DltHeaderParser::new(frame)
+                .parse_message()
+                .map_err(|error| DltDatagramError::InvalidHeader { offset, error })
+        });
+        if result.is_err() {
+            self.position = self.data.len();
+        }
+        Some(result)
+    }
+}
+
+// ========================================
+// UDP Sender
+// ========================================
+
+/// Sends DLT messages over UDP, one message per datagram
+pub struct DltUdpSender {
+    socket: UdpSocket,
+    target: SocketAddr,
+}
+
+impl DltUdpSender {
+    /// Create a sender for a unicast target, bound to an ephemeral local port
+    pub fn new<A: ToSocketAddrs>(target: A) -> io::Result<Self> {
+        let target = target
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+        let local: SocketAddr = match target {
+            SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            SocketAddr::V6(_) => (std::net::Ipv6Addr::UNSPECIFIED, 0).into(),
+        };
+        Ok(Self {
+            socket: UdpSocket::bind(local)?,
+            target,
+        })
+    }
+
+    /// Create a sender for an IPv4 multicast group
+    ///
+    /// `interface` is the address of the outgoing interface, set with
+    /// `IP_MULTICAST_IF` (use `Ipv4Addr::UNSPECIFIED` for the default route).
+    /// `ttl` limits how many routers the datagrams may cross (1 = local network).
+    pub fn multicast(group: SocketAddrV4, interface: Ipv4Addr, ttl: u32) -> io::Result<Self> {
+        if !group.ip().is_multicast() {
+            return Err(io::Error::from(io::ErrorKind::InvalidInput));
+        }
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_multicast_if_v4(&interface)?;
+        socket.set_multicast_ttl_v4(ttl)?;
+        socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)).into())?;
+        Ok(Self {
+            socket: socket.into(),
+            target: group.into(),
+        })
+    }
+
+    /// Use an already configured socket
+    pub fn with_socket(socket: UdpSocket, target: SocketAddr) -> Self {
+        Self { socket, target }
+    }
+
+    /// Send one complete message (as produced by `DltMessageBuilder`)
+    pub fn send(&self, message: &[u8]) -> io::Result<usize> {
+        self.socket.send_to(message, self.target)
+    }
+
+    /// Target address
+    pub fn target(&self) -> SocketAddr {
+        self.target
+    }
+
+    /// Underlying socket (e.g. to set multicast loopback)
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+}
+
+// ========================================
+// UDP Receiver
+// ========================================
+
+/// Receives datagrams containing DLT messages
+pub struct DltUdpReceiver {
+    socket: UdpSocket,
+    buffer: Vec<u8>,
+    datagrams: u64,
+    truncated: u64,
+}
+
+impl DltUdpReceiver {
+    /// Bind a receiver to a local address
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        Ok(Self::with_socket(UdpSocket::bind(addr)?))
+    }
+
+    /// Receive from an IPv4 multicast group on `port`
+    ///
+    /// `interface` selects the interface to join on (`Ipv4Addr::UNSPECIFIED`
+    /// lets the system choose).
+    pub fn join_multicast(group: Ipv4Addr, port: u16, interface: Ipv4Addr) -> io::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?;
+        socket.join_multicast_v4(&group, &interface)?;
+        Ok(Self::with_socket(socket))
+    }
+
+    /// Use an already configured socket
+    pub fn with_socket(socket: UdpSocket) -> Self {
+        Self {
+            socket,
+            buffer: vec![0; DLT_UDP_MAX_DATAGRAM],
+            datagrams: 0,
+            truncated: 0,
+        }
+    }
+
+    /// Receive the next datagram (blocks unless a timeout is set on the socket)
+    pub fn recv(&mut self) -> io::Result<DltDatagram<'_>> {
+        let (size, source) = self.socket.recv_from(&mut self.buffer)?;
+        let datagram = DltDatagram::new(&self.buffer[..size], Some(source));
+        self.datagrams += 1;
+        if datagram.is_truncated() {
+            self.truncated += 1;
+        }
+        Ok(datagram)
+    }
+
+    /// Underlying socket (e.g. to set a read timeout)
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Number of datagrams received
+    pub fn datagram_count(&self) -> u64 {
+        self.datagrams
+    }
+
+    /// Number of received datagrams that ended inside a message
+    pub fn truncated_count(&self) -> u64 {
+        self.truncated
+    }
+}
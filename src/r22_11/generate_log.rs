@@ -0,0 +1,658 @@
+//! # DLT v2 Message Generator
+//!
+//! This module provides the builder pattern API for creating DLT v2 protocol messages
+//! according to the AUTOSAR DLT specification release 22.11.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r22_11::*;
+//!
+//! let mut builder = DltMessageBuilder::new()
+//!     .with_ecu_id(b"ECU1")
+//!     .with_app_id(b"APP1")
+//!     .with_context_id(b"CTX1")
+//!     .with_timestamp(1_700_000_000, 500);
+//!
+//! let mut buffer = [0u8; 256];
+//! let size = builder.generate_log_message_with_payload(
+//!     &mut buffer,
+//!     b"Hello, DLT v2!",
+//!     MtinTypeDltLog::DltLogInfo,
+//!     1,
+//!     true,
+//! ).unwrap();
+//! ```
+
+use crate::r19_11::{DltError, PayloadBuilder};
+use crate::r22_11::*;
+
+// ========================================
+// DLT v2 Message Builder
+// ========================================
+
+/// Builder for creating DLT v2 protocol messages
+///
+/// Optional extension header fields are enabled by calling the matching `with_*`
+/// method; the HTYP2 flags are derived from the configured fields.
+///
+/// # Example
+/// ```no_run
+/// use dlt_protocol::r22_11::*;
+///
+/// let tags: [&[u8]; 2] = [b"power", b"boot"];
+/// let mut builder = DltMessageBuilder::new()
+///     .with_ecu_id(b"GATEWAY")
+///     .with_app_id(b"PWR")
+///     .with_context_id(b"MAIN")
+///     .with_file_name(b"power.c", 120)
+///     .with_tags(&tags);
+///
+/// let mut buffer = [0u8; 256];
+/// let size = builder.generate_log_message_with_payload(
+///     &mut buffer,
+///     b"Power up",
+///     MtinTypeDltLog::DltLogInfo,
+///     1,
+///     true,
+/// ).unwrap();
+/// ```
+pub struct DltMessageBuilder<'a> {
+    /// Message counter (auto-incremented, wraps at 255)
+    message_counter: u8,
+    /// Whether to include serial header ("DLS\x01")
+    serial_header: bool,
+    /// ECU ID (1-255 bytes, WEID)
+    ecu_id: Option<&'a [u8]>,
+    /// Application ID (1-255 bytes, WACID)
+    app_id: &'a [u8],
+    /// Context ID (1-255 bytes, WACID)
+    context_id: &'a [u8],
+    /// Whether to include App ID and Context ID
+    with_app_context: bool,
+    /// Session ID value (WSID)
+    session_id: Option<u32>,
+    /// Timestamp value (TMSP2)
+    pub timestamp: DltTimestamp2,
+    /// Source file name and line number (WSFLN)
+    file_name: Option<(&'a [u8], u32)>,
+    /// Tags (WTGS)
+    tags: Option<&'a [&'a [u8]]>,
+    /// Privacy level (WPVL)
+    privacy_level: Option<u8>,
+    /// Segmentation information (WSGM)
+    segmentation: Option<DltSegmentation>,
+}
+
+impl<'a> Default for DltMessageBuilder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> DltMessageBuilder<'a> {
+    /// Create a new message builder with default settings
+    ///
+    /// Default configuration:
+    /// - ECU ID "ECU" (WEID)
+    /// - App ID "APP" and Context ID "CTX" (WACID)
+    /// - No session ID, file name, tags, privacy level or segmentation
+    /// - Timestamp 0
+    /// - Message counter starts at 0
+    pub fn new() -> Self {
+        Self {
+            message_counter: 0,
+            serial_header: false,
+            ecu_id: Some(b"ECU"),
+            app_id: b"APP",
+            context_id: b"CTX",
+            with_app_context: true,
+            session_id: None,
+            timestamp: DltTimestamp2::default(),
+            file_name: None,
+            tags: None,
+            privacy_level: None,
+            segmentation: None,
+        }
+    }
+
+    // ========================================
+    // Configuration Methods (Builder Pattern)
+    // ========================================
+
+    /// Set message counter to a specific value
+    pub fn msg_counter(mut self, msg_cnt: u8) -> Self {
+        self.message_counter = msg_cnt;
+        self
+    }
+
+    /// Set ECU ID (1-255 bytes, e.g., b"ECU1")
+    pub fn with_ecu_id(mut self, ecu_id: &'a [u8]) -> Self {
+        self.ecu_id = Some(ecu_id);
+        self
+    }
+
+    /// Omit the ECU ID (clears WEID)
+    pub fn without_ecu_id(mut self) -> Self {
+        self.ecu_id = None;
+        self
+    }
+
+    /// Set Application ID (1-255 bytes)
+    pub fn with_app_id(mut self, app_id: &'a [u8]) -> Self {
+        self.app_id = app_id;
+        self.with_app_context = true;
+        self
+    }
+
+    /// Set Context ID (1-255 bytes)
+    pub fn with_context_id(mut self, ctx_id: &'a [u8]) -> Self {
+        self.context_id = ctx_id;
+        self.with_app_context = true;
+        self
+    }
+
+    /// Omit App ID and Context ID (clears WACID)
+    pub fn without_app_context_id(mut self) -> Self {
+        self.with_app_context = false;
+        self
+    }
+
+    /// Set session ID (sets WSID)
+    pub fn with_session_id(mut self, sess_id: u32) -> Self {
+        self.session_id = Some(sess_id);
+        self
+    }
+
+    /// Set timestamp (seconds and nanoseconds)
+    pub fn with_timestamp(mut self, seconds: u64, nanoseconds: u32) -> Self {
+        self.timestamp = DltTimestamp2 {
+            seconds,
+            nanoseconds,
+        };
+        self
+    }
+
+    /// Set source file name and line number (sets WSFLN)
+    pub fn with_file_name(mut self, file_name: &'a [u8], line_number: u32) -> Self {
+        self.file_name = Some((file_name, line_number));
+        self
+    }
+
+    /// Set tags (sets WTGS)
+    pub fn with_tags(mut self, tags: &'a [&'a [u8]]) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Set privacy level (sets WPVL)
+    pub fn with_privacy_level(mut self, level: u8) -> Self {
+        self.privacy_level = Some(level);
+        self
+    }
+
+    /// Enable serial header ("DLS\x01") at the beginning of messages
+    pub fn add_serial_header(mut self) -> Self {
+        self.serial_header = true;
+        self
+    }
+
+    /// Set segmentation information for the next messages (sets WSGM)
+    ///
+    /// Pass `None` to stop emitting the segmentation field.
+    pub fn set_segmentation(&mut self, segmentation: Option<DltSegmentation>) {
+        self.segmentation = segmentation;
+    }
+
+    /// Update the timestamp used for subsequent messages
+    pub fn set_timestamp(&mut self, seconds: u64, nanoseconds: u32) {
+        self.timestamp = DltTimestamp2 {
+            seconds,
+            nanoseconds,
+        };
+    }
+
+    // ========================================
+    // Message Counter Management
+    // ========================================
+
+    /// Increment the message counter (wraps at 255)
+    pub fn increment_counter(&mut self) {
+        self.message_counter = self.message_counter.wrapping_add(1);
+    }
+
+    /// Reset the message counter to 0
+    pub fn reset_counter(&mut self) {
+        self.message_counter = 0;
+    }
+
+    /// Get the current message counter value
+    pub fn get_counter(&self) -> u8 {
+        self.message_counter
+    }
+
+    // ========================================
+    // Message Generation - Public API
+    // ========================================
+
+    /// Insert DLT v2 header at the front of an existing verbose payload
+    ///
+    /// The payload is typically produced with `PayloadBuilder` at the start of the buffer.
+    /// It will be moved backward in the buffer to make room for the headers.
+    ///
+    /// # Returns
+    /// Total message size (headers + payload) on success
+    pub fn insert_header_at_front(
+        &mut self,
+        buffer: &mut [u8],
+        payload_size: usize,
+        arg_num: u8,
+        log_level: MtinTypeDltLog,
+    ) -> Result<usize, DltError> {
+        let serial_size = if self.serial_header { DLT_SERIAL_HEADER_SIZE } else { 0 };
+        let total_header_size = serial_size + self.header_size(ContentInfo::Verbose)?;
+        let total_size = total_header_size + payload_size;
+
+        if buffer.len() < total_size {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        buffer.copy_within(0..payload_size, total_header_size);
+
+        let msin = encode_msin(true, MstpType::DltTypeLog.to_bits(), log_level.to_bits());
+        self.write_header(buffer, ContentInfo::Verbose, msin, arg_num, 0, payload_size)?;
+
+        Ok(total_size)
+    }
+
+    /// Generate a complete DLT v2 log message with payload
+    ///
+    /// In verbose mode the payload bytes are encoded as a single string argument
+    /// (like the R19.11 builder). In non-verbose mode the payload is copied verbatim
+    /// and the message ID is set to 0; use `generate_non_verbose_message` to supply one.
+    ///
+    /// # Returns
+    /// Total message size on success
+    pub fn generate_log_message_with_payload(
+        &mut self,
+        buffer: &mut [u8],
+        payload: &[u8],
+        log_level: MtinTypeDltLog,
+        number_of_arguments: u8,
+        verbose: bool,
+    ) -> Result<usize, DltError> {
+        if !verbose {
+            return self.generate_non_verbose_message(buffer, 0, payload, log_level);
+        }
+
+        let serial_size = if self.serial_header { DLT_SERIAL_HEADER_SIZE } else { 0 };
+        let payload_offset = serial_size + self.header_size(ContentInfo::Verbose)?;
+        if payload_offset > buffer.len() {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let payload_str = core::str::from_utf8(payload).map_err(|_| DltError::InvalidParameter)?;
+        let payload_size = {
+            let mut payload_builder = PayloadBuilder::new(&mut buffer[payload_offset..]);
+            payload_builder
+                .add_string(payload_str)
+                .map_err(|_| DltError::BufferTooSmall)?;
+            payload_builder.len()
+        };
+
+        let msin = encode_msin(true, MstpType::DltTypeLog.to_bits(), log_level.to_bits());
+        self.write_header(buffer, ContentInfo::Verbose, msin, number_of_arguments, 0, payload_size)?;
+
+        Ok(payload_offset + payload_size)
+    }
+
+    /// Generate a non-verbose DLT v2 log message (CNTI = 1)
+    ///
+    /// # Arguments
+    /// * `buffer` - Destination buffer for the complete message
+    /// * `message_id` - Message ID (MSID) identifying the message description
+    /// * `payload` - Raw argument data, copied verbatim
+    /// * `log_level` - Log level for the message
+    pub fn generate_non_verbose_message(
+        &mut self,
+        buffer: &mut [u8],
+        message_id: u32,
+        payload: &[u8],
+        log_level: MtinTypeDltLog,
+    ) -> Result<usize, DltError> {
+        let msin = encode_msin(false, MstpType::DltTypeLog.to_bits(), log_level.to_bits());
+        self.generate_message(buffer, ContentInfo::NonVerbose, msin, 0, message_id, payload)
+    }
+
+    /// Generate a DLT v2 control message (CNTI = 2)
+    ///
+    /// # Arguments
+    /// * `buffer` - Destination buffer for the complete message
+    /// * `payload` - Service payload (service ID + parameters)
+    /// * `mtin` - Control request or response
+    pub fn generate_control_message(
+        &mut self,
+        buffer: &mut [u8],
+        payload: &[u8],
+        mtin: MtinTypeDltControl,
+    ) -> Result<usize, DltError> {
+        let msin = encode_msin(false, MstpType::DltTypeControl.to_bits(), mtin.to_bits());
+        self.generate_message(buffer, ContentInfo::Control, msin, 0, 0, payload)
+    }
+
+    /// Generate a DLT v2 application trace message (MSTP = AppTrace)
+    ///
+    /// The payload is copied verbatim. In non-verbose mode the message ID is set to 0.
+    pub fn generate_app_trace_message(
+        &mut self,
+        buffer: &mut [u8],
+        payload: &[u8],
+        trace_type: MtinTypeDltAppTrace,
+        number_of_arguments: u8,
+        verbose: bool,
+    ) -> Result<usize, DltError> {
+        let msin = encode_msin(verbose, MstpType::DltTypeAppTrace.to_bits(), trace_type.to_bits());
+        if verbose {
+            self.generate_message(buffer, ContentInfo::Verbose, msin, number_of_arguments, 0, payload)
+        } else {
+            self.generate_message(buffer, ContentInfo::NonVerbose, msin, 0, 0, payload)
+        }
+    }
+
+    /// Generate a DLT v2 network trace message (MSTP = NwTrace)
+    ///
+    /// The payload carries the bus-specific header and the frame data: as two raw
+    /// arguments without terminator in verbose mode, or each prefixed with a
+    /// 16-bit length in non-verbose mode (message ID 0).
+    pub fn generate_network_trace_message(
+        &mut self,
+        buffer: &mut [u8],
+        trace_type: MtinTypeDltNwTrace,
+        header: &[u8],
+        payload: &[u8],
+        verbose: bool,
+    ) -> Result<usize, DltError> {
+        let cnti = if verbose { ContentInfo::Verbose } else { ContentInfo::NonVerbose };
+        let serial_size = if self.serial_header { DLT_SERIAL_HEADER_SIZE } else { 0 };
+        let payload_offset = serial_size + self.header_size(cnti)?;
+        if payload_offset > buffer.len() {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let payload_size = if verbose {
+            let mut payload_builder = PayloadBuilder::new(&mut buffer[payload_offset..]);
+            payload_builder
+                .add_raw(header)
+                .and_then(|_| payload_builder.add_raw(payload))
+                .map_err(|_| DltError::BufferTooSmall)?;
+            payload_builder.len()
+        } else {
+            let mut offset = payload_offset;
+            for part in [header, payload] {
+                let len = u16::try_from(part.len()).map_err(|_| DltError::InvalidParameter)?;
+                let end = offset + 2 + part.len();
+                if buffer.len() < end {
+                    return Err(DltError::BufferTooSmall);
+                }
+                buffer[offset..offset + 2].copy_from_slice(&len.to_le_bytes());
+                buffer[offset + 2..end].copy_from_slice(part);
+                offset = end;
+            }
+            offset - payload_offset
+        };
+
+        let msin = encode_msin(verbose, MstpType::DltTypeNwTrace.to_bits(), trace_type.to_bits());
+        let noar = if verbose { 2 } else { 0 };
+        self.write_header(buffer, cnti, msin, noar, 0, payload_size)?;
+        Ok(payload_offset + payload_size)
+    }
+
+    // ========================================
+    // Message Generation - Internal Implementation
+    // ========================================
+
+    /// Write headers and copy the payload behind them
+    fn generate_message(
+        &mut self,
+        buffer: &mut [u8],
+        cnti: ContentInfo,
+        msin: u8,
+        noar: u8,
+        message_id: u32,
+        payload: &[u8],
+    ) -> Result<usize, DltError> {
+        let serial_size = if self.serial_header { DLT_SERIAL_HEADER_SIZE } else { 0 };
+        let payload_offset = serial_size + self.header_size(cnti)?;
+        let total_size = payload_offset + payload.len();
+
+        if buffer.len() < total_size {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        buffer[payload_offset..total_size].copy_from_slice(payload);
+        self.write_header(buffer, cnti, msin, noar, message_id, payload.len())?;
+
+        Ok(total_size)
+    }
+
+    /// Write all headers (serial, base, conditional, extension) to the buffer
+    ///
+    /// The message counter is automatically incremented after generation.
+    ///
+    /// # Returns
+    /// Number of header bytes written (excluding payload)
+    fn write_header(
+        &mut self,
+        buffer: &mut [u8],
+        cnti: ContentInfo,
+        msin: u8,
+        noar: u8,
+        message_id: u32,
+        payload_size: usize,
+    ) -> Result<usize, DltError> {
+        let header_size = self.header_size(cnti)?;
+        let len_field = header_size + payload_size;
+        if len_field > u16::MAX as usize {
+            return Err(DltError::InvalidParameter);
+        }
+
+        let serial_size = if self.serial_header { DLT_SERIAL_HEADER_SIZE } else { 0 };
+        if buffer.len() < serial_size + len_field {
+            return Err(DltError::BufferTooSmall);
+        }
+
+        let mut offset = 0;
+
+        // ----------------------------------------
+        // 1. Write Serial Header (optional)
+        // ----------------------------------------
+        if self.serial_header {
+            buffer[..DLT_SERIAL_HEADER_SIZE].copy_from_slice(&DLT_SERIAL_HEADER_ARRAY);
+            offset += DLT_SERIAL_HEADER_SIZE;
+        }
+
+        // ----------------------------------------
+        // 2. Write Base Header (7 bytes)
+        // ----------------------------------------
+        let htyp2 = self.htyp2(cnti);
+        buffer[offset..offset + 4].copy_from_slice(&htyp2.encode().to_le_bytes());
+        offset += 4;
+
+        buffer[offset] = self.message_counter;
+        offset += 1;
+
+        buffer[offset..offset + 2].copy_from_slice(&(len_field as u16).to_be_bytes());
+        offset += 2;
+
+        // ----------------------------------------
+        // 3. Write Conditional Fields
+        // ----------------------------------------
+        buffer[offset] = msin;
+        offset += 1;
+
+        if cnti != ContentInfo::NonVerbose {
+            buffer[offset] = noar;
+            offset += 1;
+        }
+
+        if cnti != ContentInfo::Control {
+            buffer[offset..offset + 4].copy_from_slice(&self.timestamp.nanoseconds.to_be_bytes());
+            buffer[offset + 4..offset + 9].copy_from_slice(&self.timestamp.seconds.to_be_bytes()[3..]);
+            offset += DLT2_TIMESTAMP_SIZE;
+        }
+
+        if cnti == ContentInfo::NonVerbose {
+            buffer[offset..offset + 4].copy_from_slice(&message_id.to_be_bytes());
+            offset += DLT2_MESSAGE_ID_SIZE;
+        }
+
+        // ----------------------------------------
+        // 4. Write Extension Header Fields
+        // ----------------------------------------
+        if let Some(ecu_id) = self.ecu_id {
+            offset = write_length_prefixed(buffer, offset, ecu_id);
+        }
+
+        if self.with_app_context {
+            offset = write_length_prefixed(buffer, offset, self.app_id);
+            offset = write_length_prefixed(buffer, offset, self.context_id);
+        }
+
+        if let Some(session_id) = self.session_id {
+            buffer[offset..offset + 4].copy_from_slice(&session_id.to_be_bytes());
+            offset += DLT2_SESSION_ID_SIZE;
+        }
+
+        if let Some((file_name, line_number)) = self.file_name {
+            offset = write_length_prefixed(buffer, offset, file_name);
+            buffer[offset..offset + 4].copy_from_slice(&line_number.to_be_bytes());
+            offset += DLT2_LINE_NUMBER_SIZE;
+        }
+
+        if let Some(tags) = self.tags {
+            buffer[offset] = tags.len() as u8;
+            offset += 1;
+            for tag in tags {
+                offset = write_length_prefixed(buffer, offset, tag);
+            }
+        }
+
+        if let Some(level) = self.privacy_level {
+            buffer[offset] = level;
+            offset += 1;
+        }
+
+        if let Some(segmentation) = self.segmentation {
+            match segmentation {
+                DltSegmentation::FirstFrame { total_length } => {
+                    buffer[offset] = DltSegmentation::FIRST_FRAME;
+                    buffer[offset + 1..offset + 9].copy_from_slice(&total_length.to_be_bytes());
+                }
+                DltSegmentation::ConsecutiveFrame { sequence } => {
+                    buffer[offset] = DltSegmentation::CONSECUTIVE_FRAME;
+                    buffer[offset + 1..offset + 5].copy_from_slice(&sequence.to_be_bytes());
+                }
+                DltSegmentation::LastFrame => {
+                    buffer[offset] = DltSegmentation::LAST_FRAME;
+                }
+                DltSegmentation::AbortFrame { reason } => {
+                    buffer[offset] = DltSegmentation::ABORT_FRAME;
+                    buffer[offset + 1] = reason;
+                }
+            }
+            offset += segmentation.encoded_size();
+        }
+
+        // ----------------------------------------
+        // 5. Post-generation: Increment counter
+        // ----------------------------------------
+        self.increment_counter();
+
+        Ok(offset)
+    }
+
+    /// Build the HTYP2 flags from the configured fields
+    fn htyp2(&self, cnti: ContentInfo) -> DltHTYP2 {
+        DltHTYP2 {
+            cnti,
+            weid: self.ecu_id.is_some(),
+            wacid: self.with_app_context,
+            wsid: self.session_id.is_some(),
+            vers: 2,
+            wsfln: self.file_name.is_some(),
+            wtgs: self.tags.is_some(),
+            wpvl: self.privacy_level.is_some(),
+            wsgm: self.segmentation.is_some(),
+        }
+    }
+
+    // ========================================
+    // Size Calculation Helpers
+    // ========================================
+
+    /// Calculate total header size (excludes serial header, excludes payload)
+    ///
+    /// Fails with `InvalidParameter` if a variable length field exceeds 255 bytes.
+    fn header_size(&self, cnti: ContentInfo) -> Result<usize, DltError> {
+        let mut size = DLT2_BASE_HEADER_SIZE;
+
+        size += match cnti {
+            ContentInfo::Verbose => 2 + DLT2_TIMESTAMP_SIZE,
+            ContentInfo::NonVerbose => 1 + DLT2_TIMESTAMP_SIZE + DLT2_MESSAGE_ID_SIZE,
+            ContentInfo::Control => 2,
+            ContentInfo::Reserved => return Err(DltError::InvalidParameter),
+        };
+
+        if let Some(ecu_id) = self.ecu_id {
+            size += length_prefixed_size(ecu_id)?;
+        }
+        if self.with_app_context {
+            size += length_prefixed_size(self.app_id)?;
+            size += length_prefixed_size(self.context_id)?;
+        }
+        if self.session_id.is_some() {
+            size += DLT2_SESSION_ID_SIZE;
+        }
+        if let Some((file_name, _)) = self.file_name {
+            size += length_prefixed_size(file_name)? + DLT2_LINE_NUMBER_SIZE;
+        }
+        if let Some(tags) = self.tags {
+            if tags.len() > u8::MAX as usize {
+                return Err(DltError::InvalidParameter);
+            }
+            size += 1;
+            for tag in tags {
+                size += length_prefixed_size(tag)?;
+            }
+        }
+        if self.privacy_level.is_some() {
+            size += 1;
+        }
+        if let Some(segmentation) = self.segmentation {
+            size += segmentation.encoded_size();
+        }
+
+        Ok(size)
+    }
+}
+
+// ========================================
+// Internal Helpers
+// ========================================
+
+/// Size of a length-prefixed field, rejecting values longer than 255 bytes
+#[inline]
+fn length_prefixed_size(value: &[u8]) -> Result<usize, DltError> {
+    if value.len() > DLT2_MAX_ID_LENGTH {
+        return Err(DltError::InvalidParameter);
+    }
+    Ok(1 + value.len())
+}
+
+/// Write a one byte length followed by the value, returning the new offset
+#[inline]
+fn write_length_prefixed(buffer: &mut [u8], offset: usize, value: &[u8]) -> usize {
+    buffer[offset] = value.len() as u8;
+    buffer[offset + 1..offset + 1 + value.len()].copy_from_slice(value);
+    offset + 1 + value.len()
+}
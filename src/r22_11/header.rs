@@ -0,0 +1,677 @@
+//! # DLT Protocol R22.11 (DLT v2) Header Definitions
+//!
+//! This module implements the header structures and parsing logic of the
+//! AUTOSAR DLT protocol version 2 as introduced with specification release 22.11.
+//!
+//! ## DLT v2 Message Structure
+//!
+//! A complete DLT v2 message consists of:
+//! 1. **Serial Header** (4 bytes, optional): "DLS\x01"
+//! 2. **Base Header** (7 bytes, required): HTYP2 (4 bytes), Message Counter, Length
+//! 3. **Base Header Conditional Fields** (depends on CNTI): MSIN, NOAR, TMSP2, MSID
+//! 4. **Extension Header** (variable, conditional): ECU ID, App/Context ID, Session ID,
+//!    file name and line number, tags, privacy level, segmentation
+//! 5. **Payload** (variable length): Message data
+//!
+//! ## HTYP2 Structure (Base Header, 32 bit)
+//!
+//! ```text
+//! Bits 0-1:   CNTI  - Content Information
+//!             0 = Verbose data, 1 = Non-verbose data, 2 = Control, 3 = Reserved
+//! Bit 2:      WEID  - With ECU ID
+//! Bit 3:      WACID - With App ID and Context ID
+//! Bit 4:      WSID  - With Session ID
+//! Bits 5-7:   VERS  - Version Number (2 for DLT v2)
+//! Bit 8:      WSFLN - With Source File Name and Line Number
+//! Bit 9:      WTGS  - With Tags
+//! Bit 10:     WPVL  - With Privacy Level
+//! Bit 11:     WSGM  - With Segmentation
+//! Bits 12-31: Reserved
+//! ```
+//!
+//! ## Conditional Fields per CNTI
+//!
+//! ```text
+//! Verbose (0):     MSIN (1) + NOAR (1) + TMSP2 (9)
+//! Non-verbose (1): MSIN (1) + TMSP2 (9) + MSID (4)
+//! Control (2):     MSIN (1) + NOAR (1)
+//! ```
+//!
+//! HTYP2 is transmitted starting with bits 0-7, so the first byte of every
+//! message carries the version number at the same position as the R19.11 HTYP
+//! byte. All other multi-byte header fields are big-endian (network byte order).
+//! The ECU ID, Application ID and Context ID are variable length (1-255 bytes)
+//! and prefixed with a one byte length field.
+
+pub use crate::r19_11::{
+    DltHeaderError, DLT_SERIAL_HEADER_ARRAY, DLT_SERIAL_HEADER_SIZE, MstpType, MtinTypeDltAppTrace,
+    MtinTypeDltControl, MtinTypeDltLog, MtinTypeDltNwTrace, encode_msin, extract_msin_mstp,
+    extract_msin_mtin, extract_msin_verbose,
+};
+
+// ========================================
+// Size Constants
+// ========================================
+
+/// Base header size (HTYP2 + MCNT + LEN)
+pub const DLT2_BASE_HEADER_SIZE: usize = 7;
+
+/// TMSP2 field size (4 bytes nanoseconds + 5 bytes seconds)
+pub const DLT2_TIMESTAMP_SIZE: usize = 9;
+
+/// Message ID field size (non-verbose mode)
+pub const DLT2_MESSAGE_ID_SIZE: usize = 4;
+
+/// Session ID field size
+pub const DLT2_SESSION_ID_SIZE: usize = 4;
+
+/// Line number field size
+pub const DLT2_LINE_NUMBER_SIZE: usize = 4;
+
+/// Maximum length of a variable length ID, file name or tag
+pub const DLT2_MAX_ID_LENGTH: usize = 255;
+
+// ========================================
+// HTYP2 Bit Masks (Base Header)
+// ========================================
+
+/// Bits 0-1: Content Information
+pub const CNTI_MASK: u32 = 0x0000_0003;
+
+/// Bit 2: With ECU ID
+pub const WEID2_MASK: u32 = 0x0000_0004;
+
+/// Bit 3: With App ID and Context ID
+pub const WACID_MASK: u32 = 0x0000_0008;
+
+/// Bit 4: With Session ID
+pub const WSID2_MASK: u32 = 0x0000_0010;
+
+/// Bits 5-7: Version Number
+pub const VERS2_MASK: u32 = 0x0000_00E0;
+
+/// Bit 8: With Source File Name and Line Number
+pub const WSFLN_MASK: u32 = 0x0000_0100;
+
+/// Bit 9: With Tags
+pub const WTGS_MASK: u32 = 0x0000_0200;
+
+/// Bit 10: With Privacy Level
+pub const WPVL_MASK: u32 = 0x0000_0400;
+
+/// Bit 11: With Segmentation
+pub const WSGM_MASK: u32 = 0x0000_0800;
+
+/// DLT Protocol Version 2 (VERS field already shifted into place)
+pub const DLT2_VERSION: u32 = 0x0000_0040;
+
+// ========================================
+// Header Type Structures
+// ========================================
+
+/// Content Information (CNTI) - bits 0-1 of HTYP2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentInfo {
+    /// Verbose mode data message
+    Verbose,
+    /// Non-verbose mode data message
+    NonVerbose,
+    /// Control message
+    Control,
+    /// Reserved value (3)
+    Reserved,
+}
+
+impl ContentInfo {
+    /// Parse CNTI from the two low bits of HTYP2
+    pub fn parse(value: u32) -> ContentInfo {
+        match value & CNTI_MASK {
+            0x0 => ContentInfo::Verbose,
+            0x1 => ContentInfo::NonVerbose,
+            0x2 => ContentInfo::Control,
+            _ => ContentInfo::Reserved,
+        }
+    }
+
+    /// Convert to 2-bit value
+    pub fn to_bits(&self) -> u32 {
+        match self {
+            ContentInfo::Verbose => 0x0,
+            ContentInfo::NonVerbose => 0x1,
+            ContentInfo::Control => 0x2,
+            ContentInfo::Reserved => 0x3,
+        }
+    }
+}
+
+/// HTYP2 decoded structure (Base Header bytes 0-3)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DltHTYP2 {
+    /// Content information (verbose, non-verbose, control)
+    pub cnti: ContentInfo,
+    /// With ECU ID
+    pub weid: bool,
+    /// With App ID and Context ID
+    pub wacid: bool,
+    /// With Session ID
+    pub wsid: bool,
+    /// Version number (should be 2 for DLT v2)
+    pub vers: u8,
+    /// With Source File Name and Line Number
+    pub wsfln: bool,
+    /// With Tags
+    pub wtgs: bool,
+    /// With Privacy Level
+    pub wpvl: bool,
+    /// With Segmentation
+    pub wsgm: bool,
+}
+
+impl DltHTYP2 {
+    /// Decode the 32-bit HTYP2 value
+    pub fn decode(htyp2: u32) -> DltHTYP2 {
+        DltHTYP2 {
+            cnti: ContentInfo::parse(htyp2),
+            weid: (htyp2 & WEID2_MASK) != 0,
+            wacid: (htyp2 & WACID_MASK) != 0,
+            wsid: (htyp2 & WSID2_MASK) != 0,
+            vers: ((htyp2 & VERS2_MASK) >> 5) as u8,
+            wsfln: (htyp2 & WSFLN_MASK) != 0,
+            wtgs: (htyp2 & WTGS_MASK) != 0,
+            wpvl: (htyp2 & WPVL_MASK) != 0,
+            wsgm: (htyp2 & WSGM_MASK) != 0,
+        }
+    }
+
+    /// Encode back into the 32-bit HTYP2 value
+    pub fn encode(&self) -> u32 {
+        let mut htyp2 = self.cnti.to_bits() | (((self.vers as u32) << 5) & VERS2_MASK);
+        if self.weid {
+            htyp2 |= WEID2_MASK;
+        }
+        if self.wacid {
+            htyp2 |= WACID_MASK;
+        }
+        if self.wsid {
+            htyp2 |= WSID2_MASK;
+        }
+        if self.wsfln {
+            htyp2 |= WSFLN_MASK;
+        }
+        if self.wtgs {
+            htyp2 |= WTGS_MASK;
+        }
+        if self.wpvl {
+            htyp2 |= WPVL_MASK;
+        }
+        if self.wsgm {
+            htyp2 |= WSGM_MASK;
+        }
+        htyp2
+    }
+}
+
+/// DLT v2 Base Header (7 bytes, always present)
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub struct DltBaseHeader {
+    /// Header Type (HTYP2, 32 bit)
+    pub htyp2: u32,
+    /// Message Counter (wraps at 255)
+    pub mcnt: u8,
+    /// Length of message from base header to end of payload (excludes serial header)
+    pub len: u16,
+}
+
+/// DLT v2 Timestamp (TMSP2, 9 bytes)
+///
+/// Absolute time given as seconds (40 bit) and nanoseconds (30 bit used).
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub struct DltTimestamp2 {
+    /// Seconds (only the lower 40 bits are transmitted)
+    pub seconds: u64,
+    /// Nanoseconds (0..999_999_999)
+    pub nanoseconds: u32,
+}
+
+/// Segmentation frame information (present if WSGM bit set)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DltSegmentation {
+    /// First frame, carries the total length of the segmented payload
+    FirstFrame { total_length: u64 },
+    /// Consecutive frame with its sequence counter
+    ConsecutiveFrame { sequence: u32 },
+    /// Last frame of a segmented message
+    LastFrame,
+    /// Segmented transfer aborted by the sender
+    AbortFrame { reason: u8 },
+}
+
+impl DltSegmentation {
+    /// Frame type byte: First frame
+    pub const FIRST_FRAME: u8 = 0x00;
+    /// Frame type byte: Consecutive frame
+    pub const CONSECUTIVE_FRAME: u8 = 0x01;
+    /// Frame type byte: Last frame
+    pub const LAST_FRAME: u8 = 0x02;
+    /// Frame type byte: Abort frame
+    pub const ABORT_FRAME: u8 = 0x03;
+
+    /// Encoded size of the segmentation field in bytes
+    pub fn encoded_size(&self) -> usize {
+        match self {
+            DltSegmentation::FirstFrame { .. } => 1 + 8,
+            DltSegmentation::ConsecutiveFrame { .. } => 1 + 4,
+            DltSegmentation::LastFrame => 1,
+            DltSegmentation::AbortFrame { .. } => 1 + 1,
+        }
+    }
+}
+
+/// Tag list of a DLT v2 message (present if WTGS bit set)
+///
+/// The tags are kept in their encoded form (length byte + name) and decoded lazily.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DltTags<'a> {
+    count: u8,
+    data: &'a [u8],
+}
+
+impl<'a> DltTags<'a> {
+    /// Number of tags
+    pub fn len(&self) -> usize {
+        self.count as usize
+    }
+
+    /// Check if there are no tags
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Iterate over tag names
+    pub fn iter(&self) -> DltTagIter<'a> {
+        DltTagIter {
+            remaining: self.count,
+            data: self.data,
+        }
+    }
+}
+
+/// Iterator over the tag names of a [`DltTags`] list
+pub struct DltTagIter<'a> {
+    remaining: u8,
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for DltTagIter<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 || self.data.is_empty() {
+            return None;
+        }
+        let len = self.data[0] as usize;
+        if 1 + len > self.data.len() {
+            return None;
+        }
+        let tag = &self.data[1..1 + len];
+        self.data = &self.data[1 + len..];
+        self.remaining -= 1;
+        Some(tag)
+    }
+}
+
+// ========================================
+// Parsed Message Structure
+// ========================================
+
+/// Complete parsed DLT v2 message with all header information and payload
+#[derive(Debug, Clone, Copy)]
+pub struct DltMessage<'a> {
+    /// Whether the message included a serial header
+    pub has_serial_header: bool,
+    /// Base header (always present)
+    pub base_header: DltBaseHeader,
+    /// Decoded header type flags
+    pub header_type: DltHTYP2,
+    /// Message Info byte (present for all defined CNTI values)
+    pub msin: Option<u8>,
+    /// Number of Arguments (verbose and control messages)
+    pub noar: Option<u8>,
+    /// Timestamp (verbose and non-verbose messages)
+    pub timestamp: Option<DltTimestamp2>,
+    /// Message ID (non-verbose messages)
+    pub message_id: Option<u32>,
+    /// ECU ID (present if WEID flag set)
+    pub ecu_id: Option<&'a [u8]>,
+    /// Application ID (present if WACID flag set)
+    pub app_id: Option<&'a [u8]>,
+    /// Context ID (present if WACID flag set)
+    pub context_id: Option<&'a [u8]>,
+    /// Session ID (present if WSID flag set)
+    pub session_id: Option<u32>,
+    /// Source file name (present if WSFLN flag set)
+    pub file_name: Option<&'a [u8]>,
+    /// Source line number (present if WSFLN flag set)
+    pub line_number: Option<u32>,
+    /// Tags (present if WTGS flag set)
+    pub tags: Option<DltTags<'a>>,
+    /// Privacy level (present if WPVL flag set)
+    pub privacy_level: Option<u8>,
+    /// Segmentation information (present if WSGM flag set)
+    pub segmentation: Option<DltSegmentation>,
+    /// Message payload (raw bytes)
+    pub payload: &'a [u8],
+}
+
+impl<'a> DltMessage<'a> {
+    /// Check if the message carries verbose payload (CNTI = 0)
+    pub fn is_verbose(&self) -> bool {
+        self.header_type.cnti == ContentInfo::Verbose
+    }
+
+    /// Get message type (MSTP) from the MSIN byte
+    pub fn message_type(&self) -> Option<MstpType> {
+        self.msin.map(|msin| MstpType::parse(extract_msin_mstp(msin)))
+    }
+
+    /// Get raw message type info (MTIN) from the MSIN byte
+    pub fn message_type_info(&self) -> Option<u8> {
+        self.msin.map(extract_msin_mtin)
+    }
+
+    /// Get log level if this is a Log message type
+    pub fn log_level(&self) -> Option<MtinTypeDltLog> {
+        match self.message_type() {
+            Some(MstpType::DltTypeLog) => self.message_type_info().map(MtinTypeDltLog::parse),
+            _ => None,
+        }
+    }
+
+    /// Get trace type if this is an Application Trace message type
+    pub fn app_trace_type(&self) -> Option<MtinTypeDltAppTrace> {
+        match self.message_type() {
+            Some(MstpType::DltTypeAppTrace) => {
+                self.message_type_info().map(MtinTypeDltAppTrace::parse)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get network trace type if this is a Network Trace message type
+    pub fn nw_trace_type(&self) -> Option<MtinTypeDltNwTrace> {
+        match self.message_type() {
+            Some(MstpType::DltTypeNwTrace) => self.message_type_info().map(MtinTypeDltNwTrace::parse),
+            _ => None,
+        }
+    }
+}
+
+// ========================================
+// DLT v2 Header Parser
+// ========================================
+
+/// Parser for DLT v2 protocol messages
+///
+/// # Example
+/// ```no_run
+/// use dlt_protocol::r22_11::*;
+///
+/// let data: &[u8] = &[/* DLT v2 packet bytes */];
+/// let mut parser = DltHeaderParser::new(data);
+///
+/// match parser.parse_message() {
+///     Ok(message) => {
+///         println!("ECU: {:?}", message.ecu_id);
+///         println!("App ID: {:?}", message.app_id);
+///         println!("Log level: {:?}", message.log_level());
+///     }
+///     Err(e) => eprintln!("Parse error: {:?}", e),
+/// }
+/// ```
+pub struct DltHeaderParser<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> DltHeaderParser<'a> {
+    /// Create a new parser from raw packet data
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// Parse a complete DLT v2 message including all headers and payload
+    ///
+    /// This method parses:
+    /// 1. Optional serial header ("DLS\x01")
+    /// 2. Base header (HTYP2, MCNT, LEN)
+    /// 3. Conditional fields selected by CNTI (MSIN, NOAR, TMSP2, MSID)
+    /// 4. Extension header fields selected by the HTYP2 flags
+    /// 5. Payload (remaining bytes based on LEN field)
+    ///
+    /// # Returns
+    /// - `Ok(DltMessage)`: Successfully parsed message
+    /// - `Err(DltHeaderError)`: Parsing failed (buffer too small, invalid version, etc.)
+    pub fn parse_message(&mut self) -> Result<DltMessage<'a>, DltHeaderError> {
+        // Check for optional serial header
+        let has_serial = self.check_serial_header();
+        if has_serial {
+            self.position += DLT_SERIAL_HEADER_SIZE;
+        }
+        let header_start = self.position;
+
+        // Parse base header (required)
+        let base_header = self.parse_base_header()?;
+        let header_type = DltHTYP2::decode(base_header.htyp2);
+
+        // Parse conditional fields
+        let (msin, noar, timestamp, message_id) = match header_type.cnti {
+            ContentInfo::Verbose => {
+                let msin = self.read_u8()?;
+                let noar = self.read_u8()?;
+                (Some(msin), Some(noar), Some(self.read_timestamp()?), None)
+            }
+            ContentInfo::NonVerbose => {
+                let msin = self.read_u8()?;
+                let timestamp = self.read_timestamp()?;
+                (Some(msin), None, Some(timestamp), Some(self.read_u32()?))
+            }
+            ContentInfo::Control => {
+                let msin = self.read_u8()?;
+                (Some(msin), Some(self.read_u8()?), None, None)
+            }
+            ContentInfo::Reserved => return Err(DltHeaderError::InvalidHeaderType),
+        };
+
+        // Parse extension header fields
+        let ecu_id = if header_type.weid {
+            Some(self.read_length_prefixed()?)
+        } else {
+            None
+        };
+
+        let (app_id, context_id) = if header_type.wacid {
+            (Some(self.read_length_prefixed()?), Some(self.read_length_prefixed()?))
+        } else {
+            (None, None)
+        };
+
+        let session_id = if header_type.wsid {
+            Some(self.read_u32()?)
+        } else {
+            None
+        };
+
+        let (file_name, line_number) = if header_type.wsfln {
+            (Some(self.read_length_prefixed()?), Some(self.read_u32()?))
+        } else {
+            (None, None)
+        };
+
+        let tags = if header_type.wtgs {
+            Some(self.parse_tags()?)
+        } else {
+            None
+        };
+
+        let privacy_level = if header_type.wpvl {
+            Some(self.read_u8()?)
+        } else {
+            None
+        };
+
+        let segmentation = if header_type.wsgm {
+            Some(self.parse_segmentation()?)
+        } else {
+            None
+        };
+
+        // The 'len' field covers everything from the base header to the end of the payload
+        let total_len = base_header.len as usize;
+        let header_bytes_consumed = self.position - header_start;
+        if total_len < header_bytes_consumed {
+            return Err(DltHeaderError::InvalidHeaderType);
+        }
+
+        let payload_start = self.position;
+        let payload_end = header_start + total_len;
+        if payload_end > self.data.len() {
+            return Err(DltHeaderError::BufferTooSmall);
+        }
+
+        let payload = &self.data[payload_start..payload_end];
+        self.position = payload_end;
+
+        Ok(DltMessage {
+            has_serial_header: has_serial,
+            base_header,
+            header_type,
+            msin,
+            noar,
+            timestamp,
+            message_id,
+            ecu_id,
+            app_id,
+            context_id,
+            session_id,
+            file_name,
+            line_number,
+            tags,
+            privacy_level,
+            segmentation,
+            payload,
+        })
+    }
+
+    /// Check if the buffer starts with a serial header
+    fn check_serial_header(&self) -> bool {
+        if self.position + DLT_SERIAL_HEADER_SIZE > self.data.len() {
+            return false;
+        }
+        self.data[self.position..self.position + DLT_SERIAL_HEADER_SIZE] == DLT_SERIAL_HEADER_ARRAY
+    }
+
+    /// Parse the base header (7 bytes)
+    fn parse_base_header(&mut self) -> Result<DltBaseHeader, DltHeaderError> {
+        // HTYP2 is transmitted with bits 0-7 first, so the version number
+        // occupies the same position as in the R19.11 HTYP byte
+        let bytes = self.read_bytes(4)?;
+        let htyp2 = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+        // Check version
+        let version = (htyp2 & VERS2_MASK) >> 5;
+        if version != 2 {
+            return Err(DltHeaderError::InvalidVersion);
+        }
+
+        let mcnt = self.read_u8()?;
+        let len = self.read_u16()?;
+
+        Ok(DltBaseHeader { htyp2, mcnt, len })
+    }
+
+    /// Parse the tag list (number of tags followed by length-prefixed names)
+    fn parse_tags(&mut self) -> Result<DltTags<'a>, DltHeaderError> {
+        let count = self.read_u8()?;
+        let start = self.position;
+        for _ in 0..count {
+            self.read_length_prefixed()?;
+        }
+        Ok(DltTags {
+            count,
+            data: &self.data[start..self.position],
+        })
+    }
+
+    /// Parse the segmentation field
+    fn parse_segmentation(&mut self) -> Result<DltSegmentation, DltHeaderError> {
+        let frame_type = self.read_u8()?;
+        match frame_type {
+            DltSegmentation::FIRST_FRAME => {
+                let bytes = self.read_bytes(8)?;
+                let mut total = [0u8; 8];
+                total.copy_from_slice(bytes);
+                Ok(DltSegmentation::FirstFrame {
+                    total_length: u64::from_be_bytes(total),
+                })
+            }
+            DltSegmentation::CONSECUTIVE_FRAME => Ok(DltSegmentation::ConsecutiveFrame {
+                sequence: self.read_u32()?,
+            }),
+            DltSegmentation::LAST_FRAME => Ok(DltSegmentation::LastFrame),
+            DltSegmentation::ABORT_FRAME => Ok(DltSegmentation::AbortFrame {
+                reason: self.read_u8()?,
+            }),
+            _ => Err(DltHeaderError::InvalidHeaderType),
+        }
+    }
+
+    /// Read TMSP2 (4 bytes nanoseconds + 5 bytes seconds, big-endian)
+    fn read_timestamp(&mut self) -> Result<DltTimestamp2, DltHeaderError> {
+        let bytes = self.read_bytes(DLT2_TIMESTAMP_SIZE)?;
+        let nanoseconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let seconds = u64::from_be_bytes([0, 0, 0, bytes[4], bytes[5], bytes[6], bytes[7], bytes[8]]);
+        Ok(DltTimestamp2 {
+            seconds,
+            nanoseconds,
+        })
+    }
+
+    /// Read a one byte length followed by that many bytes
+    fn read_length_prefixed(&mut self) -> Result<&'a [u8], DltHeaderError> {
+        let len = self.read_u8()? as usize;
+        self.read_bytes(len)
+    }
+
+    fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], DltHeaderError> {
+        if self.position + count > self.data.len() {
+            return Err(DltHeaderError::BufferTooSmall);
+        }
+        let bytes = &self.data[self.position..self.position + count];
+        self.position += count;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, DltHeaderError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, DltHeaderError> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DltHeaderError> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Get current parsing position
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get remaining unparsed data
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.position)
+    }
+}
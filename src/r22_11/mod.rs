@@ -0,0 +1,7 @@
+mod generate_log;
+mod header;
+mod parse_log;
+
+pub use generate_log::*;
+pub use header::*;
+pub use parse_log::*;
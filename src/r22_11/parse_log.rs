@@ -0,0 +1,32 @@
+//! # DLT v2 Log Message Payload Parser (Re-export)
+//!
+//! The verbose argument encoding (type info + data) is unchanged between
+//! R19.11 and R22.11, so the payload builder and parser are shared with the
+//! `r19_11` module and re-exported here.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::r22_11::*;
+//!
+//! let data: &[u8] = &[/* DLT v2 packet bytes */];
+//! let mut header_parser = DltHeaderParser::new(data);
+//! let message = header_parser.parse_message().unwrap();
+//!
+//! if message.is_verbose() {
+//!     let mut payload_parser = PayloadParser::new(message.payload);
+//!     while !payload_parser.is_empty() {
+//!         match payload_parser.read_next() {
+//!             Ok(DltValue::String(s)) => println!("String: {}", s),
+//!             Ok(value) => println!("{:?}", value),
+//!             Err(_) => break,
+//!         }
+//!     }
+//! }
+//! ```
+
+pub use crate::r19_11::{
+    DltArgument, DltArray, DltArrayElement, DltArrayIter, DltError, DltFixedPoint, DltString,
+    DltStringChars, DltStringCoding, DltStruct, DltStructFields, DltValue, PayloadBuilder,
+    PayloadError, PayloadParser, PayloadType, TypeLength, DLT_MAX_STRUCT_DEPTH,
+};
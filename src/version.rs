@@ -0,0 +1,276 @@
+//! # Version-Agnostic DLT Message Parsing
+//!
+//! This module provides a parser that accepts both DLT protocol generations:
+//! - **R19.11** (DLT v1, version number 1) handled by [`crate::r19_11::DltHeaderParser`]
+//! - **R22.11** (DLT v2, version number 2) handled by [`crate::r22_11::DltHeaderParser`]
+//!
+//! The version number is stored in bits 5-7 of the first header byte in both
+//! generations (after an optional "DLS\x01" serial header), so it can be sniffed
+//! before choosing the implementation.
+//!
+//! ## Usage
+//!
+//! ```no_run
+//! use dlt_protocol::*;
+//!
+//! let stream: &[u8] = &[/* mixed v1/v2 DLT messages */];
+//! let mut parser = DltAnyParser::new(stream);
+//!
+//! while parser.remaining() > 0 {
+//!     match parser.parse_message() {
+//!         Ok(message) => {
+//!             println!("{:?} ECU: {:?} App: {:?}", message.version(), message.ecu_id(), message.app_id());
+//!         }
+//!         Err(e) => {
+//!             eprintln!("Parse error: {:?}", e);
+//!             break;
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::r19_11::{
+    self, DLT_SERIAL_HEADER_ARRAY, DLT_SERIAL_HEADER_SIZE, DltHeaderError, MstpType,
+    MtinTypeDltLog, VERS_MASK,
+};
+use crate::r22_11;
+
+// ========================================
+// Version Detection
+// ========================================
+
+/// DLT protocol generation of a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DltVersion {
+    /// DLT v1 (AUTOSAR R19.11 and earlier)
+    V1,
+    /// DLT v2 (AUTOSAR R22.11)
+    V2,
+}
+
+/// Detect the protocol version of the message at the start of `data`
+///
+/// An optional serial header is skipped before reading the version bits.
+///
+/// # Returns
+/// - `Ok(DltVersion)`: Version 1 or 2
+/// - `Err(DltHeaderError::BufferTooSmall)`: No header byte available
+/// - `Err(DltHeaderError::InvalidVersion)`: Any other version number
+pub fn detect_version(data: &[u8]) -> Result<DltVersion, DltHeaderError> {
+    let offset = if data.starts_with(&DLT_SERIAL_HEADER_ARRAY) {
+        DLT_SERIAL_HEADER_SIZE
+    } else {
+        0
+    };
+
+    let first = *data.get(offset).ok_or(DltHeaderError::BufferTooSmall)?;
+    match (first & VERS_MASK) >> 5 {
+        1 => Ok(DltVersion::V1),
+        2 => Ok(DltVersion::V2),
+        _ => Err(DltHeaderError::InvalidVersion),
+    }
+}
+
+// ========================================
+// Unified Timestamp
+// ========================================
+
+/// Timestamp of a message in the representation of its protocol version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DltTimestamp {
+    /// DLT v1: time since ECU startup in 0.1 ms units
+    Uptime(u32),
+    /// DLT v2: absolute time
+    Absolute { seconds: u64, nanoseconds: u32 },
+}
+
+// ========================================
+// Unified Message View
+// ========================================
+
+/// A parsed DLT message of either protocol version
+#[derive(Debug, Clone, Copy)]
+pub enum AnyDltMessage<'a> {
+    /// DLT v1 (R19.11) message
+    V1(r19_11::DltMessage<'a>),
+    /// DLT v2 (R22.11) message
+    V2(r22_11::DltMessage<'a>),
+}
+
+impl<'a> AnyDltMessage<'a> {
+    /// Protocol version of the message
+    pub fn version(&self) -> DltVersion {
+        match self {
+            AnyDltMessage::V1(_) => DltVersion::V1,
+            AnyDltMessage::V2(_) => DltVersion::V2,
+        }
+    }
+
+    /// Whether the message was preceded by a serial header
+    pub fn has_serial_header(&self) -> bool {
+        match self {
+            AnyDltMessage::V1(msg) => msg.has_serial_header,
+            AnyDltMessage::V2(msg) => msg.has_serial_header,
+        }
+    }
+
+    /// Message counter
+    pub fn message_counter(&self) -> u8 {
+        match self {
+            AnyDltMessage::V1(msg) => msg.standard_header.mcnt,
+            AnyDltMessage::V2(msg) => msg.base_header.mcnt,
+        }
+    }
+
+    /// ECU ID, if present
+    pub fn ecu_id(&self) -> Option<&[u8]> {
+        match self {
+            AnyDltMessage::V1(msg) => msg.ecu_id.as_ref().map(|id| &id[..]),
+            AnyDltMessage::V2(msg) => msg.ecu_id,
+        }
+    }
+
+    /// Application ID, if present
+    pub fn app_id(&self) -> Option<&[u8]> {
+        match self {
+            AnyDltMessage::V1(msg) => msg.extended_header.as_ref().map(|ext| &ext.apid[..]),
+            AnyDltMessage::V2(msg) => msg.app_id,
+        }
+    }
+
+    /// Context ID, if present
+    pub fn context_id(&self) -> Option<&[u8]> {
+        match self {
+            AnyDltMessage::V1(msg) => msg.extended_header.as_ref().map(|ext| &ext.ctid[..]),
+            AnyDltMessage::V2(msg) => msg.context_id,
+        }
+    }
+
+    /// Session ID, if present
+    pub fn session_id(&self) -> Option<u32> {
+        match self {
+            AnyDltMessage::V1(msg) => msg.session_id,
+            AnyDltMessage::V2(msg) => msg.session_id,
+        }
+    }
+
+    /// Timestamp, if present
+    pub fn timestamp(&self) -> Option<DltTimestamp> {
+        match self {
+            AnyDltMessage::V1(msg) => msg.timestamp.map(DltTimestamp::Uptime),
+            AnyDltMessage::V2(msg) => msg.timestamp.map(|ts| DltTimestamp::Absolute {
+                seconds: ts.seconds,
+                nanoseconds: ts.nanoseconds,
+            }),
+        }
+    }
+
+    /// Message type (MSTP), if the message carries an MSIN byte
+    pub fn message_type(&self) -> Option<MstpType> {
+        match self {
+            AnyDltMessage::V1(msg) => msg.extended_header.map(|ext| ext.message_type()),
+            AnyDltMessage::V2(msg) => msg.message_type(),
+        }
+    }
+
+    /// Log level if this is a Log message
+    pub fn log_level(&self) -> Option<MtinTypeDltLog> {
+        match self {
+            AnyDltMessage::V1(msg) => msg.extended_header.and_then(|ext| ext.log_level()),
+            AnyDltMessage::V2(msg) => msg.log_level(),
+        }
+    }
+
+    /// Whether the payload is in verbose mode (typed arguments)
+    pub fn is_verbose(&self) -> bool {
+        match self {
+            AnyDltMessage::V1(msg) => msg.extended_header.is_some_and(|ext| ext.is_verbose()),
+            AnyDltMessage::V2(msg) => msg.is_verbose(),
+        }
+    }
+
+    /// Number of arguments, if present
+    pub fn number_of_arguments(&self) -> Option<u8> {
+        match self {
+            AnyDltMessage::V1(msg) => msg.extended_header.map(|ext| ext.noar),
+            AnyDltMessage::V2(msg) => msg.noar,
+        }
+    }
+
+    /// Message payload (raw bytes)
+    pub fn payload(&self) -> &'a [u8] {
+        match self {
+            AnyDltMessage::V1(msg) => msg.payload,
+            AnyDltMessage::V2(msg) => msg.payload,
+        }
+    }
+
+    /// Get the R19.11 message, if this is a v1 message
+    pub fn as_v1(&self) -> Option<&r19_11::DltMessage<'a>> {
+        match self {
+            AnyDltMessage::V1(msg) => Some(msg),
+            AnyDltMessage::V2(_) => None,
+        }
+    }
+
+    /// Get the R22.11 message, if this is a v2 message
+    pub fn as_v2(&self) -> Option<&r22_11::DltMessage<'a>> {
+        match self {
+            AnyDltMessage::V1(_) => None,
+            AnyDltMessage::V2(msg) => Some(msg),
+        }
+    }
+}
+
+// ========================================
+// Version-Agnostic Parser
+// ========================================
+
+/// Parser for streams containing DLT v1 and/or DLT v2 messages
+///
+/// Each message's version is detected individually, so mixed streams are supported.
+pub struct DltAnyParser<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> DltAnyParser<'a> {
+    /// Create a new parser from raw packet data
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// Parse the next message, dispatching on its version number
+    pub fn parse_message(&mut self) -> Result<AnyDltMessage<'a>, DltHeaderError> {
+        let data = &self.data[self.position..];
+        match detect_version(data)? {
+            DltVersion::V1 => {
+                let mut parser = r19_11::DltHeaderParser::new(data);
+                let message = parser.parse_message()?;
+                self.position += parser.position();
+                Ok(AnyDltMessage::V1(message))
+            }
+            DltVersion::V2 => {
+                let mut parser = r22_11::DltHeaderParser::new(data);
+                let message = parser.parse_message()?;
+                self.position += parser.position();
+                Ok(AnyDltMessage::V2(message))
+            }
+        }
+    }
+
+    /// Get current parsing position
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get remaining unparsed data
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.position)
+    }
+}
+
+/// Parse a single message of either protocol version
+pub fn parse_any_message(data: &[u8]) -> Result<AnyDltMessage<'_>, DltHeaderError> {
+    DltAnyParser::new(data).parse_message()
+}
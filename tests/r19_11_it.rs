@@ -545,6 +545,28 @@ fn test_payload_parser_raw() {
     assert!(parser.is_empty());
 }
 
+#[test]
+fn test_payload_raw_without_terminator() {
+    let mut buffer = [0u8; 64];
+    let payload_len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_raw(&[1, 2, 3, 0]).unwrap();
+        builder.add_raw(&[]).unwrap();
+        builder.len()
+    };
+    // Length is the number of data bytes, trailing zeros are data
+    assert_eq!(&buffer[..payload_len], &[0, 0x04, 0, 0, 4, 0, 1, 2, 3, 0, 0, 0x04, 0, 0, 0, 0]);
+
+    let mut parser = PayloadParser::new(&buffer[..payload_len]);
+    assert_eq!(parser.read_next().unwrap(), DltValue::Raw(&[1, 2, 3, 0]));
+    assert_eq!(parser.read_next().unwrap(), DltValue::Raw(&[]));
+    assert!(parser.is_empty());
+
+    parser.reset();
+    parser.skip_argument().unwrap();
+    assert_eq!(parser.read_raw().unwrap(), &[]);
+}
+
 #[test]
 fn test_payload_parser_u128() {
     let mut buffer = [0u8; 64];  // Increased buffer size for 4 (type info) + 16 (data) = 20 bytes
@@ -836,7 +858,10 @@ fn test_read_next_string() {
     let mut parser = PayloadParser::new(&buffer[..payload_len]);
     
     match parser.read_next().unwrap() {
-        DltValue::String(val) => assert_eq!(val, "Hello, DLT!"),
+        DltValue::String(val) => {
+            assert_eq!(val.as_str(), Some("Hello, DLT!"));
+            assert_eq!(val.coding(), DltStringCoding::Utf8);
+        }
         _ => panic!("Expected String"),
     }
 }
@@ -887,7 +912,7 @@ fn test_read_next_mixed_unknown_types() {
     
     // Verify the parsed values
     assert_eq!(val1, DltValue::U32(42));
-    assert_eq!(val2, DltValue::String("Unknown packet"));
+    assert_eq!(val2, DltValue::String(DltString::utf8("Unknown packet")));
     assert_eq!(val3, DltValue::Bool(true));
     
     match val4 {
@@ -921,7 +946,7 @@ fn test_read_all_args() {
     assert_eq!(count, 4);
     assert_eq!(args_buffer[0], Some(DltValue::U32(1)));
     assert_eq!(args_buffer[1], Some(DltValue::U32(2)));
-    assert_eq!(args_buffer[2], Some(DltValue::String("three")));
+    assert_eq!(args_buffer[2], Some(DltValue::String(DltString::utf8("three"))));
     assert_eq!(args_buffer[3], Some(DltValue::Bool(true)));
     assert_eq!(args_buffer[4], None);
 }
@@ -974,14 +999,14 @@ fn test_parse_incoming_packet_simulation() {
     assert_eq!(arg_count, 6);
     
     // Verify the parsed message
-    assert_eq!(args[0], Some(DltValue::String("Temperature")));
+    assert_eq!(args[0], Some(DltValue::String(DltString::utf8("Temperature"))));
     match args[1] {
         Some(DltValue::F32(v)) => assert!((v - 23.5).abs() < 0.01),
         _ => panic!("Expected F32"),
     }
-    assert_eq!(args[2], Some(DltValue::String("Status")));
-    assert_eq!(args[3], Some(DltValue::String("OK")));
-    assert_eq!(args[4], Some(DltValue::String("ErrorCode")));
+    assert_eq!(args[2], Some(DltValue::String(DltString::utf8("Status"))));
+    assert_eq!(args[3], Some(DltValue::String(DltString::utf8("OK"))));
+    assert_eq!(args[4], Some(DltValue::String(DltString::utf8("ErrorCode"))));
     assert_eq!(args[5], Some(DltValue::U32(0)));
 }
 
@@ -1688,6 +1713,148 @@ fn test_parse_get_log_info_request() {
     assert!(is_wildcard_id(&ctx_id));
 }
 
+#[test]
+fn test_log_channel_assignment_request() {
+    let mut builder = DltServiceMessageBuilder::new().with_ecu_id(b"ECU1");
+    let mut buffer = [0u8; 256];
+
+    for operation in [LogChannelAssignment::Add, LogChannelAssignment::Remove] {
+        let size = builder
+            .generate_set_log_channel_assignment_request(&mut buffer, b"APP1", b"CTX1", b"CH02", operation)
+            .unwrap();
+
+        let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+        assert_eq!(msg.extended_header.unwrap().message_type(), MstpType::DltTypeControl);
+
+        let service_parser = DltServiceParser::new(msg.payload);
+        assert_eq!(service_parser.parse_service_id().unwrap(), ServiceId::SetLogChannelAssignment);
+        let (app_id, ctx_id, log_channel, parsed) =
+            service_parser.parse_set_log_channel_assignment_request().unwrap();
+        assert_eq!(&app_id, b"APP1");
+        assert_eq!(&ctx_id, b"CTX1");
+        assert_eq!(&log_channel, b"CH02");
+        assert_eq!(parsed, operation);
+    }
+
+    // Unknown operation and truncated payload
+    let mut payload = [0u8; 17];
+    payload[0..4].copy_from_slice(&0x20u32.to_le_bytes());
+    payload[16] = 2;
+    let service_parser = DltServiceParser::new(&payload);
+    assert!(matches!(service_parser.parse_set_log_channel_assignment_request(), Err(DltError::InvalidParameter)));
+    let service_parser = DltServiceParser::new(&payload[..16]);
+    assert!(matches!(service_parser.parse_set_log_channel_assignment_request(), Err(DltError::BufferTooSmall)));
+}
+
+#[test]
+fn test_log_channel_threshold_requests() {
+    let mut builder = DltServiceMessageBuilder::new();
+    let mut buffer = [0u8; 256];
+
+    let size = builder
+        .generate_set_log_channel_threshold_request(&mut buffer, b"CH01", 5, 1)
+        .unwrap();
+    let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    let service_parser = DltServiceParser::new(msg.payload);
+    assert_eq!(service_parser.parse_service_id().unwrap(), ServiceId::SetLogChannelThreshold);
+    assert_eq!(
+        service_parser.parse_set_log_channel_threshold_request().unwrap(),
+        (*b"CH01", 5, 1)
+    );
+
+    let size = builder
+        .generate_get_log_channel_threshold_request(&mut buffer, b"CH01")
+        .unwrap();
+    let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    let service_parser = DltServiceParser::new(msg.payload);
+    assert_eq!(service_parser.parse_service_id().unwrap(), ServiceId::GetLogChannelThreshold);
+    assert_eq!(service_parser.parse_get_log_channel_threshold_request().unwrap(), *b"CH01");
+    assert_eq!(msg.payload.len(), 8);
+
+    let service_parser = DltServiceParser::new(&msg.payload[..7]);
+    assert!(matches!(service_parser.parse_get_log_channel_threshold_request(), Err(DltError::BufferTooSmall)));
+}
+
+#[test]
+fn test_log_channel_responses() {
+    let mut builder = DltServiceMessageBuilder::new().with_ecu_id(b"ECU1");
+    let mut buffer = [0u8; 256];
+
+    let size = builder
+        .generate_get_log_channel_threshold_response(&mut buffer, ServiceStatus::Ok, 4, 0)
+        .unwrap();
+    let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert!(matches!(
+        MtinTypeDltControl::parse(msg.extended_header.unwrap().message_type_info()),
+        MtinTypeDltControl::DltControlResponse
+    ));
+    let service_parser = DltServiceParser::new(msg.payload);
+    assert_eq!(service_parser.parse_service_id().unwrap(), ServiceId::GetLogChannelThreshold);
+    assert_eq!(
+        service_parser.parse_get_log_channel_threshold_response().unwrap(),
+        (ServiceStatus::Ok, 4, 0)
+    );
+
+    // SetLogChannelAssignment and SetLogChannelThreshold reply with a status only
+    for service_id in [ServiceId::SetLogChannelAssignment, ServiceId::SetLogChannelThreshold] {
+        let size = builder
+            .generate_status_response(&mut buffer, service_id, ServiceStatus::NotSupported)
+            .unwrap();
+        let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+        let service_parser = DltServiceParser::new(msg.payload);
+        assert_eq!(service_parser.parse_service_id().unwrap(), service_id);
+        assert_eq!(service_parser.parse_status_response().unwrap(), ServiceStatus::NotSupported);
+    }
+}
+
+#[test]
+fn test_sync_time_stamp_service() {
+    let mut builder = DltServiceMessageBuilder::new().with_ecu_id(b"ECU1").with_timestamp(12345);
+    // Header timestamp in network byte order, as the parser reads it
+    builder.set_endian(DltEndian::Big);
+    let mut buffer = [0u8; 256];
+
+    let size = builder.generate_sync_time_stamp_request(&mut buffer).unwrap();
+    let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert!(matches!(
+        MtinTypeDltControl::parse(msg.extended_header.unwrap().message_type_info()),
+        MtinTypeDltControl::DltControlRequest
+    ));
+    assert_eq!(msg.payload.len(), 4);
+    assert_eq!(DltServiceParser::new(msg.payload).parse_service_id().unwrap(), ServiceId::SyncTimeStamp);
+
+    // Seconds above 32 bits use the secondsHi field
+    let time_stamp = DltSyncTimeStamp {
+        time_base_status: DLT_TIME_BASE_GLOBAL_TIME_BASE,
+        seconds: 0x0001_6553_F100,
+        nanoseconds: 999_999_999,
+    };
+    let size = builder
+        .generate_sync_time_stamp_response(&mut buffer, ServiceStatus::Ok, &time_stamp)
+        .unwrap();
+    let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert_eq!(msg.timestamp, Some(12345));
+    assert_eq!(msg.payload.len(), 16);
+    assert_eq!(msg.payload[4], ServiceStatus::Ok.to_u8());
+    assert_eq!(msg.payload[5], DLT_TIME_BASE_GLOBAL_TIME_BASE);
+    assert_eq!(&msg.payload[6..10], &999_999_999u32.to_le_bytes());
+    assert_eq!(&msg.payload[10..14], &0x6553_F100u32.to_le_bytes());
+    assert_eq!(&msg.payload[14..16], &[0x01, 0x00]);
+
+    let service_parser = DltServiceParser::new(msg.payload);
+    assert_eq!(service_parser.parse_service_id().unwrap(), ServiceId::SyncTimeStamp);
+    let (status, parsed) = service_parser.parse_sync_time_stamp_response().unwrap();
+    assert_eq!(status, ServiceStatus::Ok);
+    assert_eq!(parsed, time_stamp);
+    assert!(parsed.is_synchronized());
+    assert!(!DltSyncTimeStamp { time_base_status: DLT_TIME_BASE_GLOBAL_TIME_BASE | DLT_TIME_BASE_TIMEOUT, ..parsed }.is_synchronized());
+
+    assert_eq!(
+        DltServiceParser::new(&msg.payload[..15]).parse_sync_time_stamp_response(),
+        Err(DltError::BufferTooSmall)
+    );
+}
+
 #[test]
 fn test_service_id_parsing() {
     let mut buffer = [0u8; 256];
@@ -2531,3 +2698,3408 @@ fn test_generate_get_log_info_response_matches_target_hex() {
     println!("✅ Generated GetLogInfo response hex matches target EXACTLY (101 bytes)!");
 }
 
+
+// ========================================
+// DLT Storage Header and File Reader Tests
+// ========================================
+
+/// Append a storage header and a log message to `file`, returning the new length
+fn append_stored_message(file: &mut [u8], offset: usize, seconds: u32, text: &[u8]) -> usize {
+    let header = DltStorageHeader::new(seconds, 250, b"ECU1");
+    let mut pos = offset + header.write(&mut file[offset..]).unwrap();
+
+    let mut builder = DltMessageBuilder::new()
+        .with_ecu_id(b"ECU1")
+        .with_app_id(b"APP1")
+        .with_context_id(b"CTX1");
+    pos += builder
+        .generate_log_message_with_payload(&mut file[pos..], text, MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap();
+    pos
+}
+
+#[test]
+fn test_storage_header_roundtrip() {
+    let header = DltStorageHeader::new(1_700_000_000, 123_456, b"ECU1");
+    let mut buffer = [0u8; DLT_STORAGE_HEADER_SIZE];
+
+    assert_eq!(header.write(&mut buffer), Ok(DLT_STORAGE_HEADER_SIZE));
+    assert_eq!(&buffer[0..4], b"DLT\x01");
+    assert_eq!(&buffer[4..8], &1_700_000_000u32.to_le_bytes());
+    assert_eq!(&buffer[8..12], &123_456i32.to_le_bytes());
+    assert_eq!(&buffer[12..16], b"ECU1");
+
+    let parsed = DltStorageHeader::parse(&buffer).unwrap();
+    assert_eq!(parsed, header);
+    assert_eq!(parsed.timestamp_us(), 1_700_000_000_123_456);
+}
+
+#[test]
+fn test_storage_header_errors() {
+    let mut small = [0u8; 8];
+    let header = DltStorageHeader::default();
+    assert_eq!(header.write(&mut small), Err(DltError::BufferTooSmall));
+    assert_eq!(DltStorageHeader::parse(&small), Err(DltHeaderError::BufferTooSmall));
+
+    let bad = [0x44, 0x4C, 0x53, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, b'E', b'C', b'U', b'1'];
+    assert_eq!(DltStorageHeader::parse(&bad), Err(DltHeaderError::InvalidStorageHeader));
+}
+
+#[test]
+fn test_file_reader_multiple_messages() {
+    let mut file = [0u8; 512];
+    let mut len = append_stored_message(&mut file, 0, 100, b"first");
+    len = append_stored_message(&mut file, len, 101, b"second");
+    len = append_stored_message(&mut file, len, 102, b"third");
+
+    let mut reader = DltFileReader::new(&file[..len]);
+    let mut count = 0;
+    for (i, record) in reader.by_ref().enumerate() {
+        let stored = record.unwrap();
+        assert_eq!(stored.storage_header.seconds, 100 + i as u32);
+        assert_eq!(stored.storage_header.microseconds, 250);
+        assert_eq!(stored.storage_header.ecu_id, *b"ECU1");
+        assert_eq!(stored.message.ecu_id, Some(*b"ECU1"));
+        assert_eq!(stored.message.extended_header.unwrap().apid, *b"APP1");
+        count += 1;
+    }
+
+    assert_eq!(count, 3);
+    assert_eq!(reader.remaining(), 0);
+    assert_eq!(reader.skipped_bytes(), 0);
+}
+
+#[test]
+fn test_file_reader_payload_decoding() {
+    let mut file = [0u8; 256];
+    let len = append_stored_message(&mut file, 0, 42, b"hello file");
+
+    let stored = DltFileReader::new(&file[..len]).next().unwrap().unwrap();
+    let mut parser = PayloadParser::new(stored.message.payload);
+    assert_eq!(parser.read_next().unwrap(), DltValue::String(DltString::utf8("hello file")));
+}
+
+#[test]
+fn test_file_reader_resync_after_garbage() {
+    let mut file = [0u8; 512];
+    let mut len = append_stored_message(&mut file, 0, 1, b"before");
+    let garbage_start = len;
+    file[len..len + 7].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF, 0x00, 0x11, 0x22]);
+    len += 7;
+    len = append_stored_message(&mut file, len, 2, b"after");
+
+    let mut reader = DltFileReader::new(&file[..len]);
+    assert_eq!(reader.next().unwrap().unwrap().storage_header.seconds, 1);
+    assert_eq!(reader.next().unwrap().unwrap_err(), DltHeaderError::InvalidStorageHeader);
+    assert_eq!(reader.position(), garbage_start + 7);
+    assert_eq!(reader.next().unwrap().unwrap().storage_header.seconds, 2);
+    assert!(reader.next().is_none());
+    assert_eq!(reader.skipped_bytes(), 7);
+}
+
+#[test]
+fn test_file_reader_truncated_last_record() {
+    let mut file = [0u8; 512];
+    let mut len = append_stored_message(&mut file, 0, 1, b"complete");
+    len = append_stored_message(&mut file, len, 2, b"truncated");
+
+    let mut reader = DltFileReader::new(&file[..len - 5]);
+    assert!(reader.next().unwrap().is_ok());
+    assert_eq!(reader.next().unwrap().unwrap_err(), DltHeaderError::BufferTooSmall);
+    assert!(reader.next().is_none());
+}
+
+// ========================================
+// Big-Endian (MSBF) Payload Tests
+// ========================================
+
+/// Verbose payload encoded big-endian: u32, i16, f64, string, raw
+fn big_endian_payload() -> ([u8; 64], usize) {
+    let mut buf = [0u8; 64];
+    let mut pos = 0;
+    let mut put = |bytes: &[u8]| {
+        buf[pos..pos + bytes.len()].copy_from_slice(bytes);
+        pos += bytes.len();
+    };
+    put(&0x0000_0043u32.to_be_bytes()); // UINT, 32 bit
+    put(&0x1234_5678u32.to_be_bytes());
+    put(&0x0000_0022u32.to_be_bytes()); // SINT, 16 bit
+    put(&(-2i16).to_be_bytes());
+    put(&0x0000_0084u32.to_be_bytes()); // FLOA, 64 bit
+    put(&1.5f64.to_be_bytes());
+    put(&0x0000_0200u32.to_be_bytes()); // STRG
+    put(&3u16.to_be_bytes());
+    put(b"hi\0");
+    put(&0x0000_0400u32.to_be_bytes()); // RAWD, no terminator
+    put(&2u16.to_be_bytes());
+    put(&[0xAB, 0xCD]);
+    (buf, pos)
+}
+
+#[test]
+fn test_payload_parser_big_endian_values() {
+    let (buf, len) = big_endian_payload();
+    let mut parser = PayloadParser::with_endian(&buf[..len], DltEndian::Big);
+    assert_eq!(parser.endian(), DltEndian::Big);
+
+    assert_eq!(parser.peek_type_info().unwrap(), (PayloadType::Unsigned, TypeLength::Bit32));
+    assert_eq!(parser.read_next().unwrap(), DltValue::U32(0x1234_5678));
+    assert_eq!(parser.read_next().unwrap(), DltValue::I16(-2));
+    assert_eq!(parser.read_next().unwrap(), DltValue::F64(1.5));
+    assert_eq!(parser.read_next().unwrap(), DltValue::String(DltString::ascii("hi")));
+    assert_eq!(parser.read_next().unwrap(), DltValue::Raw(&[0xAB, 0xCD]));
+    assert!(parser.is_empty());
+}
+
+#[test]
+fn test_payload_parser_big_endian_skip_argument() {
+    let (buf, len) = big_endian_payload();
+    let mut parser = PayloadParser::with_endian(&buf[..len], DltEndian::Big);
+
+    for _ in 0..3 {
+        parser.skip_argument().unwrap();
+    }
+    assert_eq!(parser.read_string().unwrap(), "hi");
+    parser.skip_argument().unwrap();
+    assert!(parser.is_empty());
+}
+
+#[test]
+fn test_payload_parser_big_endian_misread_as_little() {
+    let (buf, len) = big_endian_payload();
+    let mut parser = PayloadParser::new(&buf[..len]);
+    assert_eq!(parser.endian(), DltEndian::Little);
+    assert!(parser.read_next().is_err());
+}
+
+#[test]
+fn test_payload_parser_from_message_msbf() {
+    let (payload, payload_len) = big_endian_payload();
+    let mut builder = DltMessageBuilder::new();
+    builder.set_endian(DltEndian::Big);
+
+    let mut buffer = [0u8; 256];
+    let size = builder
+        .generate_log_message_with_payload(
+            &mut buffer,
+            &payload[..payload_len],
+            MtinTypeDltLog::DltLogInfo,
+            5,
+            false,
+        )
+        .unwrap();
+
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert!(message.header_type.MSBF);
+
+    let mut parser = PayloadParser::from_message(&message);
+    assert_eq!(parser.endian(), DltEndian::Big);
+    assert_eq!(parser.read_u32().unwrap(), 0x1234_5678);
+    assert_eq!(parser.read_i16().unwrap(), -2);
+}
+
+#[test]
+fn test_payload_parser_from_message_little_endian() {
+    let mut builder = DltMessageBuilder::new();
+    let mut buffer = [0u8; 256];
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, b"le", MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap();
+
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    let mut parser = PayloadParser::from_message(&message);
+    assert_eq!(parser.endian(), DltEndian::Little);
+    assert_eq!(parser.read_string().unwrap(), "le");
+}
+
+#[test]
+fn test_payload_builder_big_endian_encoding() {
+    let mut buffer = [0u8; 64];
+    let mut builder = PayloadBuilder::with_endian(&mut buffer, DltEndian::Big);
+    assert_eq!(builder.endian(), DltEndian::Big);
+    builder.add_u32(0x1234_5678).unwrap();
+    builder.add_string("hi").unwrap();
+
+    let (expected, _) = big_endian_payload();
+    let bytes = builder.as_slice();
+    assert_eq!(&bytes[..8], &expected[..8]);
+    assert_eq!(&bytes[8..], &[0x00, 0x00, 0x82, 0x00, 0x00, 0x03, b'h', b'i', 0x00]);
+}
+
+#[test]
+fn test_payload_big_endian_roundtrip_all_types() {
+    let mut buffer = [0u8; 256];
+    let len = {
+        let mut builder = PayloadBuilder::with_endian(&mut buffer, DltEndian::Big);
+        builder.add_bool(true).unwrap();
+        builder.add_i8(-8).unwrap();
+        builder.add_i16(-1600).unwrap();
+        builder.add_i32(-320_000).unwrap();
+        builder.add_i64(-6_400_000_000).unwrap();
+        builder.add_u8(8).unwrap();
+        builder.add_u16(1600).unwrap();
+        builder.add_u32(320_000).unwrap();
+        builder.add_u64(6_400_000_000).unwrap();
+        builder.add_u128(0x0102_0304_0506_0708_090A_0B0C_0D0E_0F10).unwrap();
+        builder.add_f32(0.25).unwrap();
+        builder.add_f64(-2.5).unwrap();
+        builder.add_string("big endian").unwrap();
+        builder.add_raw(&[1, 2, 3]).unwrap();
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::with_endian(&buffer[..len], DltEndian::Big);
+    let expected = [
+        DltValue::Bool(true),
+        DltValue::I8(-8),
+        DltValue::I16(-1600),
+        DltValue::I32(-320_000),
+        DltValue::I64(-6_400_000_000),
+        DltValue::U8(8),
+        DltValue::U16(1600),
+        DltValue::U32(320_000),
+        DltValue::U64(6_400_000_000),
+        DltValue::U128(0x0102_0304_0506_0708_090A_0B0C_0D0E_0F10),
+        DltValue::F32(0.25),
+        DltValue::F64(-2.5),
+        DltValue::String(DltString::utf8("big endian")),
+        DltValue::Raw(&[1, 2, 3]),
+    ];
+    for value in expected {
+        assert_eq!(parser.read_next().unwrap(), value);
+    }
+    assert!(parser.is_empty());
+}
+
+#[test]
+fn test_generate_big_endian_verbose_message_roundtrip() {
+    let mut builder = DltMessageBuilder::new();
+    builder.set_endian(DltEndian::Big);
+
+    let mut buffer = [0u8; 256];
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, b"self-consistent", MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap();
+
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert!(message.header_type.MSBF);
+    // String length field is big-endian
+    assert_eq!(&message.payload[4..6], &16u16.to_be_bytes());
+
+    let mut parser = PayloadParser::from_message(&message);
+    assert_eq!(parser.read_next().unwrap(), DltValue::String(DltString::utf8("self-consistent")));
+}
+
+#[test]
+fn test_insert_header_with_big_endian_payload_builder() {
+    let mut builder = DltMessageBuilder::new()
+        .with_app_id(b"BEAP")
+        .with_context_id(b"BECT");
+    builder.set_endian(DltEndian::Big);
+
+    let mut buffer = [0u8; 256];
+    let payload_len = {
+        let mut payload = builder.payload_builder(&mut buffer);
+        payload.add_i32(-42).unwrap();
+        payload.add_f32(3.5).unwrap();
+        payload.len()
+    };
+    let size = builder
+        .insert_header_at_front(&mut buffer, payload_len, 2, MtinTypeDltLog::DltLogDebug)
+        .unwrap();
+
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    let mut parser = PayloadParser::from_message(&message);
+    assert_eq!(parser.read_next().unwrap(), DltValue::I32(-42));
+    assert_eq!(parser.read_next().unwrap(), DltValue::F32(3.5));
+    assert!(parser.is_empty());
+}
+
+// ========================================
+// Array (ARAY) Argument Tests
+// ========================================
+
+#[test]
+fn test_array_u16_vector_roundtrip() {
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_array(&[4], &[10u16, 20, 30, 40]).unwrap();
+        builder.len()
+    };
+
+    // Type info: ARAY | UINT | 16 bit, then 1 dimension of 4 entries
+    assert_eq!(&buffer[0..4], &(0x0100u32 | 0x40 | 0x02).to_le_bytes());
+    assert_eq!(&buffer[4..8], &[1, 0, 4, 0]);
+    assert_eq!(len, 4 + 2 + 2 + 4 * 2);
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    assert_eq!(parser.peek_type_info().unwrap(), (PayloadType::Array, TypeLength::Bit16));
+    let array = match parser.read_next().unwrap() {
+        DltValue::Array(array) => array,
+        other => panic!("expected array, got {:?}", other),
+    };
+    assert_eq!(array.element_type(), PayloadType::Unsigned);
+    assert_eq!(array.type_length(), TypeLength::Bit16);
+    assert_eq!(array.dimension_count(), 1);
+    assert_eq!(array.len(), 4);
+    assert_eq!(array.get(2), Some(DltValue::U16(30)));
+    assert_eq!(array.get(4), None);
+    let values: Vec<_> = array.iter().collect();
+    assert_eq!(values, [DltValue::U16(10), DltValue::U16(20), DltValue::U16(30), DltValue::U16(40)]);
+    assert!(parser.is_empty());
+}
+
+#[test]
+fn test_array_matrix_indexing() {
+    let mut buffer = [0u8; 128];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_array(&[2, 3], &[1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    let array = parser.read_array().unwrap();
+    assert_eq!(array.dimensions().collect::<Vec<_>>(), [2, 3]);
+    assert_eq!(array.dimension(1), Some(3));
+    assert_eq!(array.dimension(2), None);
+    assert_eq!(array.get_at(&[0, 0]), Some(DltValue::F32(1.0)));
+    assert_eq!(array.get_at(&[1, 2]), Some(DltValue::F32(6.0)));
+    assert_eq!(array.get_at(&[1, 0]), Some(DltValue::F32(4.0)));
+    assert_eq!(array.get_at(&[2, 0]), None);
+    assert_eq!(array.get_at(&[0]), None);
+}
+
+#[test]
+fn test_array_bool_and_signed_big_endian() {
+    let mut buffer = [0u8; 128];
+    let len = {
+        let mut builder = PayloadBuilder::with_endian(&mut buffer, DltEndian::Big);
+        builder.add_array(&[3], &[true, false, true]).unwrap();
+        builder.add_array(&[2], &[-5i64, i64::MAX]).unwrap();
+        builder.add_u8(7).unwrap();
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::with_endian(&buffer[..len], DltEndian::Big);
+    let bools = parser.read_array().unwrap();
+    assert_eq!(bools.iter().collect::<Vec<_>>(), [DltValue::Bool(true), DltValue::Bool(false), DltValue::Bool(true)]);
+    let signed = parser.read_array().unwrap();
+    assert_eq!(signed.get(0), Some(DltValue::I64(-5)));
+    assert_eq!(signed.get(1), Some(DltValue::I64(i64::MAX)));
+    assert_eq!(parser.read_u8().unwrap(), 7);
+}
+
+#[test]
+fn test_array_skip_argument_and_zero_copy() {
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_array(&[3], &[1u8, 2, 3]).unwrap();
+        builder.add_string("after").unwrap();
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    parser.skip_argument().unwrap();
+    assert_eq!(parser.read_string().unwrap(), "after");
+
+    parser.reset();
+    let array = parser.read_array().unwrap();
+    assert_eq!(array.raw_data(), &[1, 2, 3]);
+    assert_eq!(array.raw_data().as_ptr(), buffer[8..].as_ptr());
+}
+
+#[test]
+fn test_array_errors() {
+    let mut buffer = [0u8; 64];
+    let mut builder = PayloadBuilder::new(&mut buffer);
+    assert!(matches!(builder.add_array(&[2, 2], &[1u32, 2, 3]), Err(PayloadError::InvalidData)));
+    assert!(builder.is_empty());
+
+    builder.add_array(&[2], &[1u32, 2]).unwrap();
+    let len = builder.len();
+
+    // Typed readers must not misinterpret an array as a scalar
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    assert!(matches!(parser.read_u32(), Err(PayloadError::InvalidType)));
+
+    // Truncated element data
+    let mut parser = PayloadParser::new(&buffer[..len - 1]);
+    assert!(matches!(parser.read_array(), Err(PayloadError::BufferTooSmall)));
+}
+
+// ========================================
+// Struct (STRU) Argument Tests
+// ========================================
+
+#[test]
+fn test_struct_roundtrip_flat() {
+    let mut buffer = [0u8; 128];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.open_struct().unwrap();
+        assert_eq!(builder.struct_depth(), 1);
+        builder.add_u32(42).unwrap();
+        builder.add_string("wheel").unwrap();
+        builder.add_f32(1.5).unwrap();
+        builder.close_struct().unwrap();
+        builder.add_bool(true).unwrap();
+        builder.len()
+    };
+
+    // Type info STRU, entry count 3
+    assert_eq!(&buffer[0..4], &0x4000u32.to_le_bytes());
+    assert_eq!(&buffer[4..6], &3u16.to_le_bytes());
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    let record = match parser.read_next().unwrap() {
+        DltValue::Struct(record) => record,
+        other => panic!("expected struct, got {:?}", other),
+    };
+    assert_eq!(record.len(), 3);
+    let fields: Vec<_> = record.fields().map(Result::unwrap).collect();
+    assert_eq!(fields, [DltValue::U32(42), DltValue::String(DltString::utf8("wheel")), DltValue::F32(1.5)]);
+
+    // Parsing continues after the struct
+    assert!(parser.read_bool().unwrap());
+    assert!(parser.is_empty());
+}
+
+#[test]
+fn test_struct_nested_recursive_iteration() {
+    let mut buffer = [0u8; 256];
+    let len = {
+        let mut builder = PayloadBuilder::with_endian(&mut buffer, DltEndian::Big);
+        builder.open_struct().unwrap();
+        builder.add_string("vehicle").unwrap();
+        builder.open_struct().unwrap();
+        builder.add_i16(-3).unwrap();
+        builder.add_array(&[2], &[7u8, 8]).unwrap();
+        builder.close_struct().unwrap();
+        builder.add_u8(1).unwrap();
+        builder.close_struct().unwrap();
+        assert_eq!(builder.struct_depth(), 0);
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::with_endian(&buffer[..len], DltEndian::Big);
+    let outer = parser.read_struct().unwrap();
+    assert!(parser.is_empty());
+    assert_eq!(outer.len(), 3);
+
+    let mut fields = outer.fields();
+    assert_eq!(fields.next().unwrap().unwrap(), DltValue::String(DltString::utf8("vehicle")));
+    let inner = match fields.next().unwrap().unwrap() {
+        DltValue::Struct(inner) => inner,
+        other => panic!("expected nested struct, got {:?}", other),
+    };
+    assert_eq!(fields.next().unwrap().unwrap(), DltValue::U8(1));
+    assert!(fields.next().is_none());
+
+    let mut inner_fields = inner.fields();
+    assert_eq!(inner_fields.next().unwrap().unwrap(), DltValue::I16(-3));
+    match inner_fields.next().unwrap().unwrap() {
+        DltValue::Array(array) => assert_eq!(array.raw_data(), &[7, 8]),
+        other => panic!("expected array, got {:?}", other),
+    }
+    assert!(inner_fields.next().is_none());
+}
+
+#[test]
+fn test_struct_skip_argument_and_empty() {
+    let mut buffer = [0u8; 128];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.open_struct().unwrap();
+        builder.close_struct().unwrap();
+        builder.open_struct().unwrap();
+        builder.add_u64(9).unwrap();
+        builder.close_struct().unwrap();
+        builder.add_u16(5).unwrap();
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    let empty = parser.read_struct().unwrap();
+    assert!(empty.is_empty());
+    assert!(empty.fields().next().is_none());
+    parser.skip_argument().unwrap();
+    assert_eq!(parser.read_u16().unwrap(), 5);
+}
+
+#[test]
+fn test_struct_builder_errors() {
+    let mut buffer = [0u8; 256];
+    let mut builder = PayloadBuilder::new(&mut buffer);
+    assert!(matches!(builder.close_struct(), Err(PayloadError::InvalidData)));
+
+    for _ in 0..DLT_MAX_STRUCT_DEPTH {
+        builder.open_struct().unwrap();
+    }
+    assert!(matches!(builder.open_struct(), Err(PayloadError::InvalidData)));
+}
+
+#[test]
+fn test_struct_parser_truncated_entries() {
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.open_struct().unwrap();
+        builder.add_u32(1).unwrap();
+        builder.add_u32(2).unwrap();
+        builder.close_struct().unwrap();
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::new(&buffer[..len - 2]);
+    assert!(matches!(parser.read_struct(), Err(PayloadError::BufferTooSmall)));
+}
+
+// ========================================
+// Variable Info (VARI) Tests
+// ========================================
+
+#[test]
+fn test_named_numeric_argument_layout() {
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_named("speed", "km/h", DltValue::U16(87)).unwrap();
+        builder.len()
+    };
+
+    // Type info: VARI | UINT | 16 bit
+    assert_eq!(&buffer[0..4], &(0x0800u32 | 0x40 | 0x02).to_le_bytes());
+    // Name length 6, unit length 5, "speed\0", "km/h\0", value
+    assert_eq!(&buffer[4..8], &[6, 0, 5, 0]);
+    assert_eq!(&buffer[8..14], b"speed\0");
+    assert_eq!(&buffer[14..19], b"km/h\0");
+    assert_eq!(&buffer[19..21], &87u16.to_le_bytes());
+    assert_eq!(len, 21);
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    let argument = parser.read_argument().unwrap();
+    assert_eq!(argument.name, Some("speed"));
+    assert_eq!(argument.unit, Some("km/h"));
+    assert_eq!(argument.value, DltValue::U16(87));
+}
+
+#[test]
+fn test_named_arguments_all_kinds_roundtrip() {
+    let mut buffer = [0u8; 256];
+    let len = {
+        let mut builder = PayloadBuilder::with_endian(&mut buffer, DltEndian::Big);
+        builder.add_named("enabled", "ignored", DltValue::Bool(true)).unwrap();
+        builder.add_named("temp", "degC", DltValue::F32(21.5)).unwrap();
+        builder.add_named("offset", "", DltValue::I32(-7)).unwrap();
+        builder.add_named("gear", "", DltValue::String(DltString::ascii("D"))).unwrap();
+        builder.add_named("blob", "", DltValue::Raw(&[9, 8])).unwrap();
+        builder.add_u8(3).unwrap();
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::with_endian(&buffer[..len], DltEndian::Big);
+    let expected = [
+        (Some("enabled"), None, DltValue::Bool(true)),
+        (Some("temp"), Some("degC"), DltValue::F32(21.5)),
+        (Some("offset"), None, DltValue::I32(-7)),
+        (Some("gear"), None, DltValue::String(DltString::ascii("D"))),
+        (Some("blob"), None, DltValue::Raw(&[9, 8])),
+        (None, None, DltValue::U8(3)),
+    ];
+    for (name, unit, value) in expected {
+        let argument = parser.read_argument().unwrap();
+        assert_eq!((argument.name, argument.unit, argument.value), (name, unit, value));
+    }
+    assert!(parser.is_empty());
+}
+
+#[test]
+fn test_named_arguments_typed_readers_and_skip() {
+    let mut buffer = [0u8; 128];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_named("rpm", "1/min", DltValue::U32(3000)).unwrap();
+        builder.add_named("label", "", DltValue::String(DltString::ascii("engine"))).unwrap();
+        builder.add_named("ok", "", DltValue::Bool(false)).unwrap();
+        builder.len()
+    };
+
+    // Typed readers skip the variable info
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    assert_eq!(parser.read_u32().unwrap(), 3000);
+    assert_eq!(parser.read_string().unwrap(), "engine");
+    assert!(!parser.read_bool().unwrap());
+
+    // read_next drops names, skip_argument steps over them
+    parser.reset();
+    parser.skip_argument().unwrap();
+    assert_eq!(parser.read_next().unwrap(), DltValue::String(DltString::ascii("engine")));
+    parser.skip_argument().unwrap();
+    assert!(parser.is_empty());
+}
+
+#[test]
+fn test_named_array_and_struct() {
+    let mut buffer = [0u8; 256];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.open_named_struct("wheel").unwrap();
+        builder.add_named_array("pressure", "bar", &[4], &[2.1f32, 2.1, 2.3, 2.2]).unwrap();
+        builder.add_named("id", "", DltValue::U8(1)).unwrap();
+        builder.close_struct().unwrap();
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    let argument = parser.read_argument().unwrap();
+    assert_eq!(argument.name, Some("wheel"));
+    let record = match argument.value {
+        DltValue::Struct(record) => record,
+        other => panic!("expected struct, got {:?}", other),
+    };
+    assert_eq!(record.name(), Some("wheel"));
+    assert_eq!(record.len(), 2);
+
+    let mut fields = record.parser();
+    let pressure = fields.read_argument().unwrap();
+    assert_eq!((pressure.name, pressure.unit), (Some("pressure"), Some("bar")));
+    match pressure.value {
+        DltValue::Array(array) => {
+            assert_eq!(array.name(), Some("pressure"));
+            assert_eq!(array.unit(), Some("bar"));
+            assert_eq!(array.get(2), Some(DltValue::F32(2.3)));
+        }
+        other => panic!("expected array, got {:?}", other),
+    }
+    let id = fields.read_argument().unwrap();
+    assert_eq!((id.name, id.unit, id.value), (Some("id"), None, DltValue::U8(1)));
+}
+
+#[test]
+fn test_named_argument_rejects_containers() {
+    let mut source = [0u8; 32];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut source);
+        builder.add_array(&[1], &[1u8]).unwrap();
+        builder.len()
+    };
+    let array = PayloadParser::new(&source[..len]).read_next().unwrap();
+
+    let mut buffer = [0u8; 32];
+    let mut builder = PayloadBuilder::new(&mut buffer);
+    assert!(matches!(builder.add_named("a", "", array), Err(PayloadError::InvalidType)));
+}
+
+// ========================================
+// Fixed-Point (FIXP) Argument Tests
+// ========================================
+
+#[test]
+fn test_fixed_point_layout_and_physical_value() {
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_fixed_point(1234u16, 0.5, -40).unwrap();
+        builder.len()
+    };
+
+    // Type info: FIXP | UINT | 16 bit, quantization f32, offset i32, raw u16
+    assert_eq!(&buffer[0..4], &(0x1000u32 | 0x40 | 0x02).to_le_bytes());
+    assert_eq!(&buffer[4..8], &0.5f32.to_le_bytes());
+    assert_eq!(&buffer[8..12], &(-40i32).to_le_bytes());
+    assert_eq!(&buffer[12..14], &1234u16.to_le_bytes());
+    assert_eq!(len, 14);
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    let value = match parser.read_next().unwrap() {
+        DltValue::FixedPoint(value) => value,
+        other => panic!("expected fixed point, got {:?}", other),
+    };
+    assert_eq!(value.raw, 1234);
+    assert!(!value.signed);
+    assert_eq!(value.type_length, TypeLength::Bit16);
+    assert_eq!(value.raw_value(), DltValue::U16(1234));
+    assert_eq!(value.physical(), 1234.0 * 0.5 - 40.0);
+}
+
+#[test]
+fn test_fixed_point_signed_64bit_big_endian() {
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::with_endian(&mut buffer, DltEndian::Big);
+        builder.add_fixed_point(-250i64, 0.25, 10_000_000_000).unwrap();
+        builder.len()
+    };
+    // 64-bit values carry a 64-bit offset
+    assert_eq!(len, 4 + 4 + 8 + 8);
+
+    let mut parser = PayloadParser::with_endian(&buffer[..len], DltEndian::Big);
+    let value = parser.read_fixed_point().unwrap();
+    assert_eq!(value.raw, -250);
+    assert_eq!(value.offset, 10_000_000_000);
+    assert_eq!(value.raw_value(), DltValue::I64(-250));
+    assert_eq!(value.physical(), -62.5 + 10_000_000_000.0);
+}
+
+#[test]
+fn test_fixed_point_named_and_typed_reader() {
+    let mut buffer = [0u8; 128];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_named_fixed_point("coolant", "degC", 180u8, 0.75, -48).unwrap();
+        builder.add_fixed_point(-3i32, 2.0, 1).unwrap();
+        builder.add_u8(1).unwrap();
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    let argument = parser.read_argument().unwrap();
+    assert_eq!((argument.name, argument.unit), (Some("coolant"), Some("degC")));
+    match argument.value {
+        DltValue::FixedPoint(value) => assert_eq!(value.physical(), 180.0 * 0.75 - 48.0),
+        other => panic!("expected fixed point, got {:?}", other),
+    }
+
+    // Typed readers return the raw value
+    assert_eq!(parser.read_i32().unwrap(), -3);
+    assert_eq!(parser.read_u8().unwrap(), 1);
+
+    parser.reset();
+    parser.skip_argument().unwrap();
+    parser.skip_argument().unwrap();
+    assert_eq!(parser.read_u8().unwrap(), 1);
+}
+
+#[test]
+fn test_fixed_point_reencode_parsed_value() {
+    let mut source = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut source);
+        builder.add_fixed_point(-7i8, 1.5, 3).unwrap();
+        builder.len()
+    };
+    let value = PayloadParser::new(&source[..len]).read_next().unwrap();
+
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_named("level", "", value).unwrap();
+        builder.len()
+    };
+    let argument = PayloadParser::new(&buffer[..len]).read_argument().unwrap();
+    assert_eq!(argument.name, Some("level"));
+    assert_eq!(argument.value, value);
+}
+
+#[test]
+fn test_fixed_point_errors() {
+    let mut buffer = [0u8; 64];
+    let mut builder = PayloadBuilder::new(&mut buffer);
+    assert!(matches!(builder.add_fixed_point(1u32, 1.0, i64::MAX), Err(PayloadError::InvalidData)));
+    assert!(builder.is_empty());
+
+    builder.add_u32(5).unwrap();
+    let len = builder.len();
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    assert!(matches!(parser.read_fixed_point(), Err(PayloadError::InvalidType)));
+}
+
+// ========================================
+// String Coding (SCOD) Tests
+// ========================================
+
+#[test]
+fn test_string_coding_type_info_bits() {
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_string_with_coding("ascii", DltStringCoding::Ascii).unwrap();
+        builder.add_string("Grüße").unwrap();
+        builder.len()
+    };
+
+    // add_string uses UTF-8 coding (SCOD = 1)
+    assert_eq!(&buffer[0..4], &0x0200u32.to_le_bytes());
+    let second = 4 + 2 + 6;
+    assert_eq!(&buffer[second..second + 4], &(0x0200u32 | 1 << 15).to_le_bytes());
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    let ascii = parser.read_coded_string().unwrap();
+    assert_eq!(ascii.coding(), DltStringCoding::Ascii);
+    assert_eq!(ascii.as_str(), Some("ascii"));
+    let utf8 = parser.read_coded_string().unwrap();
+    assert_eq!(utf8.coding(), DltStringCoding::Utf8);
+    assert_eq!(utf8.as_str(), Some("Grüße"));
+
+    assert_eq!(DltStringCoding::from_type_info(0x0200 | 3 << 15), DltStringCoding::Reserved(3));
+}
+
+#[test]
+fn test_string_coding_latin1_payload() {
+    // "Gr\xFC\xDFe" in Latin-1, sent with ASCII coding by a legacy ECU
+    let latin1 = b"Gr\xFC\xDFe";
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder
+            .add_coded_string(DltString::new(latin1, DltStringCoding::Ascii))
+            .unwrap();
+        builder.add_u8(7).unwrap();
+        builder.len()
+    };
+
+    // Strict UTF-8 access still fails
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    assert!(matches!(parser.read_string(), Err(PayloadError::InvalidData)));
+
+    // read_next exposes the bytes and coding instead of failing
+    parser.reset();
+    let string = match parser.read_next().unwrap() {
+        DltValue::String(string) => string,
+        other => panic!("expected coded string, got {:?}", other),
+    };
+    assert_eq!(string.as_bytes(), latin1);
+    assert_eq!(string.coding(), DltStringCoding::Ascii);
+    assert_eq!(string.as_str(), None);
+    assert!(string.chars().eq("Grüße".chars()));
+    assert_eq!(string.to_string(), "Grüße");
+    assert_eq!(parser.read_u8().unwrap(), 7);
+}
+
+#[test]
+fn test_string_coding_invalid_utf8_is_replaced() {
+    let bytes = b"ok\xFF\xFEend";
+    let string = DltString::new(bytes, DltStringCoding::Utf8);
+    assert_eq!(string.len(), 7);
+    assert_eq!(string.to_string(), "ok\u{FFFD}\u{FFFD}end");
+
+    let valid = DltString::new("Grüße".as_bytes(), DltStringCoding::Ascii);
+    assert_eq!(valid.to_string(), "Grüße");
+    assert!(DltString::new(b"", DltStringCoding::Utf8).is_empty());
+}
+
+#[test]
+fn test_string_coding_named_roundtrip() {
+    let mut source = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::with_endian(&mut source, DltEndian::Big);
+        builder
+            .add_coded_string(DltString::new(b"caf\xE9", DltStringCoding::Ascii))
+            .unwrap();
+        builder.len()
+    };
+    let value = PayloadParser::with_endian(&source[..len], DltEndian::Big)
+        .read_next()
+        .unwrap();
+
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_named("drink", "", value).unwrap();
+        builder.add_named("city", "", DltValue::String(DltString::utf8("Köln"))).unwrap();
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    let argument = parser.read_argument().unwrap();
+    assert_eq!(argument.name, Some("drink"));
+    assert_eq!(argument.value, value);
+    let argument = parser.read_argument().unwrap();
+    assert_eq!(argument.value, DltValue::String(DltString::utf8("Köln")));
+
+    parser.reset();
+    parser.skip_argument().unwrap();
+    parser.skip_argument().unwrap();
+    assert!(parser.is_empty());
+}
+
+// ========================================
+// Trace Info (TRAI) Tests
+// ========================================
+
+#[test]
+fn test_trace_info_layout() {
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_trace_info("main").unwrap();
+        builder.len()
+    };
+
+    assert_eq!(&buffer[0..4], &0x2000u32.to_le_bytes());
+    assert_eq!(&buffer[4..6], &5u16.to_le_bytes());
+    assert_eq!(&buffer[6..11], b"main\0");
+    assert_eq!(len, 11);
+    assert_eq!(
+        PayloadParser::new(&buffer[..len]).peek_type_info().unwrap(),
+        (PayloadType::TraceInfo, TypeLength::NotDefined)
+    );
+}
+
+#[test]
+fn test_trace_info_with_arguments_roundtrip() {
+    let mut buffer = [0u8; 128];
+    let len = {
+        let mut builder = PayloadBuilder::with_endian(&mut buffer, DltEndian::Big);
+        builder.add_trace_info("can_tp::send_frame").unwrap();
+        builder.add_u32(0x7DF).unwrap();
+        builder.add_string("sent").unwrap();
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::with_endian(&buffer[..len], DltEndian::Big);
+    assert_eq!(parser.read_next().unwrap(), DltValue::TraceInfo("can_tp::send_frame"));
+    assert_eq!(parser.read_u32().unwrap(), 0x7DF);
+    assert_eq!(parser.read_string().unwrap(), "sent");
+
+    parser.reset();
+    assert_eq!(parser.read_trace_info().unwrap(), "can_tp::send_frame");
+    parser.reset();
+    parser.skip_argument().unwrap();
+    assert_eq!(parser.read_u32().unwrap(), 0x7DF);
+}
+
+#[test]
+fn test_trace_info_type_mismatch() {
+    let mut buffer = [0u8; 64];
+    let len = {
+        let mut builder = PayloadBuilder::new(&mut buffer);
+        builder.add_string("not trace info").unwrap();
+        assert!(matches!(
+            builder.add_named("name", "", DltValue::TraceInfo("main")),
+            Err(PayloadError::InvalidType)
+        ));
+        builder.len()
+    };
+
+    let mut parser = PayloadParser::new(&buffer[..len]);
+    assert!(matches!(parser.read_trace_info(), Err(PayloadError::InvalidType)));
+}
+
+// ========================================
+// Non-Verbose Mode Decoding Tests
+// ========================================
+
+const NV_FRAMES: &[DltNonVerboseFrame] = &[
+    DltNonVerboseFrame {
+        message_id: 10,
+        app_id: *b"APP1",
+        context_id: *b"CTX1",
+        log_level: MtinTypeDltLog::DltLogWarn,
+        text: "Coolant temperature",
+        args: &[DltArgType::I16, DltArgType::AsciiString, DltArgType::Bool],
+    },
+    DltNonVerboseFrame {
+        message_id: 11,
+        app_id: *b"APP1",
+        context_id: *b"CTX1",
+        log_level: MtinTypeDltLog::DltLogInfo,
+        text: "Frame dump",
+        args: &[DltArgType::U32, DltArgType::Raw],
+    },
+];
+const NV_CATALOG: DltFrameCatalog = DltFrameCatalog::new(NV_FRAMES);
+
+/// Wrap a non-verbose payload into a complete message
+fn non_verbose_message(buffer: &mut [u8], payload: &[u8], endian: DltEndian) -> usize {
+    let mut builder = DltMessageBuilder::new()
+        .with_ecu_id(b"ECU1")
+        .with_app_id(b"APP1")
+        .with_context_id(b"CTX1");
+    builder.set_endian(endian);
+    builder
+        .generate_log_message_with_payload(buffer, payload, MtinTypeDltLog::DltLogInfo, 0, false)
+        .unwrap()
+}
+
+#[test]
+fn test_non_verbose_decode_little_endian() {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&10u32.to_le_bytes());
+    payload.extend_from_slice(&(-12i16).to_le_bytes());
+    payload.extend_from_slice(&4u16.to_le_bytes());
+    payload.extend_from_slice(b"low\0");
+    payload.push(1);
+
+    let mut buffer = [0u8; 128];
+    let size = non_verbose_message(&mut buffer, &payload, DltEndian::Little);
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+
+    assert_eq!(read_message_id(&message).unwrap(), 10);
+    let decoded = decode_non_verbose(&NV_CATALOG, &message).unwrap();
+    assert_eq!(decoded.message_id(), 10);
+    assert_eq!(decoded.text(), "Coolant temperature");
+    assert_eq!(decoded.frame().log_level, MtinTypeDltLog::DltLogWarn);
+
+    let args: Vec<_> = decoded.args().map(Result::unwrap).collect();
+    assert_eq!(args, [DltValue::I16(-12), DltValue::String(DltString::ascii("low")), DltValue::Bool(true)]);
+}
+
+#[test]
+fn test_non_verbose_decode_big_endian() {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&11u32.to_be_bytes());
+    payload.extend_from_slice(&0xDEADBEEFu32.to_be_bytes());
+    payload.extend_from_slice(&3u16.to_be_bytes());
+    payload.extend_from_slice(&[1, 2, 3]);
+
+    let mut buffer = [0u8; 128];
+    let size = non_verbose_message(&mut buffer, &payload, DltEndian::Big);
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+
+    let decoded = decode_non_verbose(&NV_CATALOG, &message).unwrap();
+    let mut args = decoded.args();
+    assert_eq!(args.next().unwrap().unwrap(), DltValue::U32(0xDEADBEEF));
+    assert_eq!(args.next().unwrap().unwrap(), DltValue::Raw(&[1, 2, 3]));
+    assert!(args.next().is_none());
+    assert!(args.remaining().is_empty());
+}
+
+#[test]
+fn test_non_verbose_args_match_verbose_values() {
+    // Raw data ending in a zero byte: no terminator in either mode
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&11u32.to_le_bytes());
+    payload.extend_from_slice(&7u32.to_le_bytes());
+    payload.extend_from_slice(&3u16.to_le_bytes());
+    payload.extend_from_slice(&[1, 2, 0]);
+
+    let mut buffer = [0u8; 128];
+    let size = non_verbose_message(&mut buffer, &payload, DltEndian::Little);
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    let decoded = decode_non_verbose(&NV_CATALOG, &message).unwrap();
+
+    let mut verbose = [0u8; 64];
+    let verbose_len = {
+        let mut builder = PayloadBuilder::new(&mut verbose);
+        builder.add_u32(7).unwrap();
+        builder.add_raw(&[1, 2, 0]).unwrap();
+        builder.len()
+    };
+    let mut parser = PayloadParser::new(&verbose[..verbose_len]);
+    for arg in decoded.args() {
+        assert_eq!(arg.unwrap(), parser.read_next().unwrap());
+    }
+    assert!(parser.is_empty());
+}
+
+#[test]
+fn test_non_verbose_decode_errors() {
+    let mut buffer = [0u8; 128];
+
+    // Unknown message ID
+    let size = non_verbose_message(&mut buffer, &99u32.to_le_bytes(), DltEndian::Little);
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert!(matches!(
+        decode_non_verbose(&NV_CATALOG, &message),
+        Err(PayloadError::UnknownMessageId(99))
+    ));
+
+    // Truncated arguments: error is yielded once, then the iterator ends
+    let mut payload = 10u32.to_le_bytes().to_vec();
+    payload.push(0x01);
+    let size = non_verbose_message(&mut buffer, &payload, DltEndian::Little);
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    let mut args = decode_non_verbose(&NV_CATALOG, &message).unwrap().args();
+    assert!(matches!(args.next(), Some(Err(PayloadError::BufferTooSmall))));
+    assert!(args.next().is_none());
+
+    // Verbose messages are rejected
+    let mut builder = DltMessageBuilder::new();
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, b"text", MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap();
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert!(matches!(read_message_id(&message), Err(PayloadError::InvalidType)));
+}
+
+// ========================================
+// Non-Verbose Mode Generation Tests
+// ========================================
+
+#[test]
+fn test_non_verbose_generate_roundtrip() {
+    for endian in [DltEndian::Little, DltEndian::Big] {
+        let mut builder = DltMessageBuilder::new()
+            .with_app_id(b"APP1")
+            .with_context_id(b"CTX1");
+        builder.set_endian(endian);
+
+        let mut buffer = [0u8; 128];
+        let args = [DltValue::I16(-12), DltValue::String(DltString::ascii("low")), DltValue::Bool(true)];
+        let size = builder
+            .generate_non_verbose_message(&mut buffer, &NV_FRAMES[0], &args)
+            .unwrap();
+
+        let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+        let ext = message.extended_header.unwrap();
+        assert!(!ext.is_verbose());
+        assert_eq!(ext.noar, 3);
+        assert_eq!(ext.log_level(), Some(MtinTypeDltLog::DltLogWarn));
+        // Message ID (4) + i16 (2) + length (2) + "low\0" (4) + bool (1), no type info
+        assert_eq!(message.payload.len(), 13);
+
+        let decoded = decode_non_verbose(&NV_CATALOG, &message).unwrap();
+        let decoded: Vec<_> = decoded.args().map(Result::unwrap).collect();
+        assert_eq!(decoded, args);
+    }
+}
+
+#[test]
+fn test_non_verbose_generate_by_id() {
+    let mut builder = DltMessageBuilder::new();
+    let mut buffer = [0u8; 128];
+    let size = builder
+        .generate_non_verbose_message_by_id(
+            &mut buffer,
+            &NV_CATALOG,
+            11,
+            &[DltValue::U32(7), DltValue::Raw(&[0xAA, 0xBB])],
+        )
+        .unwrap();
+
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert_eq!(&message.payload[..4], &11u32.to_le_bytes());
+    assert_eq!(&message.payload[8..], &[2, 0, 0xAA, 0xBB]);
+
+    assert!(matches!(
+        builder.generate_non_verbose_message_by_id(&mut buffer, &NV_CATALOG, 12, &[]),
+        Err(DltError::InvalidParameter)
+    ));
+}
+
+#[test]
+fn test_non_verbose_generate_uses_frame_ids() {
+    let mut builder = DltMessageBuilder::new()
+        .with_app_id(b"BLDR")
+        .with_context_id(b"BCTX")
+        .with_session_id(0x1122_3344)
+        .with_timestamp(0x5566_7788);
+    let mut buffer = [0u8; 128];
+
+    let frame = &NV_FRAMES[1];
+    let args = [DltValue::U32(7), DltValue::Raw(&[1])];
+    let size = builder.generate_non_verbose_message(&mut buffer, frame, &args).unwrap();
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    let ext = message.extended_header.unwrap();
+    assert_eq!(ext.apid, frame.app_id);
+    assert_eq!(ext.ctid, frame.context_id);
+    assert_eq!(ext.log_level(), Some(frame.log_level));
+    assert_eq!(message.session_id, Some(0x1122_3344));
+    assert_eq!(message.timestamp, Some(0x5566_7788));
+    assert_eq!(message.standard_header.mcnt, 0);
+
+    // Frames without IDs (e.g. from a FIBEX file without them) keep the builder's
+    let anonymous = DltNonVerboseFrame { app_id: [0; 4], context_id: *b"FCTX", ..*frame };
+    let size = builder.generate_non_verbose_message(&mut buffer, &anonymous, &args).unwrap();
+    let ext = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap().extended_header.unwrap();
+    assert_eq!(ext.apid, *b"BLDR");
+    assert_eq!(ext.ctid, *b"FCTX");
+
+    // The message counter keeps advancing across frames
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert_eq!(message.standard_header.mcnt, 1);
+}
+
+#[test]
+fn test_non_verbose_generate_argument_mismatch() {
+    let mut builder = DltMessageBuilder::new();
+    let mut buffer = [0u8; 128];
+    let frame = &NV_FRAMES[1];
+
+    // Wrong count and wrong type
+    assert!(matches!(
+        builder.generate_non_verbose_message(&mut buffer, frame, &[DltValue::U32(7)]),
+        Err(DltError::InvalidParameter)
+    ));
+    assert!(matches!(
+        builder.generate_non_verbose_message(&mut buffer, frame, &[DltValue::U16(7), DltValue::Raw(&[])]),
+        Err(DltError::InvalidParameter)
+    ));
+
+    // Buffer too small for the payload
+    let mut small = [0u8; 24];
+    assert!(matches!(
+        builder.generate_non_verbose_message(&mut small, frame, &[DltValue::U32(7), DltValue::Raw(&[0; 8])]),
+        Err(DltError::BufferTooSmall)
+    ));
+    assert_eq!(
+        write_non_verbose_payload(&mut buffer, frame, &[DltValue::U32(7), DltValue::Raw(&[1])], DltEndian::Big)
+            .unwrap(),
+        4 + 4 + 2 + 1
+    );
+}
+
+// ========================================
+// Trace Message Type Tests
+// ========================================
+
+#[test]
+fn test_trace_mtin_parse_roundtrip() {
+    let app = [
+        (1, MtinTypeDltAppTrace::DltTraceVariable),
+        (2, MtinTypeDltAppTrace::DltTraceFunctionIn),
+        (3, MtinTypeDltAppTrace::DltTraceFunctionOut),
+        (4, MtinTypeDltAppTrace::DltTraceState),
+        (5, MtinTypeDltAppTrace::DltTraceVfb),
+    ];
+    for (bits, trace_type) in app {
+        assert_eq!(MtinTypeDltAppTrace::parse(bits), trace_type);
+        assert_eq!(trace_type.to_bits(), bits);
+    }
+    assert_eq!(MtinTypeDltAppTrace::parse(6), MtinTypeDltAppTrace::Reserved(6));
+    assert_eq!(MtinTypeDltAppTrace::parse(0), MtinTypeDltAppTrace::Invalid(0));
+
+    let nw = [
+        (1, MtinTypeDltNwTrace::DltNwTraceIpc),
+        (2, MtinTypeDltNwTrace::DltNwTraceCan),
+        (3, MtinTypeDltNwTrace::DltNwTraceFlexray),
+        (4, MtinTypeDltNwTrace::DltNwTraceMost),
+        (5, MtinTypeDltNwTrace::DltNwTraceEthernet),
+        (6, MtinTypeDltNwTrace::DltNwTraceSomeip),
+    ];
+    for (bits, trace_type) in nw {
+        assert_eq!(MtinTypeDltNwTrace::parse(bits), trace_type);
+        assert_eq!(trace_type.to_bits(), bits);
+    }
+    assert_eq!(MtinTypeDltNwTrace::parse(0x0F), MtinTypeDltNwTrace::UserDefined(0x0F));
+    assert_eq!(MtinTypeDltNwTrace::parse(0), MtinTypeDltNwTrace::Invalid(0));
+
+    assert!(matches!(
+        Mtin::parse(&MstpType::DltTypeNwTrace, 5),
+        Mtin::NwTrace(MtinTypeDltNwTrace::DltNwTraceEthernet)
+    ));
+}
+
+#[test]
+fn test_generate_app_trace_message() {
+    let mut builder = DltMessageBuilder::new()
+        .with_app_id(b"APP1")
+        .with_context_id(b"CTX1");
+    let mut payload = [0u8; 64];
+    let payload_len = {
+        let mut args = builder.payload_builder(&mut payload);
+        args.add_trace_info("engine::start").unwrap();
+        args.add_u8(3).unwrap();
+        args.len()
+    };
+
+    let mut buffer = [0u8; 128];
+    let size = builder
+        .generate_app_trace_message(
+            &mut buffer,
+            &payload[..payload_len],
+            MtinTypeDltAppTrace::DltTraceFunctionIn,
+            2,
+            true,
+        )
+        .unwrap();
+
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    let ext = message.extended_header.unwrap();
+    assert_eq!(ext.message_type(), MstpType::DltTypeAppTrace);
+    assert_eq!(ext.app_trace_type(), Some(MtinTypeDltAppTrace::DltTraceFunctionIn));
+    assert_eq!(ext.log_level(), None);
+    assert_eq!(ext.nw_trace_type(), None);
+    assert!(ext.is_verbose());
+    assert_eq!(ext.noar, 2);
+
+    let mut parser = PayloadParser::from_message(&message);
+    assert_eq!(parser.read_trace_info().unwrap(), "engine::start");
+    assert_eq!(parser.read_u8().unwrap(), 3);
+}
+
+#[test]
+fn test_generate_network_trace_message() {
+    let mut builder = DltMessageBuilder::new();
+    builder.set_endian(DltEndian::Big);
+    let can_id = 0x7DFu32.to_be_bytes();
+    let frame = [0x02, 0x01, 0x0C];
+
+    // Verbose: header and frame as two raw arguments
+    let mut buffer = [0u8; 128];
+    let size = builder
+        .generate_network_trace_message(&mut buffer, MtinTypeDltNwTrace::DltNwTraceCan, &can_id, &frame, true)
+        .unwrap();
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    let ext = message.extended_header.unwrap();
+    assert_eq!(ext.nw_trace_type(), Some(MtinTypeDltNwTrace::DltNwTraceCan));
+    assert_eq!(ext.noar, 2);
+    // RAWD type info (big endian), 16-bit length and data, no terminator
+    assert_eq!(
+        message.payload,
+        &[0, 0, 0x04, 0, 0, 4, 0, 0, 0x07, 0xDF, 0, 0, 0x04, 0, 0, 3, 0x02, 0x01, 0x0C]
+    );
+    let mut parser = PayloadParser::from_message(&message);
+    assert_eq!(parser.read_raw().unwrap(), &can_id);
+    assert_eq!(parser.read_raw().unwrap(), &frame);
+
+    // Non-verbose: 16-bit length + data for each part, in message byte order
+    let size = builder
+        .generate_network_trace_message(&mut buffer, MtinTypeDltNwTrace::DltNwTraceCan, &can_id, &frame, false)
+        .unwrap();
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert!(!message.extended_header.unwrap().is_verbose());
+    assert_eq!(message.payload, &[0, 4, 0, 0, 0x07, 0xDF, 0, 3, 0x02, 0x01, 0x0C]);
+
+    let mut small = [0u8; 30];
+    assert_eq!(
+        builder.generate_network_trace_message(&mut small, MtinTypeDltNwTrace::DltNwTraceIpc, &[], &[0; 16], false),
+        Err(DltError::BufferTooSmall)
+    );
+}
+
+#[test]
+fn test_network_trace_message_read_next() {
+    let mut builder = DltMessageBuilder::new();
+    let header = [0x00, 0x00, 0x07, 0xDF];
+    let frame = [0x02, 0x01, 0x00];
+    let mut buffer = [0u8; 128];
+    let size = builder
+        .generate_network_trace_message(&mut buffer, MtinTypeDltNwTrace::DltNwTraceCan, &header, &frame, true)
+        .unwrap();
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+
+    let mut parser = PayloadParser::from_message(&message);
+    assert_eq!(parser.read_next().unwrap(), DltValue::Raw(&header));
+    assert_eq!(parser.read_next().unwrap(), DltValue::Raw(&frame));
+    assert!(parser.is_empty());
+}
+
+// ========================================
+// Segmented Network Trace Tests
+// ========================================
+
+/// Generate all messages of a segmented transfer, one buffer per message
+fn segmented_messages(segmenter: &mut DltNwSegmenter, builder: &mut DltMessageBuilder) -> Vec<Vec<u8>> {
+    let mut messages = Vec::new();
+    let mut buffer = [0u8; 256];
+    while let Some(size) = segmenter.next_message(builder, &mut buffer) {
+        messages.push(buffer[..size.unwrap()].to_vec());
+    }
+    messages
+}
+
+#[test]
+fn test_nw_segmenter_messages() {
+    let payload: Vec<u8> = (0..250u8).collect();
+    let header = [0xAA, 0xBB];
+    let mut builder = DltMessageBuilder::new().with_app_id(b"APP1").with_context_id(b"ETH0");
+    let mut segmenter = DltNwSegmenter::new(MtinTypeDltNwTrace::DltNwTraceEthernet, 0xCAFE, &header, &payload)
+        .with_segment_size(100);
+    assert_eq!(segmenter.segment_count(), 3);
+    assert_eq!(segmenter.message_count(), 5);
+
+    let messages = segmented_messages(&mut segmenter, &mut builder);
+    assert!(segmenter.is_finished());
+    assert_eq!(messages.len(), 5);
+
+    fn parse(data: &[u8]) -> (u8, DltNwSegment<'_>) {
+        let message = DltHeaderParser::new(data).parse_message().unwrap();
+        let ext = message.extended_header.unwrap();
+        assert_eq!(ext.nw_trace_type(), Some(MtinTypeDltNwTrace::DltNwTraceEthernet));
+        assert!(ext.is_verbose());
+        (ext.noar, parse_nw_segment(&message).unwrap().unwrap())
+    }
+
+    let (noar, start) = parse(&messages[0]);
+    assert_eq!(noar, 6);
+    assert_eq!(
+        start,
+        DltNwSegment::Start {
+            handle: 0xCAFE,
+            header: &header,
+            payload_length: 250,
+            segment_count: 3,
+            segment_size: 100,
+        }
+    );
+
+    for (sequence, message) in messages[1..4].iter().enumerate() {
+        let (noar, chunk) = parse(message);
+        assert_eq!(noar, 4);
+        let start = sequence * 100;
+        let end = (start + 100).min(payload.len());
+        assert_eq!(
+            chunk,
+            DltNwSegment::Chunk {
+                handle: 0xCAFE,
+                sequence: sequence as u16,
+                data: &payload[start..end],
+            }
+        );
+    }
+
+    let (noar, end) = parse(&messages[4]);
+    assert_eq!(noar, 2);
+    assert_eq!(end, DltNwSegment::End { handle: 0xCAFE });
+    assert_eq!(end.handle(), 0xCAFE);
+}
+
+#[test]
+fn test_nw_segmenter_exact_multiple_and_big_endian() {
+    let payload = [7u8; 64];
+    let mut builder = DltMessageBuilder::new();
+    builder.set_endian(DltEndian::Big);
+    let mut segmenter =
+        DltNwSegmenter::new(MtinTypeDltNwTrace::DltNwTraceSomeip, 1, &[], &payload).with_segment_size(32);
+
+    // No empty trailing segment when the payload is a multiple of the segment size
+    let messages = segmented_messages(&mut segmenter, &mut builder);
+    assert_eq!(messages.len(), 4);
+
+    let message = DltHeaderParser::new(&messages[2]).parse_message().unwrap();
+    assert!(message.header_type.MSBF);
+    assert_eq!(
+        parse_nw_segment(&message).unwrap(),
+        Some(DltNwSegment::Chunk { handle: 1, sequence: 1, data: &payload[32..] })
+    );
+}
+
+#[test]
+fn test_nw_segmenter_buffer_too_small_can_retry() {
+    let payload = [1u8; 100];
+    let mut builder = DltMessageBuilder::new();
+    let mut segmenter =
+        DltNwSegmenter::new(MtinTypeDltNwTrace::DltNwTraceCan, 2, &[], &payload).with_segment_size(50);
+
+    let mut buffer = [0u8; 256];
+    assert!(segmenter.next_message(&mut builder, &mut buffer).unwrap().is_ok());
+
+    let mut small = [0u8; 40];
+    assert_eq!(segmenter.next_message(&mut builder, &mut small), Some(Err(DltError::BufferTooSmall)));
+
+    // The failed chunk is generated again
+    let size = segmenter.next_message(&mut builder, &mut buffer).unwrap().unwrap();
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert!(matches!(
+        parse_nw_segment(&message).unwrap(),
+        Some(DltNwSegment::Chunk { sequence: 0, .. })
+    ));
+}
+
+/// Ethernet frame traced by dlt-daemon's `dlt_user_trace_network_segmented()`
+/// (ECU1/APP1/ETH0, handle 0x0007A120, 4-byte header, 12-byte payload)
+const DAEMON_NWST: [u8; 75] = [
+    0x3D, 0x00, 0x00, 0x4B, b'E', b'C', b'U', b'1', 0x00, 0x00, 0x04, 0xD2, 0x00, 0x01, 0xE2, 0x40,
+    0x55, 0x06, b'A', b'P', b'P', b'1', b'E', b'T', b'H', b'0',
+    0x00, 0x02, 0x00, 0x00, 0x05, 0x00, b'N', b'W', b'S', b'T', 0x00,
+    0x43, 0x00, 0x00, 0x00, 0x20, 0xA1, 0x07, 0x00,
+    0x00, 0x04, 0x00, 0x00, 0x04, 0x00, 0xDE, 0xAD, 0xBE, 0xEF,
+    0x43, 0x00, 0x00, 0x00, 0x0C, 0x00, 0x00, 0x00,
+    0x42, 0x00, 0x00, 0x00, 0x01, 0x00,
+    0x42, 0x00, 0x00, 0x00, 0xF2, 0x03,
+];
+const DAEMON_NWCH: [u8; 69] = [
+    0x3D, 0x01, 0x00, 0x45, b'E', b'C', b'U', b'1', 0x00, 0x00, 0x04, 0xD2, 0x00, 0x01, 0xE2, 0x41,
+    0x55, 0x04, b'A', b'P', b'P', b'1', b'E', b'T', b'H', b'0',
+    0x00, 0x02, 0x00, 0x00, 0x05, 0x00, b'N', b'W', b'C', b'H', 0x00,
+    0x43, 0x00, 0x00, 0x00, 0x20, 0xA1, 0x07, 0x00,
+    0x42, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x04, 0x00, 0x00, 0x0C, 0x00, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB,
+];
+const DAEMON_NWEN: [u8; 45] = [
+    0x3D, 0x02, 0x00, 0x2D, b'E', b'C', b'U', b'1', 0x00, 0x00, 0x04, 0xD2, 0x00, 0x01, 0xE2, 0x42,
+    0x55, 0x02, b'A', b'P', b'P', b'1', b'E', b'T', b'H', b'0',
+    0x00, 0x02, 0x00, 0x00, 0x05, 0x00, b'N', b'W', b'E', b'N', 0x00,
+    0x43, 0x00, 0x00, 0x00, 0x20, 0xA1, 0x07, 0x00,
+];
+const DAEMON_NW_HEADER: [u8; 4] = [0xDE, 0xAD, 0xBE, 0xEF];
+const DAEMON_NW_PAYLOAD: [u8; 12] = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB];
+
+#[test]
+fn test_nw_segment_dlt_daemon_interop() {
+    let parsed: Vec<_> = [&DAEMON_NWST[..], &DAEMON_NWCH, &DAEMON_NWEN]
+        .map(|data| DltHeaderParser::new(data).parse_message().unwrap())
+        .to_vec();
+    let segments: Vec<_> = parsed.iter().map(|m| parse_nw_segment(m).unwrap().unwrap()).collect();
+    assert_eq!(
+        segments,
+        [
+            DltNwSegment::Start {
+                handle: 0x0007_A120,
+                header: &DAEMON_NW_HEADER,
+                payload_length: 12,
+                segment_count: 1,
+                segment_size: DLT_MAX_TRACE_SEGMENT_SIZE,
+            },
+            DltNwSegment::Chunk { handle: 0x0007_A120, sequence: 0, data: &DAEMON_NW_PAYLOAD },
+            DltNwSegment::End { handle: 0x0007_A120 },
+        ]
+    );
+
+    // The segmenter writes the same arguments as dlt-daemon
+    let mut builder = DltMessageBuilder::new()
+        .with_ecu_id(b"ECU1")
+        .with_app_id(b"APP1")
+        .with_context_id(b"ETH0");
+    let mut segmenter = DltNwSegmenter::new(
+        MtinTypeDltNwTrace::DltNwTraceEthernet,
+        0x0007_A120,
+        &DAEMON_NW_HEADER,
+        &DAEMON_NW_PAYLOAD,
+    );
+    let messages = segmented_messages(&mut segmenter, &mut builder);
+    assert_eq!(messages.len(), parsed.len());
+    for (data, daemon) in messages.iter().zip(&parsed) {
+        let message = DltHeaderParser::new(data).parse_message().unwrap();
+        assert_eq!(message.extended_header, daemon.extended_header);
+        assert_eq!(message.payload, daemon.payload);
+    }
+}
+
+#[test]
+fn test_nw_segment_read_next() {
+    // Segments are ordinary verbose arguments, readable by generic payload dumps
+    let handle = DltValue::U32(0x0007_A120);
+    let expected: [&[DltValue]; 3] = [
+        &[
+            DltValue::String(DltString::ascii("NWST")),
+            handle.clone(),
+            DltValue::Raw(&DAEMON_NW_HEADER),
+            DltValue::U32(12),
+            DltValue::U16(1),
+            DltValue::U16(DLT_MAX_TRACE_SEGMENT_SIZE),
+        ],
+        &[
+            DltValue::String(DltString::ascii("NWCH")),
+            handle.clone(),
+            DltValue::U16(0),
+            DltValue::Raw(&DAEMON_NW_PAYLOAD),
+        ],
+        &[DltValue::String(DltString::ascii("NWEN")), handle],
+    ];
+
+    let mut builder = DltMessageBuilder::new();
+    let mut segmenter = DltNwSegmenter::new(
+        MtinTypeDltNwTrace::DltNwTraceEthernet,
+        0x0007_A120,
+        &DAEMON_NW_HEADER,
+        &DAEMON_NW_PAYLOAD,
+    );
+    let generated = segmented_messages(&mut segmenter, &mut builder);
+    let daemon = [&DAEMON_NWST[..], &DAEMON_NWCH, &DAEMON_NWEN];
+    let messages = daemon.into_iter().chain(generated.iter().map(Vec::as_slice));
+    for (data, values) in messages.zip(expected.iter().cycle()) {
+        let message = DltHeaderParser::new(data).parse_message().unwrap();
+        let mut parser = PayloadParser::from_message(&message);
+        for value in *values {
+            assert_eq!(&parser.read_next().unwrap(), value);
+        }
+        assert!(parser.is_empty());
+    }
+}
+
+#[test]
+fn test_parse_nw_segment_ignores_other_messages() {
+    let mut builder = DltMessageBuilder::new();
+    let mut buffer = [0u8; 128];
+
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, b"NWST", MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap();
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert_eq!(parse_nw_segment(&message).unwrap(), None);
+
+    // Plain (unsegmented) network trace
+    let size = builder
+        .generate_network_trace_message(&mut buffer, MtinTypeDltNwTrace::DltNwTraceCan, &[1], &[2], true)
+        .unwrap();
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert_eq!(parse_nw_segment(&message).unwrap(), None);
+
+    // Identifier present but arguments missing
+    let mut payload = [0u8; 32];
+    let len = {
+        let mut args = PayloadBuilder::new(&mut payload);
+        args.add_string(DLT_TRACE_NW_SEGMENT).unwrap();
+        args.len()
+    };
+    let size = builder
+        .generate_message_with_payload(
+            &mut buffer,
+            &payload[..len],
+            MstpType::DltTypeNwTrace,
+            MtinTypeDltNwTrace::DltNwTraceCan.to_bits(),
+            1,
+            true,
+        )
+        .unwrap();
+    let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert!(parse_nw_segment(&message).is_err());
+}
+
+// ========================================
+// Serial Stream Scanner Tests
+// ========================================
+
+/// Generate a log message with serial header and a string payload
+fn serial_message(builder: &mut DltMessageBuilder, text: &str) -> Vec<u8> {
+    let mut buffer = [0u8; 256];
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, text.as_bytes(), MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap();
+    buffer[..size].to_vec()
+}
+
+fn frame_text<'a>(frame: &DltSerialFrame<'a>) -> &'a str {
+    PayloadParser::from_message(&frame.message).read_string().unwrap()
+}
+
+#[test]
+fn test_check_message_header() {
+    let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1").add_serial_header();
+    let message = serial_message(&mut builder, "hello");
+    let header = &message[DLT_SERIAL_HEADER_SIZE..];
+    assert_eq!(check_message_header(header), Ok(header.len()));
+    assert_eq!(check_message_header(&header[..2]), Err(DltHeaderError::BufferTooSmall));
+
+    let mut bad = header.to_vec();
+    bad[0] = (bad[0] & !VERS_MASK) | (2 << 5);
+    assert_eq!(check_message_header(&bad), Err(DltHeaderError::InvalidVersion));
+
+    let mut bad = header.to_vec();
+    bad[2..4].copy_from_slice(&10u16.to_be_bytes());
+    assert_eq!(check_message_header(&bad), Err(DltHeaderError::InvalidLength));
+
+    // Extended header with reserved message type
+    let mut bad = header.to_vec();
+    let msin_offset = DLT_STANDARD_HEADER_SIZE + DLT_ID_SIZE + 8;
+    bad[msin_offset] = encode_msin(true, 5, 1);
+    assert_eq!(check_message_header(&bad), Err(DltHeaderError::InvalidMessageType));
+    // Log message with log level 0
+    bad[msin_offset] = encode_msin(true, 0, 0);
+    assert_eq!(check_message_header(&bad), Err(DltHeaderError::InvalidMessageType));
+}
+
+#[test]
+fn test_serial_scanner_skips_garbage() {
+    let mut builder = DltMessageBuilder::new().add_serial_header();
+    let mut stream = vec![0x00, 0xFF, 0x44, 0x4C];
+    stream.extend(serial_message(&mut builder, "first"));
+    // False sync: serial header followed by an invalid version
+    stream.extend([0x44, 0x4C, 0x53, 0x01, 0xE0, 0x00, 0x00]);
+    stream.extend(serial_message(&mut builder, "second"));
+    stream.extend(serial_message(&mut builder, "third"));
+
+    let mut scanner = DltSerialScanner::new(&stream);
+    let first = scanner.next_frame().unwrap();
+    assert_eq!(first.skipped, 4);
+    assert!(first.message.has_serial_header);
+    assert!(first.data.starts_with(&DLT_SERIAL_HEADER_ARRAY));
+    assert_eq!(frame_text(&first), "first");
+
+    let second = scanner.next_frame().unwrap();
+    assert_eq!(second.skipped, 7);
+    assert_eq!(frame_text(&second), "second");
+
+    let third = scanner.next_frame().unwrap();
+    assert_eq!(third.skipped, 0);
+    assert_eq!(frame_text(&third), "third");
+
+    assert!(scanner.next_frame().is_none());
+    assert_eq!(scanner.skipped_bytes(), 11);
+    assert_eq!(scanner.remaining(), 0);
+}
+
+#[test]
+fn test_serial_scanner_dropped_byte() {
+    let mut builder = DltMessageBuilder::new().add_serial_header();
+    let mut damaged = serial_message(&mut builder, "lost a byte");
+    damaged.remove(damaged.len() - 3);
+    let next = serial_message(&mut builder, "intact");
+
+    let mut stream = damaged.clone();
+    stream.extend(&next);
+
+    // The damaged message would swallow the start of the next one; it's discarded instead
+    let frames: Vec<_> = DltSerialScanner::new(&stream).collect();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].skipped, damaged.len());
+    assert_eq!(frame_text(&frames[0]), "intact");
+    assert_eq!(frames[0].data, &next[..]);
+}
+
+#[test]
+fn test_serial_scanner_keeps_message_with_nested_frame() {
+    let mut builder = DltMessageBuilder::new().add_serial_header();
+    let forwarded = serial_message(&mut builder, "forwarded");
+
+    // Dump of serial traffic: the payload contains a complete serial frame
+    let mut buffer = [0u8; 256];
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, &forwarded, MtinTypeDltLog::DltLogInfo, 0, false)
+        .unwrap();
+    let dump = buffer[..size].to_vec();
+
+    let mut stream = dump.clone();
+    stream.extend(serial_message(&mut builder, "after"));
+    stream.extend(&dump);
+
+    let frames: Vec<_> = DltSerialScanner::new(&stream).collect();
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0].data, &dump[..]);
+    assert!(frames[0].message.payload.windows(forwarded.len()).any(|w| w == forwarded));
+    assert_eq!(frame_text(&frames[1]), "after");
+    assert_eq!(frames[2].data, &dump[..]);
+    assert!(frames.iter().all(|frame| frame.skipped == 0));
+}
+
+#[test]
+fn test_serial_scanner_false_sync_with_large_length() {
+    let mut builder = DltMessageBuilder::new().add_serial_header();
+    // Garbage that looks like a serial frame claiming far more data than received
+    let mut garbage = serial_message(&mut builder, "garbage");
+    garbage[DLT_SERIAL_HEADER_SIZE + 2..DLT_SERIAL_HEADER_SIZE + 4].copy_from_slice(&0xF000u16.to_be_bytes());
+    let first = serial_message(&mut builder, "first");
+    let second = serial_message(&mut builder, "second");
+
+    let mut stream = garbage.clone();
+    stream.extend(&first);
+    stream.extend(&second);
+
+    // Frames behind the false sync are delivered without waiting for its length
+    let mut scanner = DltSerialScanner::new(&stream);
+    let frame = scanner.next_frame().unwrap();
+    assert_eq!(frame.skipped, garbage.len());
+    assert_eq!(frame.data, &first[..]);
+    assert_eq!(frame_text(&scanner.next_frame().unwrap()), "second");
+    assert!(scanner.next_frame().is_none());
+    assert_eq!(scanner.remaining(), 0);
+
+    // An incomplete frame behind the false sync is kept for the next read
+    let mut stream = garbage.clone();
+    stream.extend(&first[..10]);
+    let mut scanner = DltSerialScanner::new(&stream);
+    assert!(scanner.next_frame().is_none());
+    assert_eq!(scanner.position(), 0);
+}
+
+#[test]
+fn test_serial_scanner_incomplete_tail() {
+    let mut builder = DltMessageBuilder::new().add_serial_header();
+    let first = serial_message(&mut builder, "complete");
+    let second = serial_message(&mut builder, "incomplete");
+
+    let mut stream = first.clone();
+    stream.extend(&second[..10]);
+    let mut scanner = DltSerialScanner::new(&stream);
+    assert!(scanner.next_frame().is_some());
+    assert!(scanner.next_frame().is_none());
+    assert_eq!(scanner.position(), first.len());
+    assert_eq!(scanner.remaining(), 10);
+    assert_eq!(scanner.skipped_bytes(), 0);
+
+    // A trailing partial serial header is kept, other garbage is dropped
+    let stream = [0x01, 0x02, 0x44, 0x4C, 0x53];
+    let mut scanner = DltSerialScanner::new(&stream);
+    assert!(scanner.next_frame().is_none());
+    assert_eq!(scanner.position(), 2);
+    assert_eq!(scanner.skipped_bytes(), 2);
+}
+
+// ========================================
+// Stream Decoder Tests
+// ========================================
+
+/// Generate a verbose log message carrying `text`
+fn stream_message(builder: &mut DltMessageBuilder, text: &str) -> Vec<u8> {
+    let mut buffer = [0u8; 512];
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, text.as_bytes(), MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap();
+    buffer[..size].to_vec()
+}
+
+/// Take all currently complete messages as text, panicking on errors
+fn drain_texts<const N: usize>(decoder: &mut DltStreamDecoder<N>) -> Vec<String> {
+    let mut texts = Vec::new();
+    while let Some(message) = decoder.next_message().unwrap() {
+        texts.push(PayloadParser::from_message(&message).read_string().unwrap().to_string());
+    }
+    texts
+}
+
+#[test]
+fn test_stream_decoder_split_reads() {
+    let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1");
+    let mut stream = stream_message(&mut builder, "one");
+    stream.extend(stream_message(&mut builder, "two"));
+    stream.extend(stream_message(&mut builder, "three"));
+
+    // Byte by byte
+    let mut decoder = DltStreamDecoder::<128>::new();
+    let mut texts = Vec::new();
+    for byte in &stream {
+        assert_eq!(decoder.push(&[*byte]), 1);
+        texts.extend(drain_texts(&mut decoder));
+    }
+    assert_eq!(texts, ["one", "two", "three"]);
+    assert_eq!(decoder.buffered(), 0);
+
+    // Arbitrary chunk sizes, several messages per chunk
+    for chunk_size in [5, 17, 40, stream.len()] {
+        let mut decoder = DltStreamDecoder::<128>::new();
+        let mut texts = Vec::new();
+        for chunk in stream.chunks(chunk_size) {
+            decoder.push(chunk);
+            texts.extend(drain_texts(&mut decoder));
+        }
+        assert_eq!(texts, ["one", "two", "three"], "chunk size {}", chunk_size);
+    }
+}
+
+#[test]
+fn test_stream_decoder_read_buffer_and_backpressure() {
+    let mut builder = DltMessageBuilder::new().add_serial_header();
+    let first = stream_message(&mut builder, "first message");
+    let second = stream_message(&mut builder, "second message");
+    let mut stream = first.clone();
+    stream.extend(&second);
+
+    let mut decoder = DltStreamDecoder::<64>::new();
+    assert_eq!(decoder.capacity(), 64);
+
+    // More data than fits: only part is accepted
+    let accepted = decoder.push(&stream);
+    assert_eq!(accepted, 64);
+    let frame = decoder.next_frame().unwrap().unwrap();
+    assert_eq!(frame, &first[..]);
+    assert_eq!(decoder.next_frame(), Ok(None));
+
+    // Read the rest directly into the decoder
+    let rest = &stream[accepted..];
+    let space = decoder.read_buffer();
+    space[..rest.len()].copy_from_slice(rest);
+    decoder.commit(rest.len());
+
+    let message = decoder.next_message().unwrap().unwrap();
+    assert!(message.has_serial_header);
+    assert_eq!(PayloadParser::from_message(&message).read_string().unwrap(), "second message");
+    assert!(decoder.next_message().unwrap().is_none());
+}
+
+#[test]
+fn test_stream_decoder_errors_and_recovery() {
+    let mut builder = DltMessageBuilder::new();
+    let mut decoder = DltStreamDecoder::<64>::new();
+
+    // Garbage is reported once, then skipped until the next valid header
+    decoder.push(&[0xFF, 0xFF, 0xFF]);
+    decoder.push(&stream_message(&mut builder, "ok"));
+    assert_eq!(
+        decoder.next_message().unwrap_err(),
+        DltStreamError::InvalidHeader(DltHeaderError::InvalidVersion)
+    );
+    assert_eq!(drain_texts(&mut decoder), ["ok"]);
+    assert_eq!(decoder.skipped_bytes(), 3);
+
+    // A message larger than the buffer is reported and dropped as it arrives
+    let large = stream_message(&mut builder, &"x".repeat(100));
+    let after = stream_message(&mut builder, "after");
+    let mut stream = large.clone();
+    stream.extend(&after);
+
+    let mut chunks = stream.chunks(30);
+    decoder.push(chunks.next().unwrap());
+    assert_eq!(
+        decoder.next_message().unwrap_err(),
+        DltStreamError::MessageTooLarge(large.len())
+    );
+    let mut texts = Vec::new();
+    for chunk in chunks {
+        decoder.push(chunk);
+        texts.extend(drain_texts(&mut decoder));
+    }
+    assert_eq!(texts, ["after"]);
+}
+
+#[test]
+fn test_stream_decoder_serial_resync() {
+    let mut builder = DltMessageBuilder::new().add_serial_header();
+    let mut decoder = DltStreamDecoder::<256>::new();
+    decoder.push(&stream_message(&mut builder, "first"));
+    assert_eq!(drain_texts(&mut decoder), ["first"]);
+
+    // Once a serial stream is detected, resync only happens at "DLS\x01"
+    let mut garbage = vec![0x35, 0x00, 0x00, 0x20, 0x44, 0x4C];
+    garbage.extend(stream_message(&mut builder, "second"));
+    decoder.push(&garbage);
+    assert_eq!(
+        decoder.next_message().unwrap_err(),
+        DltStreamError::InvalidHeader(DltHeaderError::InvalidSerialHeader)
+    );
+    assert_eq!(drain_texts(&mut decoder), ["second"]);
+    assert_eq!(decoder.skipped_bytes(), 6);
+
+    // A partial serial header waits for more data
+    decoder.push(&DLT_SERIAL_HEADER_ARRAY[..2]);
+    assert!(decoder.next_message().unwrap().is_none());
+    assert_eq!(decoder.buffered(), 2);
+
+    decoder.reset();
+    assert_eq!(decoder.buffered(), 0);
+}
+
+// ========================================
+// Message Serialization Tests
+// ========================================
+
+/// Parse each message and check that writing it back reproduces its bytes
+fn assert_write_roundtrip(data: &[u8]) {
+    let message = DltHeaderParser::new(data).parse_message().unwrap();
+    assert_eq!(message.encoded_len(), data.len());
+    assert_eq!(message.encoded_htyp(), message.standard_header.htyp);
+
+    let mut buffer = [0u8; 512];
+    let size = message.write(&mut buffer).unwrap();
+    assert_eq!(&buffer[..size], data);
+}
+
+#[test]
+fn test_message_write_roundtrip() {
+    // Captured from dlt-daemon: control response without verbose payload and a verbose log
+    assert_write_roundtrip(&[
+        0x35, 0x00, 0x00, 0x20, 0x45, 0x43, 0x55, 0x31, 0x82, 0x72, 0xD9, 0x99, 0x26, 0x01, 0x44, 0x41,
+        0x31, 0x00, 0x44, 0x43, 0x31, 0x00, 0x02, 0x0F, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00,
+    ]);
+    // Standard header only, no extended header
+    assert_write_roundtrip(&[0x20, 0x07, 0x00, 0x06, 0xAB, 0xCD]);
+
+    let mut buffer = [0u8; 256];
+    let mut builder = DltMessageBuilder::new()
+        .with_ecu_id(b"ECU1")
+        .with_app_id(b"APP1")
+        .with_context_id(b"CTX1")
+        .with_session_id(0x1234_5678)
+        .with_timestamp(0xCAFE_BABE)
+        .add_serial_header();
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, b"serial", MtinTypeDltLog::DltLogWarn, 1, true)
+        .unwrap();
+    assert_write_roundtrip(&buffer[..size]);
+
+    let mut builder = DltMessageBuilder::new();
+    builder.set_endian(DltEndian::Big);
+    let size = builder
+        .generate_app_trace_message(&mut buffer, b"\x01\x02", MtinTypeDltAppTrace::DltTraceFunctionIn, 1, false)
+        .unwrap();
+    assert_write_roundtrip(&buffer[..size]);
+
+    let mut builder = DltServiceMessageBuilder::new().with_ecu_id(b"ECU1");
+    let size = builder
+        .generate_set_log_level_request(&mut buffer, b"APP1", b"CTX1", 4)
+        .unwrap();
+    assert_write_roundtrip(&buffer[..size]);
+}
+
+#[test]
+fn test_message_write_modified_fields() {
+    let mut buffer = [0u8; 256];
+    let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1").with_timestamp(100);
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, b"modified", MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap();
+    let original = buffer[..size].to_vec();
+
+    let mut message = DltHeaderParser::new(&original).parse_message().unwrap();
+    let original_has_session = message.session_id.is_some();
+    message.ecu_id = None;
+    message.session_id = Some(42);
+    message.extended_header.as_mut().unwrap().apid = *b"NEW1";
+
+    // Length and flags follow the fields
+    let mut output = [0u8; 256];
+    let written = message.write(&mut output).unwrap();
+    assert_eq!(written, message.encoded_len());
+    assert_eq!(written, size - DLT_ID_SIZE + if original_has_session { 0 } else { 4 });
+    let reparsed = DltHeaderParser::new(&output[..written]).parse_message().unwrap();
+    assert!(!reparsed.header_type.WEID);
+    assert!(reparsed.header_type.WSID);
+    assert_eq!(reparsed.session_id, Some(42));
+    assert_eq!(reparsed.timestamp, Some(100));
+    assert_eq!(reparsed.standard_header.len as usize, written);
+    assert_eq!(&reparsed.extended_header.unwrap().apid, b"NEW1");
+    assert_eq!(reparsed.payload, message.payload);
+
+    assert!(matches!(message.write(&mut output[..written - 1]), Err(DltError::BufferTooSmall)));
+
+    let large = vec![0u8; 70000];
+    message.payload = &large;
+    let mut large_output = vec![0u8; 70100];
+    assert!(matches!(message.write(&mut large_output), Err(DltError::InvalidParameter)));
+}
+
+// ========================================
+// Message Rewriting Tests
+// ========================================
+
+const REWRITE_ECU_MAP: [([u8; 4], [u8; 4]); 2] = [(*b"ECU1", *b"BEN1"), (*b"ECU2", *b"BEN2")];
+const REWRITE_APP_MAP: [([u8; 4], [u8; 4]); 1] = [(*b"APP1", *b"MAIN")];
+const REWRITE_CONTEXT_MAP: [([u8; 4], [u8; 4]); 1] = [(*b"CTX1", *b"CORE")];
+
+fn rewrite_source(buffer: &mut [u8], ecu_id: &[u8; 4], timestamp: u32) -> usize {
+    let mut builder = DltMessageBuilder::new()
+        .with_ecu_id(ecu_id)
+        .with_app_id(b"APP1")
+        .with_context_id(b"CTX2")
+        .with_timestamp(timestamp);
+    builder
+        .generate_log_message_with_payload(buffer, b"rewrite me", MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap()
+}
+
+#[test]
+fn test_rewrite_ids_and_timestamps() {
+    let rewriter = DltMessageRewriter::new()
+        .with_ecu_id_map(&REWRITE_ECU_MAP)
+        .with_app_id_map(&REWRITE_APP_MAP)
+        .with_context_id_map(&REWRITE_CONTEXT_MAP)
+        .with_timestamp_offset(-5_000);
+
+    let mut source = [0u8; 256];
+    let size = rewrite_source(&mut source, b"ECU2", 20_000);
+    let message = DltHeaderParser::new(&source[..size]).parse_message().unwrap();
+
+    let mut buffer = [0u8; 256];
+    let written = rewriter.rewrite_into(&message, &mut buffer).unwrap();
+    assert_eq!(written, size);
+    let rewritten = DltHeaderParser::new(&buffer[..written]).parse_message().unwrap();
+    assert_eq!(rewritten.ecu_id, Some(*b"BEN2"));
+    assert_eq!(rewritten.timestamp, Some(15_000));
+    let extended = rewritten.extended_header.unwrap();
+    assert_eq!(&extended.apid, b"MAIN");
+    // Not in the map: unchanged
+    assert_eq!(&extended.ctid, b"CTX2");
+    assert_eq!(rewritten.payload, message.payload);
+
+    // Timestamps are clamped instead of wrapping
+    let size = rewrite_source(&mut source, b"ECU3", 1_000);
+    let message = DltHeaderParser::new(&source[..size]).parse_message().unwrap();
+    let rewritten = rewriter.rewrite(&message);
+    assert_eq!(rewritten.timestamp, Some(0));
+    assert_eq!(rewritten.ecu_id, Some(*b"ECU3"));
+
+    let header = DltStorageHeader::new(1_700_000_000, 5, b"ECU1");
+    assert_eq!(&rewriter.rewrite_storage_header(&header).ecu_id, b"BEN1");
+    assert_eq!(rewriter.rewrite_storage_header(&header).seconds, 1_700_000_000);
+}
+
+#[test]
+fn test_rewrite_header_fields() {
+    let mut source = [0u8; 256];
+    let size = rewrite_source(&mut source, b"ECU1", 500);
+    let original = &source[..size];
+    let message = DltHeaderParser::new(original).parse_message().unwrap();
+
+    // Without rules the message is unchanged
+    let mut buffer = [0u8; 256];
+    let written = DltMessageRewriter::new().rewrite_into(&message, &mut buffer).unwrap();
+    assert_eq!(&buffer[..written], original);
+
+    // Remove the extra fields and add a serial header
+    let stripped = DltMessageRewriter::new()
+        .without_ecu_id()
+        .without_session_id()
+        .without_timestamp()
+        .with_serial_header();
+    let written = stripped.rewrite_into(&message, &mut buffer).unwrap();
+    let rewritten = DltHeaderParser::new(&buffer[..written]).parse_message().unwrap();
+    assert!(rewritten.has_serial_header);
+    assert_eq!(rewritten.ecu_id, None);
+    assert_eq!(rewritten.session_id, None);
+    assert_eq!(rewritten.timestamp, None);
+    assert_eq!(rewritten.standard_header.htyp & (WEID_MASK | WSID_MASK | WTMS_MASK), 0);
+    assert_eq!(
+        rewritten.standard_header.len as usize,
+        DLT_STANDARD_HEADER_SIZE + DLT_EXTENDED_HEADER_SIZE + message.payload.len()
+    );
+    assert_eq!(check_message_header(&buffer[DLT_SERIAL_HEADER_SIZE..written]), Ok(written - DLT_SERIAL_HEADER_SIZE));
+    assert_eq!(rewritten.payload, message.payload);
+
+    // Add them again with new values and strip the serial header
+    let restored = DltMessageRewriter::new()
+        .with_ecu_id(b"NEW1")
+        .with_session_id(77)
+        .with_timestamp(1234)
+        .without_serial_header();
+    let mut output = [0u8; 256];
+    let written = restored.rewrite_into(&rewritten, &mut output).unwrap();
+    let message = DltHeaderParser::new(&output[..written]).parse_message().unwrap();
+    assert!(!message.has_serial_header);
+    assert_eq!(message.ecu_id, Some(*b"NEW1"));
+    assert_eq!(message.session_id, Some(77));
+    assert_eq!(message.timestamp, Some(1234));
+    assert!(message.header_type.WEID && message.header_type.WSID && message.header_type.WTMS);
+    assert_eq!(message.standard_header.len as usize, written);
+
+    assert!(matches!(restored.rewrite_into(&rewritten, &mut output[..10]), Err(DltError::BufferTooSmall)));
+}
+
+// ========================================
+// DLT File Writer Tests (std feature)
+// ========================================
+
+#[cfg(feature = "std")]
+mod file_writer_tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    /// Create an empty scratch directory for a test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dlt_protocol_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Generate a verbose log message carrying `text`
+    fn log_message(buffer: &mut [u8], text: &[u8]) -> usize {
+        let mut builder = DltMessageBuilder::new()
+            .with_ecu_id(b"ECU9")
+            .with_app_id(b"APP1")
+            .with_context_id(b"CTX1");
+        builder
+            .generate_log_message_with_payload(buffer, text, MtinTypeDltLog::DltLogInfo, 1, true)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_file_writer_single_file_roundtrip() {
+        let dir = scratch_dir("single");
+        let path = dir.join("trace.dlt");
+        let mut buffer = [0u8; 256];
+
+        let mut writer = DltFileWriter::new(&path);
+        for i in 0..3u32 {
+            let size = log_message(&mut buffer, b"message");
+            writer.write_message_at(&buffer[..size], 1000 + i, 500).unwrap();
+        }
+        assert_eq!(writer.current_path(), Some(path.as_path()));
+        assert_eq!(writer.current_messages(), 3);
+        drop(writer);
+
+        let data = std::fs::read(&path).unwrap();
+        let records: Vec<_> = DltFileReader::new(&data).collect::<Result<_, _>>().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].storage_header.seconds, 1002);
+        assert_eq!(records[2].storage_header.microseconds, 500);
+        // ECU ID taken from the message when not configured
+        assert_eq!(records[0].storage_header.ecu_id, *b"ECU9");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_writer_strips_serial_header_and_sets_ecu() {
+        let dir = scratch_dir("serial");
+        let path = dir.join("serial.dlt");
+        let mut buffer = [0u8; 256];
+
+        let mut builder = DltMessageBuilder::new().add_serial_header();
+        let size = builder
+            .generate_log_message_with_payload(&mut buffer, b"x", MtinTypeDltLog::DltLogWarn, 1, true)
+            .unwrap();
+
+        let mut writer = DltFileWriter::new(&path).with_ecu_id(b"STOR");
+        writer.write_message(&buffer[..size]).unwrap();
+        writer.flush().unwrap();
+
+        let data = std::fs::read(&path).unwrap();
+        assert_eq!(data.len(), DLT_STORAGE_HEADER_SIZE + size - DLT_SERIAL_HEADER_SIZE);
+        let stored = DltFileReader::new(&data).next().unwrap().unwrap();
+        assert_eq!(stored.storage_header.ecu_id, *b"STOR");
+        assert!(!stored.message.has_serial_header);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_writer_rotate_by_message_count() {
+        let dir = scratch_dir("count");
+        let mut buffer = [0u8; 256];
+        let size = log_message(&mut buffer, b"rotate");
+
+        let mut writer = DltFileWriter::new(dir.join("bench.dlt")).with_max_messages(2);
+        for _ in 0..5 {
+            writer.write_message(&buffer[..size]).unwrap();
+        }
+        let files: Vec<PathBuf> = writer.files().map(|p| p.to_path_buf()).collect();
+        drop(writer);
+
+        let names: Vec<_> = files.iter().map(|p| p.file_name().unwrap().to_owned()).collect();
+        assert_eq!(names, ["bench_000.dlt", "bench_001.dlt", "bench_002.dlt"]);
+        let counts: Vec<usize> = files
+            .iter()
+            .map(|p| DltFileReader::new(&std::fs::read(p).unwrap()).count())
+            .collect();
+        assert_eq!(counts, [2, 2, 1]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_writer_rotate_by_size_with_max_files() {
+        let dir = scratch_dir("size");
+        let mut buffer = [0u8; 256];
+        let size = log_message(&mut buffer, b"sized");
+        let record = (DLT_STORAGE_HEADER_SIZE + size) as u64;
+
+        let mut writer = DltFileWriter::new(dir.join("size.dlt"))
+            .with_max_file_size(record * 3)
+            .with_max_files(2);
+        for _ in 0..10 {
+            writer.write_message(&buffer[..size]).unwrap();
+            assert!(writer.current_size() <= record * 3);
+        }
+        drop(writer);
+
+        let mut remaining: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        remaining.sort();
+        assert_eq!(remaining, ["size_002.dlt", "size_003.dlt"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_writer_rotate_without_limits_appends() {
+        let dir = scratch_dir("append");
+        let path = dir.join("single.dlt");
+        let mut buffer = [0u8; 256];
+
+        let mut writer = DltFileWriter::new(&path);
+        let size = log_message(&mut buffer, b"before");
+        writer.write_message_at(&buffer[..size], 1, 0).unwrap();
+        writer.rotate().unwrap();
+        let size = log_message(&mut buffer, b"after");
+        writer.write_message_at(&buffer[..size], 2, 0).unwrap();
+        assert_eq!(writer.current_path(), Some(path.as_path()));
+        drop(writer);
+
+        let data = std::fs::read(&path).unwrap();
+        let texts: Vec<String> = DltFileReader::new(&data)
+            .map(|stored| PayloadParser::from_message(&stored.unwrap().message).read_string().unwrap().to_string())
+            .collect();
+        assert_eq!(texts, ["before", "after"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_writer_continues_after_earlier_run() {
+        let dir = scratch_dir("restart");
+        let mut buffer = [0u8; 256];
+        let size = log_message(&mut buffer, b"run");
+
+        let mut first = DltFileWriter::new(dir.join("bench.dlt")).with_max_messages(1);
+        for _ in 0..3 {
+            first.write_message(&buffer[..size]).unwrap();
+        }
+        drop(first);
+        std::fs::write(dir.join("bench_notes.dlt"), b"unrelated").unwrap();
+
+        // A new writer continues the numbering and leaves the old files alone
+        let mut second = DltFileWriter::new(dir.join("bench.dlt"))
+            .with_max_messages(1)
+            .with_max_files(1);
+        second.write_message(&buffer[..size]).unwrap();
+        second.write_message(&buffer[..size]).unwrap();
+        let names: Vec<_> = second
+            .files()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        drop(second);
+
+        assert_eq!(names, ["bench_004.dlt"]);
+        for index in [0, 1, 2, 4] {
+            assert!(dir.join(format!("bench_{:03}.dlt", index)).exists());
+        }
+        assert!(!dir.join("bench_003.dlt").exists());
+
+        // Deleting files of earlier runs is opt-in
+        let mut third = DltFileWriter::new(dir.join("bench.dlt"))
+            .with_max_messages(1)
+            .with_max_files(3)
+            .with_delete_earlier_files();
+        third.write_message(&buffer[..size]).unwrap();
+        let names: Vec<_> = third
+            .files()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        drop(third);
+
+        assert_eq!(names, ["bench_002.dlt", "bench_004.dlt", "bench_005.dlt"]);
+        assert!(!dir.join("bench_000.dlt").exists());
+        assert!(!dir.join("bench_001.dlt").exists());
+        assert_eq!(std::fs::read(dir.join("bench_notes.dlt")).unwrap(), b"unrelated");
+        for name in &names {
+            assert_eq!(DltFileReader::new(&std::fs::read(dir.join(name)).unwrap()).count(), 1);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_writer_appends_to_existing_file() {
+        let dir = scratch_dir("existing");
+        let path = dir.join("trace.dlt");
+        let mut buffer = [0u8; 256];
+        let size = log_message(&mut buffer, b"run");
+
+        for seconds in [1, 2] {
+            let mut writer = DltFileWriter::new(&path);
+            writer.write_message_at(&buffer[..size], seconds, 0).unwrap();
+            assert_eq!(writer.current_size(), u64::from(seconds) * (DLT_STORAGE_HEADER_SIZE + size) as u64);
+        }
+
+        let data = std::fs::read(&path).unwrap();
+        let seconds: Vec<u32> = DltFileReader::new(&data)
+            .map(|stored| stored.unwrap().storage_header.seconds)
+            .collect();
+        assert_eq!(seconds, [1, 2]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_writer_rotate_by_age() {
+        let dir = scratch_dir("age");
+        let mut buffer = [0u8; 256];
+        let size = log_message(&mut buffer, b"aged");
+
+        let mut writer = DltFileWriter::new(dir.join("age.dlt"))
+            .with_max_file_age(Duration::from_millis(20));
+        writer.write_message(&buffer[..size]).unwrap();
+        writer.write_message(&buffer[..size]).unwrap();
+        std::thread::sleep(Duration::from_millis(30));
+        writer.write_message(&buffer[..size]).unwrap();
+
+        assert_eq!(writer.files().count(), 2);
+        assert_eq!(writer.current_messages(), 1);
+        drop(writer);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+#[cfg(feature = "std")]
+mod fibex_tests {
+    use dlt_protocol::r19_11::*;
+
+    /// Catalog in the layout written by dlt-viewer's non-verbose tooling
+    const FIBEX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<fx:FIBEX xmlns:fx="http://www.asam.net/xml/fbx" xmlns:ho="http://www.asam.net/xml" VERSION="3.1.0">
+  <!-- generated -->
+  <fx:ELEMENTS>
+    <fx:FRAMES>
+      <fx:FRAME ID="ID_1001">
+        <ho:SHORT-NAME>ID_1001</ho:SHORT-NAME>
+        <fx:PDU-INSTANCES>
+          <fx:PDU-INSTANCE ID="P_1001_1">
+            <fx:PDU-REF ID-REF="PDU_1001_1"/>
+            <fx:SEQUENCE-NUMBER>1</fx:SEQUENCE-NUMBER>
+          </fx:PDU-INSTANCE>
+          <fx:PDU-INSTANCE ID="P_1001_0">
+            <fx:PDU-REF ID-REF="PDU_1001_0"/>
+            <fx:SEQUENCE-NUMBER>0</fx:SEQUENCE-NUMBER>
+          </fx:PDU-INSTANCE>
+          <fx:PDU-INSTANCE ID="P_1001_2">
+            <fx:PDU-REF ID-REF="PDU_1001_2"/>
+            <fx:SEQUENCE-NUMBER>2</fx:SEQUENCE-NUMBER>
+          </fx:PDU-INSTANCE>
+        </fx:PDU-INSTANCES>
+        <fx:MANUFACTURER-EXTENSION>
+          <MESSAGE_TYPE>DLT_TYPE_LOG</MESSAGE_TYPE>
+          <MESSAGE_INFO>DLT_LOG_ERROR</MESSAGE_INFO>
+          <APPLICATION_ID>ENG</APPLICATION_ID>
+          <CONTEXT_ID>TEMP</CONTEXT_ID>
+        </fx:MANUFACTURER-EXTENSION>
+      </fx:FRAME>
+    </fx:FRAMES>
+    <fx:PDUS>
+      <fx:PDU ID="PDU_1001_0">
+        <ho:DESC><![CDATA[Sensor]]> &quot;T1&quot; &lt;out of range&gt;</ho:DESC>
+        <fx:BYTE-LENGTH>0</fx:BYTE-LENGTH>
+      </fx:PDU>
+      <fx:PDU ID="PDU_1001_1">
+        <fx:SIGNAL-INSTANCES>
+          <fx:SIGNAL-INSTANCE ID="S_1001_1">
+            <fx:SEQUENCE-NUMBER>0</fx:SEQUENCE-NUMBER>
+            <fx:SIGNAL-REF ID-REF="S_SINT16"/>
+          </fx:SIGNAL-INSTANCE>
+        </fx:SIGNAL-INSTANCES>
+      </fx:PDU>
+      <fx:PDU ID="PDU_1001_2">
+        <fx:SIGNAL-INSTANCES>
+          <fx:SIGNAL-INSTANCE ID="S_1001_2">
+            <fx:SEQUENCE-NUMBER>0</fx:SEQUENCE-NUMBER>
+            <fx:SIGNAL-REF ID-REF="VOLTAGE"/>
+          </fx:SIGNAL-INSTANCE>
+        </fx:SIGNAL-INSTANCES>
+      </fx:PDU>
+    </fx:PDUS>
+    <fx:SIGNALS>
+      <fx:SIGNAL ID="VOLTAGE">
+        <ho:SHORT-NAME>VOLTAGE</ho:SHORT-NAME>
+        <fx:CODING-REF ID-REF="FLOAT32"/>
+      </fx:SIGNAL>
+    </fx:SIGNALS>
+  </fx:ELEMENTS>
+  <fx:PROCESSING-INFORMATION>
+    <fx:CODINGS>
+      <fx:CODING ID="FLOAT32">
+        <ho:CODED-TYPE ho:BASE-DATA-TYPE="A_FLOAT32" CATEGORY="STANDARD-LENGTH-TYPE"/>
+      </fx:CODING>
+    </fx:CODINGS>
+  </fx:PROCESSING-INFORMATION>
+</fx:FIBEX>
+"#;
+
+    #[test]
+    fn test_fibex_catalog_parse() {
+        let catalog = DltFibexCatalog::parse(FIBEX).unwrap();
+        assert_eq!(catalog.len(), 1);
+
+        let frame = catalog.frame(1001).unwrap();
+        assert_eq!(frame.text, "Sensor \"T1\" <out of range>");
+        assert_eq!(frame.app_id, *b"ENG\0");
+        assert_eq!(frame.context_id, *b"TEMP");
+        assert_eq!(frame.log_level, MtinTypeDltLog::DltLogError);
+        assert_eq!(frame.args, [DltArgType::I16, DltArgType::F32]);
+        assert!(catalog.frame(1002).is_none());
+    }
+
+    #[test]
+    fn test_fibex_catalog_decode_message() {
+        let catalog = DltFibexCatalog::parse(FIBEX).unwrap();
+
+        let mut payload = 1001u32.to_le_bytes().to_vec();
+        payload.extend_from_slice(&(-41i16).to_le_bytes());
+        payload.extend_from_slice(&12.5f32.to_le_bytes());
+
+        let mut builder = DltMessageBuilder::new()
+            .with_app_id(b"ENG\0")
+            .with_context_id(b"TEMP");
+        let mut buffer = [0u8; 128];
+        let size = builder
+            .generate_log_message_with_payload(&mut buffer, &payload, MtinTypeDltLog::DltLogError, 2, false)
+            .unwrap();
+        let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+
+        let decoded = decode_non_verbose(&catalog, &message).unwrap();
+        assert_eq!(decoded.text(), "Sensor \"T1\" <out of range>");
+        let args: Vec<_> = decoded.args().map(Result::unwrap).collect();
+        assert_eq!(args, [DltValue::I16(-41), DltValue::F32(12.5)]);
+    }
+
+    #[test]
+    fn test_fibex_catalog_errors() {
+        assert!(matches!(
+            DltFibexCatalog::parse("<fx:FIBEX><fx:FRAMES></fx:FIBEX>"),
+            Err(DltFibexError::InvalidXml(_))
+        ));
+        assert!(matches!(
+            DltFibexCatalog::parse(r#"<FIBEX><FRAME ID="MSG_1"/></FIBEX>"#),
+            Err(DltFibexError::InvalidMessageId(id)) if id == "MSG_1"
+        ));
+
+        let missing_pdu = FIBEX.replace(r#"<fx:PDU ID="PDU_1001_2">"#, r#"<fx:PDU ID="OTHER">"#);
+        assert!(matches!(
+            DltFibexCatalog::parse(&missing_pdu),
+            Err(DltFibexError::MissingReference(id)) if id == "PDU_1001_2"
+        ));
+
+        let unknown_coding = FIBEX.replace("A_FLOAT32", "A_VECTOR");
+        assert!(matches!(
+            DltFibexCatalog::parse(&unknown_coding),
+            Err(DltFibexError::UnsupportedSignal(id)) if id == "VOLTAGE"
+        ));
+    }
+    #[test]
+    fn test_fibex_export_roundtrip() {
+        const FRAMES: &[DltNonVerboseFrame] = &[
+            DltNonVerboseFrame {
+                message_id: 7,
+                app_id: *b"ENG\0",
+                context_id: *b"TEMP",
+                log_level: MtinTypeDltLog::DltLogWarn,
+                text: "Limit <exceeded> & \"clamped\"",
+                args: &[DltArgType::Bool, DltArgType::U64, DltArgType::F64, DltArgType::Utf8String],
+            },
+            DltNonVerboseFrame {
+                message_id: 8,
+                app_id: *b"ENG\0",
+                context_id: *b"TEMP",
+                log_level: MtinTypeDltLog::DltLogDebug,
+                text: "Dump",
+                args: &[DltArgType::Raw, DltArgType::I8],
+            },
+        ];
+        const CATALOG: DltFrameCatalog = DltFrameCatalog::new(FRAMES);
+
+        let mut xml = Vec::new();
+        write_fibex(&mut xml, b"ECU1", CATALOG.frames().iter().copied()).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert!(xml.contains(r#"<fx:FRAME ID="ID_7">"#));
+        assert!(xml.contains("<APPLICATION_ID>ENG</APPLICATION_ID>"));
+
+        let loaded = DltFibexCatalog::parse(&xml).unwrap();
+        assert_eq!(loaded.len(), 2);
+        for frame in FRAMES {
+            assert_eq!(loaded.frame(frame.message_id).unwrap(), *frame);
+        }
+
+        // Messages generated from the const catalog decode with the exported file
+        let mut builder = DltMessageBuilder::new();
+        let mut buffer = [0u8; 128];
+        let args = [DltValue::Raw(&[1, 2]), DltValue::I8(-1)];
+        let size = builder
+            .generate_non_verbose_message_by_id(&mut buffer, &CATALOG, 8, &args)
+            .unwrap();
+        let message = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+        let decoded = decode_non_verbose(&loaded, &message).unwrap();
+        assert_eq!(decoded.text(), "Dump");
+        assert_eq!(decoded.args().map(Result::unwrap).collect::<Vec<_>>(), args);
+    }
+}
+
+#[cfg(feature = "std")]
+mod nw_reassembler_tests {
+    use dlt_protocol::r19_11::*;
+    use std::time::{Duration, Instant};
+
+    fn segments(handle: u32, header: &[u8], payload: &[u8], segment_size: u16) -> Vec<Vec<u8>> {
+        let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1");
+        let mut segmenter = DltNwSegmenter::new(MtinTypeDltNwTrace::DltNwTraceEthernet, handle, header, payload)
+            .with_segment_size(segment_size);
+        let mut messages = Vec::new();
+        let mut buffer = [0u8; 512];
+        while let Some(size) = segmenter.next_message(&mut builder, &mut buffer) {
+            messages.push(buffer[..size.unwrap()].to_vec());
+        }
+        messages
+    }
+
+    fn push(
+        reassembler: &mut DltNwReassembler,
+        data: &[u8],
+        now: Instant,
+    ) -> Result<Option<DltNwTrace>, DltNwReassemblyError> {
+        let message = DltHeaderParser::new(data).parse_message().unwrap();
+        reassembler.push_at(&message, now)
+    }
+
+    #[test]
+    fn test_reassemble_interleaved_transfers() {
+        let first: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+        let second = vec![0x55u8; 300];
+        let a = segments(1, &[0x08, 0x00], &first, 256);
+        let b = segments(2, &[], &second, 256);
+
+        let now = Instant::now();
+        let mut reassembler = DltNwReassembler::new();
+        let mut done = Vec::new();
+        for i in 0..a.len().max(b.len()) {
+            for messages in [&a, &b] {
+                if let Some(data) = messages.get(i)
+                    && let Some(trace) = push(&mut reassembler, data, now).unwrap()
+                {
+                    done.push(trace);
+                }
+            }
+        }
+
+        assert_eq!(done.len(), 2);
+        assert_eq!(done[0].handle, 2);
+        assert_eq!(done[0].payload, second);
+        assert_eq!(done[1].handle, 1);
+        assert_eq!(done[1].ecu_id, Some(*b"ECU1"));
+        assert_eq!(done[1].trace_type, Some(MtinTypeDltNwTrace::DltNwTraceEthernet));
+        assert_eq!(done[1].header, vec![0x08, 0x00]);
+        assert_eq!(done[1].payload, first);
+        assert_eq!(reassembler.pending(), 0);
+    }
+
+    #[test]
+    fn test_reassemble_dlt_daemon_transfer() {
+        let now = Instant::now();
+        let mut reassembler = DltNwReassembler::new();
+        assert_eq!(push(&mut reassembler, &super::DAEMON_NWST, now).unwrap(), None);
+        assert_eq!(push(&mut reassembler, &super::DAEMON_NWCH, now).unwrap(), None);
+        let trace = push(&mut reassembler, &super::DAEMON_NWEN, now).unwrap().unwrap();
+        assert_eq!(trace.handle, 0x0007_A120);
+        assert_eq!(trace.header, super::DAEMON_NW_HEADER);
+        assert_eq!(trace.payload, super::DAEMON_NW_PAYLOAD);
+    }
+
+    #[test]
+    fn test_reassemble_out_of_order_chunks() {
+        let payload: Vec<u8> = (0..200u8).collect();
+        let messages = segments(7, &[], &payload, 64);
+        let now = Instant::now();
+        let mut reassembler = DltNwReassembler::new();
+
+        for index in [0, 4, 2, 1, 3] {
+            assert_eq!(push(&mut reassembler, &messages[index], now).unwrap(), None);
+        }
+        let trace = push(&mut reassembler, &messages[5], now).unwrap().unwrap();
+        assert_eq!(trace.payload, payload);
+    }
+
+    #[test]
+    fn test_reassemble_errors() {
+        let payload = [3u8; 100];
+        let messages = segments(9, &[], &payload, 40);
+        let now = Instant::now();
+        let mut reassembler = DltNwReassembler::new();
+
+        assert_eq!(
+            push(&mut reassembler, &messages[1], now),
+            Err(DltNwReassemblyError::UnknownHandle(9))
+        );
+
+        push(&mut reassembler, &messages[0], now).unwrap();
+        push(&mut reassembler, &messages[1], now).unwrap();
+        assert_eq!(
+            push(&mut reassembler, &messages[4], now),
+            Err(DltNwReassemblyError::Incomplete { handle: 9, received: 1, expected: 3 })
+        );
+        assert_eq!(reassembler.pending(), 0);
+
+        let mut limited = DltNwReassembler::new().with_max_payload_size(50);
+        assert_eq!(
+            push(&mut limited, &messages[0], now),
+            Err(DltNwReassemblyError::PayloadTooLarge { handle: 9, length: 100 })
+        );
+
+        // Chunk from a transfer with a different segment size
+        let other = segments(9, &[], &payload, 30);
+        push(&mut reassembler, &messages[0], now).unwrap();
+        assert_eq!(
+            push(&mut reassembler, &other[1], now),
+            Err(DltNwReassemblyError::SegmentOutOfRange { handle: 9, sequence: 0 })
+        );
+    }
+
+    #[test]
+    fn test_reassembly_timeout() {
+        let payload = [1u8; 100];
+        let messages = segments(5, &[], &payload, 50);
+        let start = Instant::now();
+        let mut reassembler = DltNwReassembler::new().with_timeout(Duration::from_secs(2));
+
+        push(&mut reassembler, &messages[0], start).unwrap();
+        push(&mut reassembler, &messages[1], start + Duration::from_secs(1)).unwrap();
+        assert!(reassembler.expire_at(start + Duration::from_secs(2)).is_empty());
+        assert_eq!(reassembler.expire_at(start + Duration::from_secs(4)), vec![5]);
+        assert_eq!(reassembler.expired_count(), 1);
+
+        // Late segments of the dropped transfer are rejected
+        assert_eq!(
+            push(&mut reassembler, &messages[2], start + Duration::from_secs(5)),
+            Err(DltNwReassemblyError::UnknownHandle(5))
+        );
+
+        // Pushing also drops stale transfers
+        push(&mut reassembler, &messages[0], start).unwrap();
+        assert_eq!(reassembler.pending(), 1);
+        let unrelated = segments(6, &[], &payload, 50);
+        push(&mut reassembler, &unrelated[0], start + Duration::from_secs(10)).unwrap();
+        assert_eq!(reassembler.pending(), 1);
+        assert_eq!(reassembler.expired_count(), 2);
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod codec_tests {
+    use bytes::BytesMut;
+    use dlt_protocol::r19_11::*;
+    use futures::{SinkExt, StreamExt};
+    use tokio_util::codec::{Decoder, Encoder, Framed};
+
+    fn log_message(builder: &mut DltMessageBuilder, text: &str) -> Vec<u8> {
+        let mut buffer = [0u8; 256];
+        let size = builder
+            .generate_log_message_with_payload(&mut buffer, text.as_bytes(), MtinTypeDltLog::DltLogInfo, 1, true)
+            .unwrap();
+        buffer[..size].to_vec()
+    }
+
+    fn frame_text(frame: &DltFrame) -> String {
+        PayloadParser::from_message(&frame.message()).read_string().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_codec_decode_partial_and_garbage() {
+        let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1");
+        let first = log_message(&mut builder, "first");
+        let second = log_message(&mut builder, "second");
+
+        let mut codec = DltCodec::new();
+        let mut src = BytesMut::new();
+        src.extend_from_slice(&[0xFF, 0xFF]);
+        src.extend_from_slice(&first[..10]);
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+        assert_eq!(codec.skipped_bytes(), 2);
+
+        src.extend_from_slice(&first[10..]);
+        src.extend_from_slice(&second);
+        let frame = codec.decode(&mut src).unwrap().unwrap();
+        assert_eq!(frame.as_bytes(), &first[..]);
+        assert!(!frame.has_serial_header());
+        assert_eq!(frame.message().ecu_id, Some(*b"ECU1"));
+        assert_eq!(frame_text(&frame), "first");
+
+        let frame = codec.decode(&mut src).unwrap().unwrap();
+        assert_eq!(frame_text(&frame), "second");
+        assert!(src.is_empty());
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+    }
+
+    #[test]
+    fn test_codec_encode() {
+        let mut builder = DltMessageBuilder::new();
+        let message = log_message(&mut builder, "hello");
+
+        let mut dst = BytesMut::new();
+        DltCodec::new().encode(&message[..], &mut dst).unwrap();
+        assert_eq!(&dst[..], &message[..]);
+
+        // Serial header added once
+        let mut codec = DltCodec::new().with_serial_header();
+        let mut dst = BytesMut::new();
+        codec.encode(&message[..], &mut dst).unwrap();
+        let frame = codec.decode(&mut dst).unwrap().unwrap();
+        assert!(frame.has_serial_header());
+        assert!(frame.message().has_serial_header);
+        codec.encode(&frame, &mut dst).unwrap();
+        assert_eq!(&dst[..], frame.as_bytes());
+
+        // Truncated messages are rejected
+        let mut dst = BytesMut::new();
+        assert!(DltCodec::new().encode(&message[..message.len() - 1], &mut dst).is_err());
+        assert!(dst.is_empty());
+        assert_eq!(
+            DltFrame::from_bytes(message[..message.len() - 1].to_vec()),
+            Err(DltHeaderError::InvalidLength)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_codec_framed_roundtrip() {
+        let (client, server) = tokio::io::duplex(64);
+        let mut client = Framed::new(client, DltCodec::new().with_serial_header());
+        let server = Framed::new(server, DltCodec::new());
+        let long_text = "x".repeat(200);
+
+        // Messages larger than the pipe capacity arrive in several reads
+        let send = async {
+            let mut builder = DltMessageBuilder::new();
+            for text in ["one", long_text.as_str(), "three"] {
+                client.send(&log_message(&mut builder, text)[..]).await.unwrap();
+            }
+            drop(client);
+        };
+        let receive = server.map(Result::unwrap).collect::<Vec<_>>();
+        let ((), frames) = tokio::join!(send, receive);
+
+        let texts: Vec<_> = frames.iter().map(frame_text).collect();
+        assert_eq!(texts, ["one", long_text.as_str(), "three"]);
+        assert!(frames.iter().all(DltFrame::has_serial_header));
+    }
+}
+
+#[cfg(feature = "std")]
+mod udp_tests {
+    use dlt_protocol::r19_11::*;
+    use std::net::{Ipv4Addr, SocketAddr};
+    use std::time::Duration;
+
+    fn log_message(builder: &mut DltMessageBuilder, text: &str) -> Vec<u8> {
+        let mut buffer = [0u8; 256];
+        let size = builder
+            .generate_log_message_with_payload(&mut buffer, text.as_bytes(), MtinTypeDltLog::DltLogInfo, 1, true)
+            .unwrap();
+        buffer[..size].to_vec()
+    }
+
+    fn text(message: &DltMessage) -> String {
+        PayloadParser::from_message(message).read_string().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_datagram_multiple_messages() {
+        let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1");
+        let mut data = log_message(&mut builder, "one");
+        data.extend(log_message(&mut DltMessageBuilder::new().add_serial_header(), "two"));
+
+        let datagram = DltDatagram::new(&data, None);
+        let messages: Vec<_> = datagram.messages().map(Result::unwrap).collect();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(text(&messages[0]), "one");
+        assert!(messages[1].has_serial_header);
+        assert_eq!(text(&messages[1]), "two");
+        assert!(!datagram.is_truncated());
+    }
+
+    #[test]
+    fn test_datagram_truncated_and_invalid() {
+        let mut builder = DltMessageBuilder::new();
+        let first = log_message(&mut builder, "complete");
+        let second = log_message(&mut builder, "cut off");
+
+        let mut data = first.clone();
+        data.extend(&second[..second.len() - 2]);
+        let datagram = DltDatagram::new(&data, None);
+        let results: Vec<_> = datagram.messages().collect();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert_eq!(results[1].unwrap_err(), DltDatagramError::Truncated { offset: first.len() });
+        assert!(datagram.is_truncated());
+
+        // Partial header
+        let datagram = DltDatagram::new(&second[..2], None);
+        assert!(datagram.is_truncated());
+
+        let mut data = first.clone();
+        data.extend([0xFF; 8]);
+        let datagram = DltDatagram::new(&data, None);
+        let results: Vec<_> = datagram.messages().collect();
+        assert_eq!(
+            results[1].unwrap_err(),
+            DltDatagramError::InvalidHeader { offset: first.len(), error: DltHeaderError::InvalidVersion }
+        );
+        assert!(!datagram.is_truncated());
+    }
+
+    #[test]
+    fn test_udp_send_receive() {
+        let mut receiver = DltUdpReceiver::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        receiver.socket().set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let port = receiver.socket().local_addr().unwrap().port();
+
+        let sender = DltUdpSender::new((Ipv4Addr::LOCALHOST, port)).unwrap();
+        assert_eq!(sender.target().port(), port);
+
+        let mut builder = DltMessageBuilder::new();
+        let message = log_message(&mut builder, "over udp");
+        assert_eq!(sender.send(&message).unwrap(), message.len());
+        sender.send(&message[..message.len() - 1]).unwrap();
+
+        let datagram = receiver.recv().unwrap();
+        let sender_port = sender.socket().local_addr().unwrap().port();
+        assert_eq!(datagram.source().map(|addr| addr.port()), Some(sender_port));
+        let received: Vec<_> = datagram.messages().map(Result::unwrap).collect();
+        assert_eq!(text(&received[0]), "over udp");
+
+        assert!(receiver.recv().unwrap().is_truncated());
+        assert_eq!(receiver.datagram_count(), 2);
+        assert_eq!(receiver.truncated_count(), 1);
+    }
+
+    #[test]
+    fn test_udp_multicast_sender_rejects_unicast() {
+        let target = "127.0.0.1:3490".parse().unwrap();
+        assert!(DltUdpSender::multicast(target, Ipv4Addr::UNSPECIFIED, 1).is_err());
+    }
+
+    #[test]
+    fn test_udp_multicast_sender_configures_socket() {
+        let group = "239.255.42.99:3490".parse().unwrap();
+        let sender = DltUdpSender::multicast(group, Ipv4Addr::LOCALHOST, 3).unwrap();
+        assert_eq!(sender.target(), SocketAddr::V4(group));
+        assert_eq!(sender.socket().multicast_ttl_v4().unwrap(), 3);
+        assert!(sender.socket().local_addr().unwrap().ip().is_unspecified());
+    }
+}
+
+#[cfg(feature = "std")]
+mod pcap_tests {
+    use dlt_protocol::r19_11::*;
+    use std::io::Cursor;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    const CLIENT: [u8; 4] = [192, 168, 0, 2];
+    const ECU: [u8; 4] = [192, 168, 0, 10];
+
+    fn log_message(builder: &mut DltMessageBuilder, text: &str) -> Vec<u8> {
+        let mut buffer = [0u8; 256];
+        let size = builder
+            .generate_log_message_with_payload(&mut buffer, text.as_bytes(), MtinTypeDltLog::DltLogInfo, 1, true)
+            .unwrap();
+        buffer[..size].to_vec()
+    }
+
+    fn text(captured: &DltPcapMessage) -> String {
+        PayloadParser::from_message(&captured.message()).read_string().unwrap().to_string()
+    }
+
+    /// Ethernet frame with an IPv4 packet carrying `segment` (protocol 6 or 17)
+    fn ethernet_ipv4(protocol: u8, src: [u8; 4], dst: [u8; 4], segment: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0u8; 12];
+        frame.extend([0x08, 0x00]);
+        frame.extend([0x45, 0x00]);
+        frame.extend((20 + segment.len() as u16).to_be_bytes());
+        frame.extend([0, 0, 0x40, 0x00, 64, protocol, 0, 0]);
+        frame.extend(src);
+        frame.extend(dst);
+        frame.extend(segment);
+        frame
+    }
+
+    fn udp(src_port: u16, dst_port: u16, payload: &[u8]) -> Vec<u8> {
+        let mut segment = Vec::new();
+        segment.extend(src_port.to_be_bytes());
+        segment.extend(dst_port.to_be_bytes());
+        segment.extend((8 + payload.len() as u16).to_be_bytes());
+        segment.extend([0, 0]);
+        segment.extend(payload);
+        segment
+    }
+
+    fn tcp(src_port: u16, dst_port: u16, seq: u32, flags: u8, payload: &[u8]) -> Vec<u8> {
+        let mut segment = Vec::new();
+        segment.extend(src_port.to_be_bytes());
+        segment.extend(dst_port.to_be_bytes());
+        segment.extend(seq.to_be_bytes());
+        segment.extend([0, 0, 0, 0, 0x50, flags, 0xFF, 0xFF, 0, 0, 0, 0]);
+        segment.extend(payload);
+        segment
+    }
+
+    /// Little-endian pcap file with microsecond timestamps
+    fn pcap(link_type: u32, packets: &[(u32, u32, Vec<u8>)]) -> Vec<u8> {
+        let mut file = Vec::new();
+        file.extend(0xA1B2_C3D4u32.to_le_bytes());
+        file.extend(2u16.to_le_bytes());
+        file.extend(4u16.to_le_bytes());
+        file.extend([0u8; 8]);
+        file.extend(65535u32.to_le_bytes());
+        file.extend(link_type.to_le_bytes());
+        for (seconds, micros, data) in packets {
+            file.extend(seconds.to_le_bytes());
+            file.extend(micros.to_le_bytes());
+            file.extend((data.len() as u32).to_le_bytes());
+            file.extend((data.len() as u32).to_le_bytes());
+            file.extend(data);
+        }
+        file
+    }
+
+    fn pcapng_block(block_type: u32, body: &[u8]) -> Vec<u8> {
+        let mut body = body.to_vec();
+        body.resize(body.len().next_multiple_of(4), 0);
+        let length = (body.len() + 12) as u32;
+        let mut block = Vec::new();
+        block.extend(block_type.to_le_bytes());
+        block.extend(length.to_le_bytes());
+        block.extend(body);
+        block.extend(length.to_le_bytes());
+        block
+    }
+
+    fn reader(file: Vec<u8>) -> DltPcapReader<Cursor<Vec<u8>>> {
+        DltPcapReader::new(Cursor::new(file)).unwrap()
+    }
+
+    #[test]
+    fn test_pcap_udp_messages() {
+        let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1");
+        let mut datagram = log_message(&mut builder, "one");
+        datagram.extend(log_message(&mut builder, "two"));
+
+        let packets = vec![
+            (1_700_000_000, 250_000, ethernet_ipv4(17, ECU, CLIENT, &udp(40000, 3490, &datagram))),
+            // Not a DLT port
+            (1_700_000_001, 0, ethernet_ipv4(17, ECU, CLIENT, &udp(40000, 5000, &datagram))),
+            // Configured additional port
+            (1_700_000_002, 0, ethernet_ipv4(17, ECU, CLIENT, &udp(40000, 3491, &log_message(&mut builder, "three")))),
+        ];
+        let messages: Vec<_> = reader(pcap(1, &packets))
+            .with_udp_port(3491)
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(messages.len(), 3);
+        assert_eq!(text(&messages[0]), "one");
+        assert_eq!(text(&messages[1]), "two");
+        assert_eq!(text(&messages[2]), "three");
+        assert_eq!(messages[0].transport, DltTransport::Udp);
+        assert_eq!(messages[0].source, SocketAddr::from((ECU, 40000)));
+        assert_eq!(messages[0].destination, SocketAddr::from((CLIENT, 3490)));
+        assert_eq!(messages[0].timestamp, Duration::new(1_700_000_000, 250_000_000));
+
+        let header = messages[0].storage_header();
+        assert_eq!(header.seconds, 1_700_000_000);
+        assert_eq!(header.microseconds, 250_000);
+        assert_eq!(&header.ecu_id, b"ECU1");
+    }
+
+    #[test]
+    fn test_pcap_tcp_reassembly() {
+        let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1");
+        let mut stream = log_message(&mut builder, "first");
+        stream.extend(log_message(&mut builder, "second message"));
+        let split = stream.len() / 2;
+
+        let segment = |seq: u32, flags: u8, data: &[u8]| ethernet_ipv4(6, ECU, CLIENT, &tcp(3490, 50000, seq, flags, data));
+        let packets = vec![
+            (10, 0, segment(1000, 0x02, &[])),
+            // Second half arrives first
+            (11, 0, segment(1001 + split as u32, 0x18, &stream[split..])),
+            (12, 0, segment(1001, 0x18, &stream[..split])),
+            // Retransmission of data already received
+            (13, 0, segment(1001, 0x18, &stream[..split])),
+            (14, 0, segment(1001 + stream.len() as u32, 0x11, &[])),
+        ];
+        let mut reader = reader(pcap(1, &packets));
+        let messages: Vec<_> = reader.by_ref().map(Result::unwrap).collect();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(text(&messages[0]), "first");
+        assert_eq!(text(&messages[1]), "second message");
+        assert_eq!(messages[1].transport, DltTransport::Tcp);
+        assert_eq!(messages[1].source, SocketAddr::from((ECU, 3490)));
+        // Completed by the in-order segment
+        assert_eq!(messages[1].timestamp, Duration::from_secs(12));
+        assert_eq!(reader.packet_count(), 5);
+        assert_eq!(reader.skipped_bytes(), 0);
+    }
+
+    #[test]
+    fn test_pcap_tcp_resync_mid_stream() {
+        let mut builder = DltMessageBuilder::new().add_serial_header();
+        let cut = log_message(&mut builder, "cut");
+        let mut stream = cut[5..].to_vec();
+        stream.extend(log_message(&mut builder, "whole"));
+
+        // Capture starts inside a connection, on a raw IP link
+        let ip = ethernet_ipv4(6, CLIENT, ECU, &tcp(50000, 3490, 7, 0x18, &stream))[14..].to_vec();
+        let messages: Vec<_> = reader(pcap(101, &[(1, 0, ip)])).map(Result::unwrap).collect();
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(text(&messages[0]), "whole");
+        assert!(messages[0].message().has_serial_header);
+    }
+
+    #[test]
+    fn test_pcap_serial_stream_to_dlt_file() {
+        let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1").add_serial_header();
+        let mut stream = log_message(&mut builder, "first");
+        stream.extend(log_message(&mut builder, "second"));
+
+        let packets = vec![(20, 250, ethernet_ipv4(6, ECU, CLIENT, &tcp(3490, 50000, 1, 0x18, &stream)))];
+        let path = std::env::temp_dir().join(format!("dlt_protocol_pcap_serial_{}.dlt", std::process::id()));
+        let mut writer = DltFileWriter::new(&path);
+        for captured in reader(pcap(1, &packets)) {
+            let captured = captured.unwrap();
+            assert!(captured.as_bytes().starts_with(&DLT_SERIAL_HEADER_ARRAY));
+            writer.write_stored(&captured.storage_header(), captured.as_bytes()).unwrap();
+        }
+        drop(writer);
+
+        let data = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(!data.windows(DLT_SERIAL_HEADER_SIZE).any(|w| w == DLT_SERIAL_HEADER_ARRAY));
+
+        let records: Vec<_> = DltFileReader::new(&data).collect::<Result<_, _>>().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].storage_header.seconds, 20);
+        assert_eq!(records[0].storage_header.microseconds, 250);
+        assert_eq!(records[1].storage_header.ecu_id, *b"ECU1");
+        assert!(!records[1].message.has_serial_header);
+        assert_eq!(PayloadParser::from_message(&records[0].message).read_string().unwrap(), "first");
+        assert_eq!(PayloadParser::from_message(&records[1].message).read_string().unwrap(), "second");
+    }
+
+    #[test]
+    fn test_pcapng_timestamp_resolution() {
+        let mut builder = DltMessageBuilder::new();
+        let datagram = log_message(&mut builder, "pcapng");
+
+        let mut file = Vec::new();
+        let mut section = Vec::new();
+        section.extend(0x1A2B_3C4Du32.to_le_bytes());
+        section.extend(1u16.to_le_bytes());
+        section.extend(0u16.to_le_bytes());
+        section.extend((-1i64).to_le_bytes());
+        file.extend(pcapng_block(0x0A0D_0D0A, &section));
+
+        // Ethernet interface with nanosecond resolution (if_tsresol = 9)
+        let mut interface = Vec::new();
+        interface.extend(1u16.to_le_bytes());
+        interface.extend(0u16.to_le_bytes());
+        interface.extend(0u32.to_le_bytes());
+        interface.extend(9u16.to_le_bytes());
+        interface.extend(1u16.to_le_bytes());
+        interface.extend([9, 0, 0, 0]);
+        interface.extend([0u8; 4]);
+        file.extend(pcapng_block(1, &interface));
+
+        let frame = ethernet_ipv4(17, ECU, CLIENT, &udp(3490, 3490, &datagram));
+        let timestamp: u64 = 1_700_000_000_123_456_789;
+        let mut packet = Vec::new();
+        packet.extend(0u32.to_le_bytes());
+        packet.extend(((timestamp >> 32) as u32).to_le_bytes());
+        packet.extend((timestamp as u32).to_le_bytes());
+        packet.extend((frame.len() as u32).to_le_bytes());
+        packet.extend((frame.len() as u32).to_le_bytes());
+        packet.extend(&frame);
+        // Unknown blocks are skipped
+        file.extend(pcapng_block(5, &[0u8; 8]));
+        file.extend(pcapng_block(6, &packet));
+
+        let messages: Vec<_> = reader(file).map(Result::unwrap).collect();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(text(&messages[0]), "pcapng");
+        assert_eq!(messages[0].timestamp, Duration::new(1_700_000_000, 123_456_789));
+    }
+
+    #[test]
+    fn test_pcap_errors() {
+        assert!(matches!(
+            DltPcapReader::new(Cursor::new(vec![0u8; 24])),
+            Err(DltPcapError::InvalidFormat(_))
+        ));
+
+        let mut builder = DltMessageBuilder::new();
+        let message = log_message(&mut builder, "truncated");
+        let packets = vec![
+            (1, 0, ethernet_ipv4(17, ECU, CLIENT, &udp(3490, 3490, &message[..message.len() - 3]))),
+            (2, 0, ethernet_ipv4(17, ECU, CLIENT, &udp(3490, 3490, &message))),
+        ];
+        let mut file = pcap(1, &packets);
+        // File ends inside a packet record
+        file.extend([0u8; 10]);
+
+        let mut reader = reader(file);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(DltPcapError::Datagram {
+                error: DltDatagramError::Truncated { offset: 0 },
+                ..
+            }))
+        ));
+        assert_eq!(text(&reader.next().unwrap().unwrap()), "truncated");
+        assert!(matches!(reader.next(), Some(Err(DltPcapError::Io(_)))));
+        assert!(reader.next().is_none());
+    }
+}
+
+#[cfg(feature = "std")]
+mod owned_message_tests {
+    use dlt_protocol::r19_11::*;
+
+    fn log_message(builder: &mut DltMessageBuilder, text: &str) -> Vec<u8> {
+        let mut buffer = [0u8; 256];
+        let size = builder
+            .generate_log_message_with_payload(&mut buffer, text.as_bytes(), MtinTypeDltLog::DltLogInfo, 1, true)
+            .unwrap();
+        buffer[..size].to_vec()
+    }
+
+    #[test]
+    fn test_owned_message_roundtrip() {
+        let mut builder = DltMessageBuilder::new()
+            .with_ecu_id(b"ECU1")
+            .with_session_id(7)
+            .with_timestamp(12345)
+            .add_serial_header();
+        let bytes = log_message(&mut builder, "owned");
+
+        let owned = {
+            let received = bytes.clone();
+            let message = DltHeaderParser::new(&received).parse_message().unwrap();
+            DltOwnedMessage::from(&message)
+        };
+        assert_eq!(owned.to_bytes().unwrap(), bytes);
+        assert_eq!(owned.encoded_len(), bytes.len());
+        assert_eq!(DltOwnedMessage::parse(&bytes).unwrap(), owned);
+
+        let view = owned.as_message();
+        assert_eq!(view.standard_header.len as usize, bytes.len() - DLT_SERIAL_HEADER_SIZE);
+        assert_eq!(PayloadParser::from_message(&view).read_string().unwrap(), "owned");
+    }
+
+    #[test]
+    fn test_owned_message_modify() {
+        let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1");
+        let mut message = DltOwnedMessage::parse(&log_message(&mut builder, "before")).unwrap();
+
+        message.ecu_id = None;
+        message.timestamp = Some(99);
+        message.extended_header.as_mut().unwrap().ctid = *b"FWD1";
+        message.payload = DltOwnedMessage::parse(&log_message(&mut builder, "after, longer"))
+            .unwrap()
+            .payload;
+
+        let bytes = message.to_bytes().unwrap();
+        let parsed = DltHeaderParser::new(&bytes).parse_message().unwrap();
+        assert_eq!(parsed.ecu_id, None);
+        assert_eq!(parsed.timestamp, Some(99));
+        assert_eq!(&parsed.extended_header.unwrap().ctid, b"FWD1");
+        assert_eq!(parsed.standard_header.len as usize, bytes.len());
+        assert_eq!(parsed.header_type, message.as_message().header_type);
+        assert_eq!(PayloadParser::from_message(&parsed).read_string().unwrap(), "after, longer");
+
+        message.payload = vec![0; 70000];
+        assert!(matches!(message.to_bytes(), Err(DltError::InvalidParameter)));
+    }
+
+    #[test]
+    fn test_owned_message_file_roundtrip() {
+        let mut builder = DltMessageBuilder::new().with_ecu_id(b"ECU1").with_timestamp(1);
+        let mut file = Vec::new();
+        for text in ["first", "second", "third"] {
+            let mut header = [0u8; DLT_STORAGE_HEADER_SIZE];
+            DltStorageHeader::new(1_700_000_000, 0, b"ECU1").write(&mut header).unwrap();
+            file.extend(header);
+            file.extend(log_message(&mut builder, text));
+        }
+
+        // Re-encoding every stored message reproduces the file
+        let mut rewritten = Vec::new();
+        for stored in DltFileReader::new(&file) {
+            let stored = stored.unwrap();
+            let mut header = [0u8; DLT_STORAGE_HEADER_SIZE];
+            stored.storage_header.write(&mut header).unwrap();
+            rewritten.extend(header);
+            rewritten.extend(DltOwnedMessage::from(stored.message).to_bytes().unwrap());
+        }
+        assert_eq!(rewritten, file);
+    }
+}
+
+#[cfg(feature = "std")]
+mod time_sync_tests {
+    use dlt_protocol::r19_11::*;
+    use std::time::Duration;
+
+    fn sync_response(ecu_id: &[u8; 4], session_id: u32, uptime: u32, seconds: u64, nanoseconds: u32) -> Vec<u8> {
+        let mut builder = DltServiceMessageBuilder::new()
+            .with_ecu_id(ecu_id)
+            .with_session_id(session_id)
+            .with_timestamp(uptime);
+        builder.set_endian(DltEndian::Big);
+        let time_stamp = DltSyncTimeStamp {
+            time_base_status: DLT_TIME_BASE_GLOBAL_TIME_BASE,
+            seconds,
+            nanoseconds,
+        };
+        let mut buffer = [0u8; 64];
+        let size = builder
+            .generate_sync_time_stamp_response(&mut buffer, ServiceStatus::Ok, &time_stamp)
+            .unwrap();
+        buffer[..size].to_vec()
+    }
+
+    fn log_message(ecu_id: &[u8; 4], session_id: u32, uptime: u32) -> Vec<u8> {
+        let mut builder = DltMessageBuilder::new()
+            .with_ecu_id(ecu_id)
+            .with_session_id(session_id)
+            .with_timestamp(uptime);
+        let mut buffer = [0u8; 64];
+        let size = builder
+            .generate_log_message_with_payload(&mut buffer, b"log", MtinTypeDltLog::DltLogInfo, 1, true)
+            .unwrap();
+        buffer[..size].to_vec()
+    }
+
+    fn parse(bytes: &[u8]) -> DltMessage<'_> {
+        DltHeaderParser::new(bytes).parse_message().unwrap()
+    }
+
+    #[test]
+    fn test_time_correlation_per_ecu_and_session() {
+        let mut correlator = DltTimeCorrelator::new();
+
+        let before = log_message(b"ECU1", 1, 20_000);
+        assert!(!correlator.update(&parse(&before)));
+        assert_eq!(correlator.absolute_time(&parse(&before)), None);
+
+        // ECU1 session 1: uptime 10.0000s = 1_700_000_000.5s
+        let sync = sync_response(b"ECU1", 1, 100_000, 1_700_000_000, 500_000_000);
+        assert!(correlator.update(&parse(&sync)));
+        assert_eq!(
+            correlator.absolute_time(&parse(&log_message(b"ECU1", 1, 100_025))),
+            Some(Duration::new(1_700_000_000, 502_500_000))
+        );
+        assert_eq!(
+            correlator.absolute_time(&parse(&before)),
+            Some(Duration::new(1_699_999_992, 500_000_000))
+        );
+
+        // Other sessions of ECU1 fall back to the ECU-wide reference
+        assert_eq!(
+            correlator.absolute_time(&parse(&log_message(b"ECU1", 7, 110_000))),
+            Some(Duration::new(1_700_000_001, 500_000_000))
+        );
+        assert_eq!(correlator.absolute_time(&parse(&log_message(b"ECU2", 1, 110_000))), None);
+
+        // A session's own reference takes precedence
+        let sync = sync_response(b"ECU1", 7, 0, 1_800_000_000, 0);
+        assert!(correlator.update(&parse(&sync)));
+        assert_eq!(
+            correlator.absolute_time(&parse(&log_message(b"ECU1", 7, 10_000))),
+            Some(Duration::new(1_800_000_001, 0))
+        );
+        assert_eq!(
+            correlator.absolute_time(&parse(&log_message(b"ECU1", 1, 100_000))),
+            Some(Duration::new(1_700_000_000, 500_000_000))
+        );
+        assert_eq!(correlator.reference(Some(*b"ECU1"), Some(3)).unwrap().uptime, 0);
+
+        correlator.clear();
+        assert_eq!(correlator.absolute_time(&parse(&log_message(b"ECU1", 1, 100_000))), None);
+    }
+
+    #[test]
+    fn test_time_correlation_ignores_other_messages() {
+        let mut correlator = DltTimeCorrelator::new();
+        let mut buffer = [0u8; 64];
+
+        // Request and failed response carry no time
+        let mut builder = DltServiceMessageBuilder::new().with_ecu_id(b"ECU1").with_timestamp(1);
+        builder.set_endian(DltEndian::Big);
+        let size = builder.generate_sync_time_stamp_request(&mut buffer).unwrap();
+        assert!(!correlator.update(&parse(&buffer[..size])));
+        let size = builder
+            .generate_sync_time_stamp_response(&mut buffer, ServiceStatus::NotSupported, &DltSyncTimeStamp::default())
+            .unwrap();
+        assert!(!correlator.update(&parse(&buffer[..size])));
+
+        // Without a header timestamp there is no uptime to correlate
+        let size = builder
+            .generate_sync_time_stamp_response(&mut buffer, ServiceStatus::Ok, &DltSyncTimeStamp::default())
+            .unwrap();
+        let msg = DltMessageRewriter::new().without_timestamp().rewrite(&parse(&buffer[..size]));
+        assert!(!correlator.update(&msg));
+
+        // Log messages are not references
+        assert!(!correlator.update(&parse(&log_message(b"ECU1", 1, 1))));
+        assert!(correlator.reference(Some(*b"ECU1"), None).is_none());
+    }
+
+    #[test]
+    fn test_time_reference_uptime_wrap() {
+        let reference = DltTimeReference {
+            uptime: u32::MAX - 9,
+            time: Duration::from_secs(1_700_000_000),
+            time_base_status: DLT_TIME_BASE_GLOBAL_TIME_BASE,
+        };
+        // 10 ticks after the reference, across the wrap of the timestamp field
+        assert_eq!(reference.absolute_time(0), Some(Duration::new(1_700_000_000, 1_000_000)));
+        assert_eq!(reference.absolute_time(u32::MAX - 19), Some(Duration::new(1_699_999_999, 999_000_000)));
+
+        let early = DltTimeReference { time: Duration::ZERO, ..reference };
+        assert_eq!(early.absolute_time(u32::MAX - 10), None);
+    }
+}
@@ -0,0 +1,342 @@
+use dlt_protocol::r22_11::*;
+
+// ========================================
+// DLT v2 Builder Tests
+// ========================================
+
+#[test]
+fn test_v2_generate_verbose_log_message_layout() {
+    let mut builder = DltMessageBuilder::new()
+        .with_ecu_id(b"ECU1")
+        .with_app_id(b"APP1")
+        .with_context_id(b"CTX1")
+        .with_timestamp(0x01_0203_0405, 500);
+
+    let mut buffer = [0u8; 256];
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, b"Hi", MtinTypeDltLog::DltLogWarn, 1, true)
+        .unwrap();
+
+    // HTYP2: CNTI=0, WEID, WACID, version 2
+    let htyp2 = u32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]);
+    assert_eq!(htyp2 & CNTI_MASK, 0);
+    assert_eq!(htyp2 & WEID2_MASK, WEID2_MASK);
+    assert_eq!(htyp2 & WACID_MASK, WACID_MASK);
+    assert_eq!(htyp2 & VERS2_MASK, DLT2_VERSION);
+    assert_eq!(htyp2 & WSID2_MASK, 0);
+
+    assert_eq!(buffer[4], 0); // Message counter
+    assert_eq!(u16::from_be_bytes([buffer[5], buffer[6]]) as usize, size);
+
+    // MSIN + NOAR
+    assert_eq!(buffer[7], encode_msin(true, 0, 3));
+    assert_eq!(buffer[8], 1);
+
+    // TMSP2: nanoseconds then 40-bit seconds
+    assert_eq!(&buffer[9..13], &500u32.to_be_bytes());
+    assert_eq!(&buffer[13..18], &[0x01, 0x02, 0x03, 0x04, 0x05]);
+
+    // Length-prefixed IDs
+    assert_eq!(buffer[18], 4);
+    assert_eq!(&buffer[19..23], b"ECU1");
+    assert_eq!(buffer[23], 4);
+    assert_eq!(&buffer[24..28], b"APP1");
+    assert_eq!(buffer[28], 4);
+    assert_eq!(&buffer[29..33], b"CTX1");
+
+    // Payload is a verbose string argument
+    let mut parser = PayloadParser::new(&buffer[33..size]);
+    assert_eq!(parser.read_string().unwrap(), "Hi");
+}
+
+#[test]
+fn test_v2_roundtrip_all_extension_fields() {
+    let tags: [&[u8]; 2] = [b"power", b"boot"];
+    let mut builder = DltMessageBuilder::new()
+        .with_ecu_id(b"GATEWAY_ECU")
+        .with_app_id(b"POWERMGR")
+        .with_context_id(b"STATEMACHINE")
+        .with_session_id(0xCAFE)
+        .with_timestamp(1_700_000_000, 123_456_789)
+        .with_file_name(b"power.c", 42)
+        .with_tags(&tags)
+        .with_privacy_level(3)
+        .add_serial_header();
+
+    let mut buffer = [0u8; 256];
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, b"boot", MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap();
+
+    assert_eq!(&buffer[0..4], &DLT_SERIAL_HEADER_ARRAY);
+
+    let mut parser = DltHeaderParser::new(&buffer[..size]);
+    let msg = parser.parse_message().unwrap();
+
+    assert!(msg.has_serial_header);
+    assert!(msg.is_verbose());
+    assert_eq!(msg.header_type.vers, 2);
+    assert_eq!(msg.ecu_id, Some(&b"GATEWAY_ECU"[..]));
+    assert_eq!(msg.app_id, Some(&b"POWERMGR"[..]));
+    assert_eq!(msg.context_id, Some(&b"STATEMACHINE"[..]));
+    assert_eq!(msg.session_id, Some(0xCAFE));
+    assert_eq!(
+        msg.timestamp,
+        Some(DltTimestamp2 {
+            seconds: 1_700_000_000,
+            nanoseconds: 123_456_789
+        })
+    );
+    assert_eq!(msg.file_name, Some(&b"power.c"[..]));
+    assert_eq!(msg.line_number, Some(42));
+    assert_eq!(msg.privacy_level, Some(3));
+    assert_eq!(msg.segmentation, None);
+    assert_eq!(msg.noar, Some(1));
+    assert_eq!(msg.message_id, None);
+
+    let parsed_tags = msg.tags.unwrap();
+    assert_eq!(parsed_tags.len(), 2);
+    let mut iter = parsed_tags.iter();
+    assert_eq!(iter.next(), Some(&b"power"[..]));
+    assert_eq!(iter.next(), Some(&b"boot"[..]));
+    assert_eq!(iter.next(), None);
+
+    assert_eq!(msg.message_type(), Some(MstpType::DltTypeLog));
+    assert!(matches!(msg.log_level(), Some(MtinTypeDltLog::DltLogInfo)));
+
+    let mut payload_parser = PayloadParser::new(msg.payload);
+    assert_eq!(payload_parser.read_string().unwrap(), "boot");
+    assert_eq!(parser.remaining(), 0);
+}
+
+#[test]
+fn test_v2_non_verbose_message() {
+    let mut builder = DltMessageBuilder::new().with_timestamp(10, 20);
+
+    let mut buffer = [0u8; 128];
+    let size = builder
+        .generate_non_verbose_message(&mut buffer, 0x1234, &[1, 2, 3, 4], MtinTypeDltLog::DltLogDebug)
+        .unwrap();
+
+    let mut parser = DltHeaderParser::new(&buffer[..size]);
+    let msg = parser.parse_message().unwrap();
+
+    assert_eq!(msg.header_type.cnti, ContentInfo::NonVerbose);
+    assert!(!msg.is_verbose());
+    assert_eq!(msg.message_id, Some(0x1234));
+    assert_eq!(msg.noar, None);
+    assert_eq!(msg.timestamp, Some(DltTimestamp2 { seconds: 10, nanoseconds: 20 }));
+    assert!(matches!(msg.log_level(), Some(MtinTypeDltLog::DltLogDebug)));
+    assert_eq!(msg.payload, &[1, 2, 3, 4]);
+}
+
+#[test]
+fn test_v2_control_message() {
+    let mut builder = DltMessageBuilder::new().with_app_id(b"SYS").with_context_id(b"MGMT");
+
+    let mut buffer = [0u8; 128];
+    let payload = [0x13, 0x00, 0x00, 0x00];
+    let size = builder
+        .generate_control_message(&mut buffer, &payload, MtinTypeDltControl::DltControlRequest)
+        .unwrap();
+
+    let mut parser = DltHeaderParser::new(&buffer[..size]);
+    let msg = parser.parse_message().unwrap();
+
+    assert_eq!(msg.header_type.cnti, ContentInfo::Control);
+    assert_eq!(msg.timestamp, None);
+    assert_eq!(msg.noar, Some(0));
+    assert_eq!(msg.message_type(), Some(MstpType::DltTypeControl));
+    assert_eq!(msg.message_type_info(), Some(1));
+    assert!(msg.log_level().is_none());
+    assert_eq!(msg.payload, &payload);
+}
+
+#[test]
+fn test_v2_insert_header_at_front_with_payload_builder() {
+    let mut buffer = [0u8; 256];
+    let payload_len = {
+        let mut payload_builder = PayloadBuilder::new(&mut buffer);
+        payload_builder.add_u32(42).unwrap();
+        payload_builder.add_string("v2").unwrap();
+        payload_builder.len()
+    };
+
+    let mut builder = DltMessageBuilder::new().without_ecu_id();
+    let size = builder
+        .insert_header_at_front(&mut buffer, payload_len, 2, MtinTypeDltLog::DltLogError)
+        .unwrap();
+
+    let mut parser = DltHeaderParser::new(&buffer[..size]);
+    let msg = parser.parse_message().unwrap();
+
+    assert_eq!(msg.ecu_id, None);
+    assert_eq!(msg.noar, Some(2));
+    let mut payload_parser = PayloadParser::new(msg.payload);
+    assert_eq!(payload_parser.read_u32().unwrap(), 42);
+    assert_eq!(payload_parser.read_string().unwrap(), "v2");
+}
+
+#[test]
+fn test_v2_segmentation_frames_roundtrip() {
+    let mut builder = DltMessageBuilder::new();
+    let frames = [
+        DltSegmentation::FirstFrame { total_length: 70_000 },
+        DltSegmentation::ConsecutiveFrame { sequence: 7 },
+        DltSegmentation::LastFrame,
+        DltSegmentation::AbortFrame { reason: 2 },
+    ];
+
+    for frame in frames {
+        builder.set_segmentation(Some(frame));
+        let mut buffer = [0u8; 128];
+        let size = builder
+            .generate_log_message_with_payload(&mut buffer, b"seg", MtinTypeDltLog::DltLogInfo, 1, true)
+            .unwrap();
+
+        let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+        assert!(msg.header_type.wsgm);
+        assert_eq!(msg.segmentation, Some(frame));
+    }
+}
+
+#[test]
+fn test_v2_counter_increment() {
+    let mut builder = DltMessageBuilder::new().msg_counter(254);
+    let mut buffer = [0u8; 128];
+
+    for expected in [254u8, 255, 0] {
+        let size = builder
+            .generate_log_message_with_payload(&mut buffer, b"x", MtinTypeDltLog::DltLogInfo, 1, true)
+            .unwrap();
+        let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+        assert_eq!(msg.base_header.mcnt, expected);
+    }
+    assert_eq!(builder.get_counter(), 1);
+}
+
+#[test]
+fn test_v2_id_too_long() {
+    let long_id = [b'A'; 256];
+    let mut builder = DltMessageBuilder::new().with_ecu_id(&long_id);
+    let mut buffer = [0u8; 1024];
+
+    let result =
+        builder.generate_log_message_with_payload(&mut buffer, b"x", MtinTypeDltLog::DltLogInfo, 1, true);
+    assert_eq!(result, Err(DltError::InvalidParameter));
+}
+
+#[test]
+fn test_v2_buffer_too_small() {
+    let mut builder = DltMessageBuilder::new();
+    let mut buffer = [0u8; 20];
+
+    let result = builder.generate_non_verbose_message(&mut buffer, 1, &[0u8; 16], MtinTypeDltLog::DltLogInfo);
+    assert_eq!(result, Err(DltError::BufferTooSmall));
+}
+
+#[test]
+fn test_v2_trace_messages() {
+    let mut builder = DltMessageBuilder::new().with_app_id(b"APP1").with_context_id(b"CTX1");
+    let mut buffer = [0u8; 256];
+
+    let size = builder
+        .generate_app_trace_message(&mut buffer, &[], MtinTypeDltAppTrace::DltTraceState, 0, true)
+        .unwrap();
+    let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert_eq!(msg.message_type(), Some(MstpType::DltTypeAppTrace));
+    assert_eq!(msg.app_trace_type(), Some(MtinTypeDltAppTrace::DltTraceState));
+    assert_eq!(msg.log_level(), None);
+
+    let size = builder
+        .generate_network_trace_message(&mut buffer, MtinTypeDltNwTrace::DltNwTraceSomeip, &[1, 2], &[3], true)
+        .unwrap();
+    let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert_eq!(msg.nw_trace_type(), Some(MtinTypeDltNwTrace::DltNwTraceSomeip));
+    assert_eq!(msg.noar, Some(2));
+    assert_eq!(msg.payload, &[0, 0x04, 0, 0, 2, 0, 1, 2, 0, 0x04, 0, 0, 1, 0, 3]);
+    let mut parser = PayloadParser::new(msg.payload);
+    assert_eq!(parser.read_raw().unwrap(), &[1, 2]);
+    assert_eq!(parser.read_raw().unwrap(), &[3]);
+    let mut parser = PayloadParser::new(msg.payload);
+    assert_eq!(parser.read_next().unwrap(), DltValue::Raw(&[1, 2]));
+    assert_eq!(parser.read_next().unwrap(), DltValue::Raw(&[3]));
+
+    let size = builder
+        .generate_network_trace_message(&mut buffer, MtinTypeDltNwTrace::DltNwTraceCan, &[1, 2], &[3], false)
+        .unwrap();
+    let msg = DltHeaderParser::new(&buffer[..size]).parse_message().unwrap();
+    assert!(!msg.is_verbose());
+    assert_eq!(msg.payload, &[2, 0, 1, 2, 1, 0, 3]);
+}
+
+// ========================================
+// DLT v2 Parser Tests
+// ========================================
+
+#[test]
+fn test_v2_parse_rejects_version_1() {
+    // R19.11 standard header: HTYP with version 1
+    let buffer = [0x21, 0x00, 0x00, 0x04];
+    let result = DltHeaderParser::new(&buffer).parse_message();
+    assert_eq!(result.unwrap_err(), DltHeaderError::InvalidVersion);
+}
+
+#[test]
+fn test_v2_parse_reserved_content_info() {
+    let htyp2 = DLT2_VERSION | 0x3;
+    let mut buffer = [0u8; 16];
+    buffer[0..4].copy_from_slice(&htyp2.to_le_bytes());
+    buffer[5..7].copy_from_slice(&16u16.to_be_bytes());
+
+    let result = DltHeaderParser::new(&buffer).parse_message();
+    assert_eq!(result.unwrap_err(), DltHeaderError::InvalidHeaderType);
+}
+
+#[test]
+fn test_v2_parse_truncated_message() {
+    let mut builder = DltMessageBuilder::new();
+    let mut buffer = [0u8; 128];
+    let size = builder
+        .generate_log_message_with_payload(&mut buffer, b"truncated", MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap();
+
+    let result = DltHeaderParser::new(&buffer[..size - 1]).parse_message();
+    assert_eq!(result.unwrap_err(), DltHeaderError::BufferTooSmall);
+}
+
+#[test]
+fn test_v2_parse_multiple_messages_in_stream() {
+    let mut builder = DltMessageBuilder::new();
+    let mut buffer = [0u8; 256];
+    let first = builder
+        .generate_log_message_with_payload(&mut buffer, b"one", MtinTypeDltLog::DltLogInfo, 1, true)
+        .unwrap();
+    let second = builder
+        .generate_non_verbose_message(&mut buffer[first..], 9, &[0xAA], MtinTypeDltLog::DltLogInfo)
+        .unwrap();
+
+    let mut parser = DltHeaderParser::new(&buffer[..first + second]);
+    let msg1 = parser.parse_message().unwrap();
+    let msg2 = parser.parse_message().unwrap();
+
+    assert!(msg1.is_verbose());
+    assert_eq!(msg2.message_id, Some(9));
+    assert_eq!(msg2.payload, &[0xAA]);
+    assert_eq!(parser.remaining(), 0);
+}
+
+#[test]
+fn test_v2_htyp2_encode_decode() {
+    let htyp2 = DLT2_VERSION | WEID2_MASK | WSFLN_MASK | WPVL_MASK | 0x1;
+    let decoded = DltHTYP2::decode(htyp2);
+
+    assert_eq!(decoded.cnti, ContentInfo::NonVerbose);
+    assert!(decoded.weid);
+    assert!(!decoded.wacid);
+    assert!(decoded.wsfln);
+    assert!(decoded.wpvl);
+    assert!(!decoded.wtgs);
+    assert_eq!(decoded.vers, 2);
+    assert_eq!(decoded.encode(), htyp2);
+}
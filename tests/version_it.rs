@@ -0,0 +1,138 @@
+use dlt_protocol::r19_11;
+use dlt_protocol::r22_11;
+use dlt_protocol::{AnyDltMessage, DltAnyParser, DltTimestamp, DltVersion, detect_version, parse_any_message};
+use dlt_protocol::{DltHeaderError, MstpType, MtinTypeDltLog};
+
+fn build_v1(buffer: &mut [u8], serial: bool) -> usize {
+    let mut builder = r19_11::DltMessageBuilder::new()
+        .with_ecu_id(b"ECU1")
+        .with_app_id(b"APP1")
+        .with_context_id(b"CTX1")
+        .with_timestamp(1234);
+    if serial {
+        builder = builder.add_serial_header();
+    }
+    builder
+        .generate_log_message_with_payload(buffer, b"v1", MtinTypeDltLog::DltLogWarn, 1, true)
+        .unwrap()
+}
+
+fn build_v2(buffer: &mut [u8], serial: bool) -> usize {
+    let mut builder = r22_11::DltMessageBuilder::new()
+        .with_ecu_id(b"ECU2")
+        .with_app_id(b"APP2")
+        .with_context_id(b"CTX2")
+        .with_timestamp(100, 200);
+    if serial {
+        builder = builder.add_serial_header();
+    }
+    builder
+        .generate_log_message_with_payload(buffer, b"v2", MtinTypeDltLog::DltLogError, 1, true)
+        .unwrap()
+}
+
+#[test]
+fn test_detect_version() {
+    let mut buffer = [0u8; 128];
+
+    let size = build_v1(&mut buffer, false);
+    assert_eq!(detect_version(&buffer[..size]), Ok(DltVersion::V1));
+
+    let size = build_v2(&mut buffer, false);
+    assert_eq!(detect_version(&buffer[..size]), Ok(DltVersion::V2));
+
+    let size = build_v2(&mut buffer, true);
+    assert_eq!(detect_version(&buffer[..size]), Ok(DltVersion::V2));
+}
+
+#[test]
+fn test_detect_version_errors() {
+    assert_eq!(detect_version(&[]), Err(DltHeaderError::BufferTooSmall));
+    assert_eq!(detect_version(b"DLS\x01"), Err(DltHeaderError::BufferTooSmall));
+    assert_eq!(detect_version(&[0x60]), Err(DltHeaderError::InvalidVersion));
+    assert_eq!(detect_version(&[0x00]), Err(DltHeaderError::InvalidVersion));
+}
+
+#[test]
+fn test_parse_any_v1_accessors() {
+    let mut buffer = [0u8; 128];
+    let size = build_v1(&mut buffer, false);
+
+    let msg = parse_any_message(&buffer[..size]).unwrap();
+    assert_eq!(msg.version(), DltVersion::V1);
+    assert_eq!(msg.ecu_id(), Some(&b"ECU1"[..]));
+    assert_eq!(msg.app_id(), Some(&b"APP1"[..]));
+    assert_eq!(msg.context_id(), Some(&b"CTX1"[..]));
+    assert_eq!(msg.timestamp(), Some(DltTimestamp::Uptime(1234)));
+    assert_eq!(msg.message_type(), Some(MstpType::DltTypeLog));
+    assert!(matches!(msg.log_level(), Some(MtinTypeDltLog::DltLogWarn)));
+    assert!(msg.is_verbose());
+    assert_eq!(msg.number_of_arguments(), Some(1));
+    assert!(msg.as_v1().is_some());
+    assert!(msg.as_v2().is_none());
+
+    let mut payload_parser = r19_11::PayloadParser::new(msg.payload());
+    assert_eq!(payload_parser.read_string().unwrap(), "v1");
+}
+
+#[test]
+fn test_parse_any_v2_accessors() {
+    let mut buffer = [0u8; 128];
+    let size = build_v2(&mut buffer, false);
+
+    let msg = parse_any_message(&buffer[..size]).unwrap();
+    assert_eq!(msg.version(), DltVersion::V2);
+    assert_eq!(msg.ecu_id(), Some(&b"ECU2"[..]));
+    assert_eq!(msg.app_id(), Some(&b"APP2"[..]));
+    assert_eq!(msg.context_id(), Some(&b"CTX2"[..]));
+    assert_eq!(
+        msg.timestamp(),
+        Some(DltTimestamp::Absolute {
+            seconds: 100,
+            nanoseconds: 200
+        })
+    );
+    assert!(matches!(msg.log_level(), Some(MtinTypeDltLog::DltLogError)));
+    assert!(msg.is_verbose());
+    assert!(matches!(msg, AnyDltMessage::V2(_)));
+
+    let mut payload_parser = r22_11::PayloadParser::new(msg.payload());
+    assert_eq!(payload_parser.read_string().unwrap(), "v2");
+}
+
+#[test]
+fn test_any_parser_mixed_stream() {
+    let mut buffer = [0u8; 512];
+    let mut size = build_v1(&mut buffer, false);
+    size += build_v2(&mut buffer[size..], true);
+    size += build_v1(&mut buffer[size..], true);
+    size += build_v2(&mut buffer[size..], false);
+
+    let mut parser = DltAnyParser::new(&buffer[..size]);
+    let mut versions = [None; 4];
+    for slot in versions.iter_mut() {
+        let msg = parser.parse_message().unwrap();
+        *slot = Some((msg.version(), msg.has_serial_header()));
+    }
+
+    assert_eq!(
+        versions,
+        [
+            Some((DltVersion::V1, false)),
+            Some((DltVersion::V2, true)),
+            Some((DltVersion::V1, true)),
+            Some((DltVersion::V2, false)),
+        ]
+    );
+    assert_eq!(parser.remaining(), 0);
+    assert_eq!(parser.position(), size);
+}
+
+#[test]
+fn test_any_parser_error_does_not_advance() {
+    let buffer = [0x80u8, 0, 0, 4];
+    let mut parser = DltAnyParser::new(&buffer);
+
+    assert_eq!(parser.parse_message().unwrap_err(), DltHeaderError::InvalidVersion);
+    assert_eq!(parser.position(), 0);
+}